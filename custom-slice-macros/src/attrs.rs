@@ -2,10 +2,23 @@
 
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Attribute, Expr, Ident, ItemFn, Lit, Meta, MetaNameValue, NestedMeta, Type};
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    token, Attribute, Expr, Ident, ItemFn, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path,
+    Token, Type,
+};
+
+use crate::{codegen::props::Safety, defs::FnStub};
+
+pub(crate) use self::schema::{suggest, DidYouMean, FnShape, ItemSchema};
+
+mod schema;
 
 /// Special item types.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum SpecialItemType {
     /// Slice type definition.
     SliceType,
@@ -13,28 +26,76 @@ pub(crate) enum SpecialItemType {
     OwnedType,
     /// Validator function definition.
     Validator,
+    /// Normalizer function definition.
+    Normalizer,
 }
 
 impl SpecialItemType {
     fn from_ident(ident: &Ident) -> Option<Self> {
-        if ident == "slice" {
+        if key_is(ident, "slice") {
             Some(SpecialItemType::SliceType)
-        } else if ident == "owned" {
+        } else if key_is(ident, "owned") {
             Some(SpecialItemType::OwnedType)
-        } else if ident == "validator" {
+        } else if key_is(ident, "validator") {
             Some(SpecialItemType::Validator)
+        } else if key_is(ident, "normalizer") {
+            Some(SpecialItemType::Normalizer)
         } else {
             None
         }
     }
+
+    /// Returns the marker key name.
+    pub(crate) fn marker(self) -> &'static str {
+        match self {
+            SpecialItemType::SliceType => "slice",
+            SpecialItemType::OwnedType => "owned",
+            SpecialItemType::Validator => "validator",
+            SpecialItemType::Normalizer => "normalizer",
+        }
+    }
+
+    /// Returns the human-readable name of the item kind.
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            SpecialItemType::SliceType => "slice types",
+            SpecialItemType::OwnedType => "owned types",
+            SpecialItemType::Validator => "validators",
+            SpecialItemType::Normalizer => "normalizers",
+        }
+    }
+
+    /// Returns the attribute schema for the item kind.
+    pub(crate) fn schema(self) -> &'static ItemSchema {
+        match self {
+            SpecialItemType::SliceType => &schema::SLICE,
+            SpecialItemType::OwnedType => &schema::OWNED,
+            SpecialItemType::Validator => &schema::VALIDATOR,
+            SpecialItemType::Normalizer => &schema::NORMALIZER,
+        }
+    }
 }
 
+/// Checks whether the given identifier is the given key.
+///
+/// Raw identifiers (such as `r#type`) are compared without `r#` prefix.
+pub(crate) fn key_is(ident: &Ident, name: &str) -> bool {
+    ident.unraw() == name
+}
+
+/// Type arguments of derive targets, with the target names.
+type DeriveTypes = Vec<(Ident, Vec<Type>)>;
+
 /// Meta for custom slice items.
 pub(crate) struct CustomSliceAttrs {
     /// Custom meta.
     pub(crate) custom_meta: Vec<NestedMeta>,
     /// Raw attributes (not for custom-slice).
     pub(crate) raw: Vec<Attribute>,
+    /// Type arguments of derive targets, such as `AsRefVia(Path, OsStr)`.
+    derive_types: DeriveTypes,
+    /// Errors for malformed `#[custom_slice ..]` attributes.
+    malformed: Vec<syn::Error>,
 }
 
 impl CustomSliceAttrs {
@@ -48,16 +109,41 @@ impl CustomSliceAttrs {
                 }
             }
         }
+        // `validator(..)` is also a marker for validators (but not for slice
+        // types with marker words).
+        if self.validator_spec().is_some() {
+            return Some(SpecialItemType::Validator);
+        }
 
         None
     }
 
+    /// Checks whether no `#[custom_slice(..)]` attributes are specified.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.custom_meta.is_empty() && self.malformed.is_empty()
+    }
+
+    /// Returns errors for malformed `#[custom_slice ..]` attributes.
+    pub(crate) fn malformed(&self) -> &[syn::Error] {
+        &self.malformed
+    }
+
+    /// Checks whether `validator(owned)` is specified.
+    pub(crate) fn is_owned_validator(&self) -> bool {
+        self.validator_spec().map_or(false, |list| {
+            list.nested.iter().any(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::Word(ident)) => key_is(ident, "owned"),
+                _ => false,
+            })
+        })
+    }
+
     /// Returns `[foo, bar, ..]` of `#[custom_slice(name(foo, bar, ..))]`.
     fn lists<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a NestedMeta> + 'a {
         self.custom_meta
             .iter()
             .filter_map(move |nested_meta| match nested_meta {
-                NestedMeta::Meta(Meta::List(list)) if list.ident == name => Some(list),
+                NestedMeta::Meta(Meta::List(list)) if key_is(&list.ident, name) => Some(list),
                 _ => None,
             })
             .flat_map(|list| &list.nested)
@@ -73,8 +159,8 @@ impl CustomSliceAttrs {
             })
     }
 
-    /// Checks whether `#[repr(transparent)]` or `#[repr(C)]` is specified.
-    pub(crate) fn is_repr_transparent_or_c(&self) -> bool {
+    /// Returns an iterator of `#[repr(..)]` hints.
+    fn repr_hints<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
         self.raw
             .iter()
             .filter_map(|attr| attr.parse_meta().ok())
@@ -88,9 +174,41 @@ impl CustomSliceAttrs {
                 NestedMeta::Meta(Meta::Word(ident)) => Some(ident),
                 _ => None,
             })
+    }
+
+    /// Checks whether `#[repr(transparent)]` or `#[repr(C)]` is specified.
+    pub(crate) fn is_repr_transparent_or_c(&self) -> bool {
+        self.repr_hints()
             .any(|ident| ident == "transparent" || ident == "C")
     }
 
+    /// Checks whether `#[repr(transparent)]` is specified.
+    pub(crate) fn is_repr_transparent(&self) -> bool {
+        self.repr_hints().any(|ident| ident == "transparent")
+    }
+
+    /// Checks whether the trait is derived by the standard `#[derive(..)]`.
+    ///
+    /// The trait is recognized by the last path segment, so both
+    /// `#[derive(Hash)]` and `#[derive(::core::hash::Hash)]` are detected.
+    pub(crate) fn has_std_derive(&self, name: &str) -> bool {
+        let parser = |input: ParseStream<'_>| {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::<Path, Token![,]>::parse_terminated(&content)
+        };
+        self.raw
+            .iter()
+            .filter(|attr| attr.path.is_ident("derive"))
+            .filter_map(|attr| parser.parse2(attr.tts.clone()).ok())
+            .flatten()
+            .any(|path| {
+                path.segments
+                    .last()
+                    .map_or(false, |segment| segment.value().ident == name)
+            })
+    }
+
     /// Returns an iterator of identifiers to be `derive`d.
     pub(crate) fn derives<'a>(&'a self) -> impl Iterator<Item = &'a Ident> + 'a {
         self.lists("derive")
@@ -100,10 +218,231 @@ impl CustomSliceAttrs {
             })
     }
 
+    /// Returns the type arguments of the derive target.
+    ///
+    /// `Some(&[A, B])` for `derive(Target(A, B))`, and `None` for
+    /// `derive(Target)` or if the target is not specified.
+    pub(crate) fn derive_types(&self, target: &str) -> Option<&[Type]> {
+        self.derive_types
+            .iter()
+            .find(|(ident, _)| key_is(ident, target))
+            .map(|(_, types)| types.as_slice())
+    }
+
+    /// Returns the identifier of the first key with the given name.
+    pub(crate) fn find_key<'a>(&'a self, name: &'a str) -> Option<&'a Ident> {
+        self.custom_meta
+            .iter()
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(meta) => Some(meta),
+                NestedMeta::Literal(_) => None,
+            })
+            .map(|meta| match meta {
+                Meta::Word(ident) => ident,
+                Meta::List(list) => &list.ident,
+                Meta::NameValue(nv) => &nv.ident,
+            })
+            .find(|ident| key_is(ident, name))
+    }
+
+    /// Returns `validator(..)` meta.
+    pub(crate) fn validator_spec(&self) -> Option<&MetaList> {
+        self.custom_meta
+            .iter()
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::List(list)) if key_is(&list.ident, "validator") => {
+                    Some(list)
+                }
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the path to the validator function.
+    ///
+    /// `Ok(Some((key, path)))` for `validator = "path"`.
+    pub(crate) fn get_validator_path(&self) -> Result<Option<(&Ident, Path)>, syn::Error> {
+        self.namevalues()
+            .filter(|nv| key_is(&nv.ident, "validator"))
+            .filter_map(|nv| match nv.lit {
+                Lit::Str(ref s) => Some(s.parse::<Path>().map(|path| (&nv.ident, path))),
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns `validators(..)` meta.
+    pub(crate) fn validators_spec(&self) -> Option<&MetaList> {
+        self.custom_meta
+            .iter()
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::List(list)) if key_is(&list.ident, "validators") => {
+                    Some(list)
+                }
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the error mapping of the validator.
+    ///
+    /// `Ok(Some((key, expr)))` for `validator(map = "expr")`.
+    pub(crate) fn get_validator_map(&self) -> Result<Option<(&Ident, Expr)>, syn::Error> {
+        self.validator_spec()
+            .into_iter()
+            .flat_map(|list| &list.nested)
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, "map") => match nv.lit {
+                    Lit::Str(ref s) => Some(s.parse::<Expr>().map(|expr| (&nv.ident, expr))),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns the function to get the length of the valid prefix from the
+    /// validator error.
+    ///
+    /// `Ok(Some((key, expr)))` for `valid_up_to = "expr"`.
+    pub(crate) fn get_valid_up_to(&self) -> Result<Option<(&Ident, Expr)>, syn::Error> {
+        self.namevalues()
+            .filter(|nv| key_is(&nv.ident, "valid_up_to"))
+            .filter_map(|nv| match nv.lit {
+                Lit::Str(ref s) => Some(s.parse::<Expr>().map(|expr| (&nv.ident, expr))),
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns the function to repair invalid values generated by fuzzers.
+    ///
+    /// `Ok(Some((key, expr)))` for `fuzz_repair = "expr"`.
+    pub(crate) fn get_fuzz_repair(&self) -> Result<Option<(&Ident, Expr)>, syn::Error> {
+        self.namevalues()
+            .filter(|nv| key_is(&nv.ident, "fuzz_repair"))
+            .filter_map(|nv| match nv.lit {
+                Lit::Str(ref s) => Some(s.parse::<Expr>().map(|expr| (&nv.ident, expr))),
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    fn get_json_schema_conf<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Lit> + 'a {
+        self.lists("json_schema")
+            .filter_map(move |nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, key) => Some(&nv.lit),
+                _ => None,
+            })
+    }
+
+    /// Returns the description of `json_schema(description = "..")`.
+    pub(crate) fn get_json_schema_description(&self) -> Option<String> {
+        self.get_json_schema_conf("description")
+            .filter_map(|lit| match lit {
+                Lit::Str(ref s) => Some(s.value()),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the schema function of `json_schema(with = "path::to::schema")`.
+    pub(crate) fn get_json_schema_with(&self) -> Result<Option<Expr>, syn::Error> {
+        self.get_json_schema_conf("with")
+            .filter_map(|lit| match lit {
+                Lit::Str(ref s) => Some(s.parse::<Expr>()),
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns the incremental validator type.
+    ///
+    /// `Ok(Some((key, ty)))` for `incremental_validator = "Type"`.
+    pub(crate) fn get_incremental_validator(&self) -> Result<Option<(&Ident, Type)>, syn::Error> {
+        self.namevalues()
+            .filter(|nv| key_is(&nv.ident, "incremental_validator"))
+            .filter_map(|nv| match nv.lit {
+                Lit::Str(ref s) => Some(s.parse::<Type>().map(|ty| (&nv.ident, ty))),
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns the `Arbitrary` backend configuration.
+    ///
+    /// `Ok(Some((key, None)))` for `arbitrary(backend)`, and
+    /// `Ok(Some((key, Some(expr))))` for `arbitrary(backend = "expr")`.
+    pub(crate) fn get_arbitrary_backend<'a>(
+        &'a self,
+        backend: &'a str,
+    ) -> Result<Option<(&'a Ident, Option<Expr>)>, syn::Error> {
+        self.lists("arbitrary")
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::Word(ident)) if key_is(ident, backend) => {
+                    Some(Ok((ident, None)))
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, backend) => {
+                    match nv.lit {
+                        Lit::Str(ref s) => {
+                            Some(s.parse::<Expr>().map(|expr| (&nv.ident, Some(expr))))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns the key of the backend in `equivalent(..)`, if specified.
+    pub(crate) fn get_equivalent_backend<'a>(&'a self, backend: &'a str) -> Option<&'a Ident> {
+        self.lists("equivalent")
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::Word(ident)) if key_is(ident, backend) => Some(ident),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the key of the backend in `sql(..)`, if specified.
+    pub(crate) fn get_sql_backend<'a>(&'a self, backend: &'a str) -> Option<&'a Ident> {
+        self.lists("sql")
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::Word(ident)) if key_is(ident, backend) => Some(ident),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the maximum number of retries to generate a valid value.
+    ///
+    /// `Some((key, n))` for `arbitrary(max_retries = n)`.
+    pub(crate) fn get_arbitrary_max_retries(&self) -> Option<(&Ident, u64)> {
+        self.lists("arbitrary")
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, "max_retries") => {
+                    match nv.lit {
+                        Lit::Int(ref lit) => Some((&nv.ident, lit.value())),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .next()
+    }
+
     /// Returns value part of name-value meta.
     fn get_nv_value<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Lit> + 'a {
         self.namevalues()
-            .filter(move |nv| nv.ident == name)
+            .filter(move |nv| key_is(&nv.ident, name))
             .map(|nv| &nv.lit)
     }
 
@@ -119,7 +458,7 @@ impl CustomSliceAttrs {
     fn get_error_conf<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Lit> + 'a {
         self.lists("error")
             .filter_map(move |nested_meta| match nested_meta {
-                NestedMeta::Meta(Meta::NameValue(nv)) if nv.ident == key => Some(&nv.lit),
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, key) => Some(&nv.lit),
                 _ => None,
             })
     }
@@ -134,6 +473,44 @@ impl CustomSliceAttrs {
             .transpose()
     }
 
+    /// Returns the name of the error type to be generated.
+    ///
+    /// `Ok(Some((key, name)))` for `error(generate = "name")`.
+    pub(crate) fn get_generated_error(&self) -> Result<Option<(&Ident, Ident)>, syn::Error> {
+        self.lists("error")
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, "generate") => {
+                    match nv.lit {
+                        Lit::Str(ref s) => Some(s.parse::<Ident>().map(|name| (&nv.ident, name))),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .next()
+            .transpose()
+    }
+
+    /// Returns the identifier of the key in `error(..)`.
+    pub(crate) fn find_error_key<'a>(&'a self, name: &'a str) -> Option<&'a Ident> {
+        self.lists("error")
+            .filter_map(|nested_meta| match nested_meta {
+                NestedMeta::Meta(meta) => Some(meta),
+                NestedMeta::Literal(_) => None,
+            })
+            .map(|meta| match meta {
+                Meta::Word(ident) => ident,
+                Meta::List(list) => &list.ident,
+                Meta::NameValue(nv) => &nv.ident,
+            })
+            .find(|ident| key_is(ident, name))
+    }
+
+    /// Checks whether `error(type = "...")` or `error(map = "...")` is specified.
+    pub(crate) fn has_error_type_or_map(&self) -> bool {
+        self.find_error_key("type").is_some() || self.find_error_key("map").is_some()
+    }
+
     pub(crate) fn get_mapped_error(
         &self,
         error_var: impl ToTokens,
@@ -164,37 +541,114 @@ impl From<Vec<Attribute>> for CustomSliceAttrs {
     fn from(attrs: Vec<Attribute>) -> Self {
         let mut raw = Vec::new();
         let mut custom = Vec::new();
+        let mut derive_types = Vec::new();
+        let mut malformed = Vec::new();
         for attr in attrs {
-            let meta = match attr.parse_meta() {
-                Ok(v) => v,
-                Err(_) => {
-                    raw.push(attr);
-                    continue;
-                }
-            };
-            if let Meta::List(list) = meta {
-                if list.ident == "custom_slice" {
-                    custom.extend(list.nested);
-                    continue;
-                }
+            if !attr.path.is_ident("custom_slice") {
+                raw.push(attr);
+                continue;
+            }
+            match attr.parse_meta() {
+                Ok(Meta::List(_)) | Err(_) => match parse_custom_meta.parse2(attr.tts) {
+                    Ok((nested, types)) => {
+                        custom.extend(nested);
+                        derive_types.extend(types);
+                    }
+                    Err(e) => malformed.push(e),
+                },
+                Ok(meta) => malformed.push(syn::Error::new_spanned(
+                    meta,
+                    "expected `#[custom_slice(..)]`",
+                )),
             }
-            raw.push(attr);
         }
 
         Self {
             custom_meta: custom,
             raw,
+            derive_types,
+            malformed,
         }
     }
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct FnPrefix {
+/// Parses `(meta, ..)` of `#[custom_slice(meta, ..)]`.
+///
+/// Derive targets can take types as arguments (such as
+/// `derive(AsRefVia(Path, [u8]))`), which are not valid as meta items.
+/// They are returned separately, and the targets are left as words.
+fn parse_custom_meta(input: ParseStream<'_>) -> syn::Result<(Vec<NestedMeta>, DeriveTypes)> {
+    let content;
+    parenthesized!(content in input);
+    let mut nested = Vec::new();
+    let mut derive_types = Vec::new();
+    while !content.is_empty() {
+        let fork = content.fork();
+        let is_derive = fork
+            .call(Ident::parse_any)
+            .map(|ident| key_is(&ident, "derive"))
+            .unwrap_or(false)
+            && fork.peek(token::Paren);
+        if is_derive {
+            let ident = content.call(Ident::parse_any)?;
+            let list;
+            let paren_token = parenthesized!(list in content);
+            let mut targets = Punctuated::new();
+            while !list.is_empty() {
+                let target = list.call(Ident::parse_any)?;
+                if list.peek(token::Paren) {
+                    let types;
+                    parenthesized!(types in list);
+                    let types = Punctuated::<Type, Token![,]>::parse_terminated(&types)?;
+                    derive_types.push((target.clone(), types.into_iter().collect()));
+                }
+                targets.push_value(NestedMeta::Meta(Meta::Word(target)));
+                if list.is_empty() {
+                    break;
+                }
+                targets.push_punct(list.parse()?);
+            }
+            nested.push(NestedMeta::Meta(Meta::List(MetaList {
+                ident,
+                paren_token,
+                nested: targets,
+            })));
+        } else {
+            nested.push(content.parse()?);
+        }
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+    Ok((nested, derive_types))
+}
+
+/// Generated function declaration.
+#[derive(Clone)]
+pub(crate) enum FnPrefix {
     /// Function definition without `(args...) -> Type { body }` part.
-    prefix: String,
+    ///
+    /// For example, `pub unsafe fn new_unchecked`.
+    Str(String),
+    /// Function declaration without body.
+    ///
+    /// For example, `pub unsafe fn new_unchecked(v: &str) -> &Self;`.
+    Stub(Box<FnStub>),
 }
 
 impl FnPrefix {
+    /// Returns the safety of the function.
+    pub(crate) fn safety(&self) -> Result<Safety, syn::Error> {
+        match self {
+            FnPrefix::Str(prefix) => {
+                let item = syn::parse_str::<ItemFn>(&format!("{}() {{}}", prefix))?;
+                Ok(Safety::from(&item.unsafety))
+            }
+            FnPrefix::Stub(stub) => Ok(stub.safety()),
+        }
+    }
+
     pub(crate) fn build_item_with_named_arg(
         &self,
         arg_name: impl ToTokens,
@@ -202,22 +656,61 @@ impl FnPrefix {
         ty_ret: impl ToTokens,
         body_expr: impl ToTokens,
     ) -> Result<ItemFn, syn::Error> {
-        self.build_item_with_raw_args(quote!(#arg_name: #ty_arg), ty_ret, body_expr)
+        match self {
+            FnPrefix::Str(prefix) => {
+                build_item_with_raw_args(prefix, quote!(#arg_name: #ty_arg), ty_ret, body_expr)
+            }
+            FnPrefix::Stub(stub) => stub.build_item_with_named_arg(arg_name, ty_arg, body_expr),
+        }
+    }
+
+    pub(crate) fn build_item_with_receiver(
+        &self,
+        shape: FnShape,
+        ty_ret: impl ToTokens,
+        body_expr: impl ToTokens,
+    ) -> Result<ItemFn, syn::Error> {
+        match self {
+            FnPrefix::Str(prefix) => {
+                build_item_with_raw_args(prefix, shape.receiver(), ty_ret, body_expr)
+            }
+            FnPrefix::Stub(stub) => stub.build_item_with_receiver(shape, body_expr),
+        }
     }
 
-    pub(crate) fn build_item_with_raw_args(
+    pub(crate) fn build_item_with_receiver_and_arg(
         &self,
-        raw_args: impl ToTokens,
+        shape: FnShape,
+        arg_name: impl ToTokens,
+        ty_arg: impl ToTokens,
         ty_ret: impl ToTokens,
         body_expr: impl ToTokens,
     ) -> Result<ItemFn, syn::Error> {
-        let following = quote!((#raw_args) -> #ty_ret { #body_expr });
-        syn::parse_str::<ItemFn>(&format!("{}{}", self.prefix, following.to_string()))
+        match self {
+            FnPrefix::Str(prefix) => {
+                let receiver = shape.receiver();
+                let raw_args = quote!(#receiver, #arg_name: #ty_arg);
+                build_item_with_raw_args(prefix, raw_args, ty_ret, body_expr)
+            }
+            FnPrefix::Stub(stub) => {
+                stub.build_item_with_receiver_and_arg(shape, arg_name, ty_arg, body_expr)
+            }
+        }
     }
 }
 
 impl From<String> for FnPrefix {
     fn from(prefix: String) -> Self {
-        Self { prefix }
+        FnPrefix::Str(prefix)
     }
 }
+
+fn build_item_with_raw_args(
+    prefix: &str,
+    raw_args: impl ToTokens,
+    ty_ret: impl ToTokens,
+    body_expr: impl ToTokens,
+) -> Result<ItemFn, syn::Error> {
+    let following = quote!((#raw_args) -> #ty_ret { #body_expr });
+    syn::parse_str::<ItemFn>(&format!("{}{}", prefix, following))
+}
@@ -0,0 +1,774 @@
+//! Attribute schema.
+
+use std::fmt;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, Ident, Lit, Meta, NestedMeta};
+
+use crate::{
+    attrs::{CustomSliceAttrs, SpecialItemType},
+    codegen::props::{DynMutability, Mutability},
+};
+
+/// Expected value of an attribute key.
+#[derive(Debug, Clone, Copy)]
+enum ValueKind {
+    /// Key without value: `key`.
+    Flag,
+    /// String literal: `key = "..."`.
+    Str,
+    /// Integer literal: `key = 42`.
+    Int,
+    /// Key without value or string literal: `key` or `key = "..."`.
+    FlagOrStr,
+    /// Generated function: `key = "pub fn name"`, or a method declaration
+    /// with `#[custom_slice(key)]`.
+    Fn(FnShape),
+    /// Derive targets: `key(Foo, Bar)`.
+    Derives,
+    /// Nested name-value pairs: `key(foo = "...", bar = "...")`.
+    List(&'static [KeySchema]),
+    /// String literal or nested name-value pairs: `key = "..."` or
+    /// `key(foo = "...", ..)`.
+    StrOrList(&'static [KeySchema]),
+}
+
+/// Expected arguments of a generated function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FnShape {
+    /// A single argument: `(name: Type)`.
+    Arg,
+    /// `(&self)`.
+    RefSelf,
+    /// `(&mut self)`.
+    RefMutSelf,
+    /// `(self)`.
+    SelfValue,
+    /// `(&self, name: Type)`.
+    RefSelfArg,
+    /// `(&mut self, name: Type)`.
+    RefMutSelfArg,
+}
+
+impl FnShape {
+    /// Returns the shape for `&self` or `&mut self`.
+    pub(crate) fn ref_self(mutability: impl Mutability) -> Self {
+        match mutability.into() {
+            DynMutability::Constant => FnShape::RefSelf,
+            DynMutability::Mutable => FnShape::RefMutSelf,
+        }
+    }
+
+    /// Returns the receiver tokens.
+    pub(crate) fn receiver(self) -> TokenStream {
+        match self {
+            FnShape::Arg => unreachable!("Should never happen: `Arg` has no receiver"),
+            FnShape::RefSelf | FnShape::RefSelfArg => quote!(&self),
+            FnShape::RefMutSelf | FnShape::RefMutSelfArg => quote!(&mut self),
+            FnShape::SelfValue => quote!(self),
+        }
+    }
+}
+
+/// Schema of an attribute key.
+#[derive(Debug, Clone, Copy)]
+struct KeySchema {
+    /// Key name.
+    name: &'static str,
+    /// Expected value.
+    value: ValueKind,
+}
+
+impl KeySchema {
+    const fn new(name: &'static str, value: ValueKind) -> Self {
+        Self { name, value }
+    }
+}
+
+/// Schema of `#[custom_slice(..)]` attributes for an item kind.
+#[derive(Debug)]
+pub(crate) struct ItemSchema {
+    /// Item kind.
+    kind: SpecialItemType,
+    /// Available keys (except the item kind marker).
+    keys: &'static [KeySchema],
+    /// Available derive targets.
+    derives: &'static [&'static str],
+}
+
+/// Derive targets taking types as arguments: `Target(Type, ..)`.
+const DERIVES_WITH_TYPES: &[&str] = &["AsRefVia", "IntoVia"];
+
+/// Keys for `error(..)`.
+const ERROR_KEYS: &[KeySchema] = &[
+    KeySchema::new("type", ValueKind::Str),
+    KeySchema::new("map", ValueKind::Str),
+];
+
+/// Keys for `error(..)` of owned types.
+const OWNED_ERROR_KEYS: &[KeySchema] = &[
+    KeySchema::new("generate", ValueKind::Str),
+    KeySchema::new("type", ValueKind::Str),
+    KeySchema::new("map", ValueKind::Str),
+];
+
+/// Rules for `validator(all(..))` of slice types.
+const VALIDATOR_RULE_KEYS: &[KeySchema] = &[
+    KeySchema::new("ascii", ValueKind::Flag),
+    KeySchema::new("ascii_lowercase", ValueKind::Flag),
+    KeySchema::new("charset", ValueKind::Str),
+    KeySchema::new("max_len", ValueKind::Int),
+    KeySchema::new("no_leading", ValueKind::Str),
+    KeySchema::new("non_empty", ValueKind::Flag),
+    KeySchema::new("utf8", ValueKind::Flag),
+];
+
+/// Keys for `validator(..)` of slice types.
+const VALIDATOR_SPEC_KEYS: &[KeySchema] = &[
+    KeySchema::new("all", ValueKind::List(VALIDATOR_RULE_KEYS)),
+    KeySchema::new("error", ValueKind::Str),
+    KeySchema::new("regex", ValueKind::Str),
+    KeySchema::new("type", ValueKind::Str),
+];
+
+/// Keys for `validators(..)` of slice types.
+const VALIDATORS_KEYS: &[KeySchema] = &[
+    KeySchema::new("collect_all", ValueKind::Flag),
+    KeySchema::new("error", ValueKind::Str),
+];
+
+/// Keys for `validator(..)` of validators.
+const VALIDATOR_FN_KEYS: &[KeySchema] = &[
+    KeySchema::new("map", ValueKind::Str),
+    KeySchema::new("owned", ValueKind::Flag),
+];
+
+/// Keys for `arbitrary(..)` of owned types.
+const ARBITRARY_KEYS: &[KeySchema] = &[
+    KeySchema::new("max_retries", ValueKind::Int),
+    KeySchema::new("proptest", ValueKind::FlagOrStr),
+    KeySchema::new("quickcheck", ValueKind::FlagOrStr),
+];
+
+/// Keys for `equivalent(..)` of owned types.
+const EQUIVALENT_KEYS: &[KeySchema] = &[
+    KeySchema::new("hashbrown", ValueKind::Flag),
+    KeySchema::new("indexmap", ValueKind::Flag),
+];
+
+/// Keys for `sql(..)`.
+const SQL_KEYS: &[KeySchema] = &[
+    KeySchema::new("postgres", ValueKind::Flag),
+    KeySchema::new("rusqlite", ValueKind::Flag),
+];
+
+/// Keys for `json_schema(..)`.
+const JSON_SCHEMA_KEYS: &[KeySchema] = &[
+    KeySchema::new("description", ValueKind::Str),
+    KeySchema::new("with", ValueKind::Str),
+];
+
+/// Schema for owned types.
+pub(crate) const OWNED: ItemSchema = ItemSchema {
+    kind: SpecialItemType::OwnedType,
+    keys: &[
+        KeySchema::new("allow_safe_unchecked", ValueKind::Flag),
+        KeySchema::new("arbitrary", ValueKind::List(ARBITRARY_KEYS)),
+        KeySchema::new("debug_validate", ValueKind::Flag),
+        KeySchema::new("derive", ValueKind::Derives),
+        KeySchema::new("equivalent", ValueKind::List(EQUIVALENT_KEYS)),
+        KeySchema::new("error", ValueKind::List(OWNED_ERROR_KEYS)),
+        KeySchema::new("from_inner_normalized", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("fuzz_repair", ValueKind::Str),
+        KeySchema::new("get_mut", ValueKind::Fn(FnShape::RefMutSelf)),
+        KeySchema::new("get_ref", ValueKind::Fn(FnShape::RefSelf)),
+        KeySchema::new("incremental_validator", ValueKind::Str),
+        KeySchema::new("into_inner", ValueKind::Fn(FnShape::SelfValue)),
+        KeySchema::new("into_valid_prefix", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("json_schema", ValueKind::List(JSON_SCHEMA_KEYS)),
+        KeySchema::new("new_checked", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("new_unchecked", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("push_checked", ValueKind::Fn(FnShape::RefMutSelfArg)),
+        KeySchema::new("slice_range", ValueKind::Fn(FnShape::RefSelfArg)),
+        KeySchema::new("slice_ref", ValueKind::Fn(FnShape::RefSelfArg)),
+        KeySchema::new("sql", ValueKind::List(SQL_KEYS)),
+        KeySchema::new("subslice_closed", ValueKind::Flag),
+    ],
+    derives: &[
+        "Arbitrary",
+        "ArbitraryFuzz",
+        "Archive",
+        "AsMutSlice",
+        "AsMutSliceInner",
+        "AsRefSlice",
+        "AsRefSliceInner",
+        "AsRefVia",
+        "BorrowDecode",
+        "BorrowMut",
+        "BorshDeserialize",
+        "BorshSerialize",
+        "ClapValueParser",
+        "Decode",
+        "Deref",
+        "DerefMut",
+        "Encode",
+        "Equivalent",
+        "FmtWrite",
+        "FromInner",
+        "FromSql",
+        "FromStr",
+        "IntoInner",
+        "IntoVia",
+        "IoWrite",
+        "JsonSchema",
+        "PartialEq",
+        "PartialEqBulk",
+        "PartialEqInnerBulk",
+        "PartialOrd",
+        "PartialOrdBulk",
+        "PartialOrdInnerBulk",
+        "ToSql",
+        "TryFromInner",
+    ],
+};
+
+/// Schema for slice types.
+pub(crate) const SLICE: ItemSchema = ItemSchema {
+    kind: SpecialItemType::SliceType,
+    keys: &[
+        KeySchema::new("allow_safe_unchecked", ValueKind::Flag),
+        KeySchema::new("debug_validate", ValueKind::Flag),
+        KeySchema::new("derive", ValueKind::Derives),
+        KeySchema::new("error", ValueKind::List(ERROR_KEYS)),
+        KeySchema::new("from_inner_normalized", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("from_prefix", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("fuzz_repair", ValueKind::Str),
+        KeySchema::new("get_mut", ValueKind::Fn(FnShape::RefMutSelf)),
+        KeySchema::new("get_ref", ValueKind::Fn(FnShape::RefSelf)),
+        KeySchema::new("json_schema", ValueKind::List(JSON_SCHEMA_KEYS)),
+        KeySchema::new("new_checked", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("new_checked_mut", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("new_unchecked", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("new_unchecked_mut", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("split_valid_prefix", ValueKind::Fn(FnShape::Arg)),
+        KeySchema::new("sql", ValueKind::List(SQL_KEYS)),
+        KeySchema::new("valid_up_to", ValueKind::Str),
+        KeySchema::new("validator", ValueKind::StrOrList(VALIDATOR_SPEC_KEYS)),
+        KeySchema::new("validators", ValueKind::List(VALIDATORS_KEYS)),
+    ],
+    derives: &[
+        "ArbitraryFuzz",
+        "AsBytes",
+        "AsMutSlice",
+        "AsMutSliceInner",
+        "AsRefSlice",
+        "AsRefSliceInner",
+        "AsRefVia",
+        "BorrowDecode",
+        "BorshSerialize",
+        "DefaultArc",
+        "DefaultBox",
+        "DefaultRc",
+        "DefaultRef",
+        "DefaultRefMut",
+        "Deref",
+        "DerefMut",
+        "Encode",
+        "FromBytes",
+        "FromInner",
+        "FromInnerMut",
+        "IntoArc",
+        "IntoBox",
+        "IntoRc",
+        "IntoVia",
+        "JsonSchema",
+        "PartialEqBulk",
+        "PartialEqInnerBulk",
+        "PartialOrdBulk",
+        "PartialOrdInnerBulk",
+        "ToSql",
+        "TryFromInner",
+        "TransparentWrapper",
+        "TryFromInnerMut",
+    ],
+};
+
+/// Schema for validators.
+pub(crate) const VALIDATOR: ItemSchema = ItemSchema {
+    kind: SpecialItemType::Validator,
+    keys: &[KeySchema::new(
+        "validator",
+        ValueKind::List(VALIDATOR_FN_KEYS),
+    )],
+    derives: &[],
+};
+
+/// Schema for normalizers.
+pub(crate) const NORMALIZER: ItemSchema = ItemSchema {
+    kind: SpecialItemType::Normalizer,
+    keys: &[],
+    derives: &[],
+};
+
+/// All item schemas.
+const ALL: &[&ItemSchema] = &[&OWNED, &SLICE, &VALIDATOR, &NORMALIZER];
+
+impl ItemSchema {
+    /// Checks the given attributes and returns the found errors.
+    pub(crate) fn check(&self, attrs: &CustomSliceAttrs) -> Vec<syn::Error> {
+        let mut errors = attrs.malformed.clone();
+        let mut seen_keys = Vec::new();
+        let mut seen_derives = Vec::new();
+
+        for nested_meta in &attrs.custom_meta {
+            let meta = match nested_meta {
+                NestedMeta::Meta(meta) => meta,
+                NestedMeta::Literal(lit) => {
+                    errors.push(syn::Error::new_spanned(
+                        lit,
+                        "unexpected literal, expected a key",
+                    ));
+                    continue;
+                }
+            };
+            let ident = meta.name();
+            let name = ident.unraw().to_string();
+
+            let key = self.keys.iter().find(|key| key.name == name);
+            if let Some(kind) = SpecialItemType::from_ident(&ident) {
+                // Marker names can also be keys with values (such as
+                // `validator(..)` for slice types).
+                if is_word(meta) || (key.is_none() && !is_key_elsewhere(self.kind, &name)) {
+                    errors.extend(self.check_marker(kind, meta, &mut seen_keys));
+                    continue;
+                }
+            }
+
+            let key = match key {
+                Some(key) => key,
+                None => {
+                    errors.push(self.unknown_key_error(&ident, &name));
+                    continue;
+                }
+            };
+            match key.value {
+                ValueKind::Derives => {
+                    self.check_derives(attrs, meta, &mut seen_derives, &mut errors);
+                }
+                value => {
+                    if seen_keys.contains(&name) {
+                        errors.push(duplicate_error(&ident, &name));
+                        continue;
+                    }
+                    seen_keys.push(name);
+                    check_value(key.name, value, meta, &mut errors);
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the expected arguments of the generated function.
+    pub(crate) fn fn_shape(&self, key: &str) -> Option<FnShape> {
+        self.keys
+            .iter()
+            .filter(|k| k.name == key)
+            .filter_map(|k| match k.value {
+                ValueKind::Fn(shape) => Some(shape),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Checks the attributes of a method declaration and returns the key.
+    pub(crate) fn check_fn_stub<'a>(
+        &self,
+        attrs: &'a CustomSliceAttrs,
+        fn_ident: &Ident,
+    ) -> Result<(&'a Ident, FnShape), syn::Error> {
+        if let Some(e) = attrs.malformed.first() {
+            return Err(e.clone());
+        }
+        let ident = match attrs.custom_meta.as_slice() {
+            [NestedMeta::Meta(Meta::Word(ident))] => ident,
+            [] => {
+                return Err(syn::Error::new_spanned(
+                    fn_ident,
+                    "method declaration requires `#[custom_slice(kind)]` attribute \
+                     (such as `#[custom_slice(new_checked)]`)",
+                ))
+            }
+            [meta] => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected generated method kind (such as `new_checked`)",
+                ))
+            }
+            metas => {
+                return Err(syn::Error::new_spanned(
+                    &metas[1],
+                    "method declaration should have only one generated method kind",
+                ))
+            }
+        };
+        let name = ident.unraw().to_string();
+        match self.fn_shape(&name) {
+            Some(shape) => Ok((ident, shape)),
+            None => {
+                let candidates = self.keys.iter().filter_map(|k| match k.value {
+                    ValueKind::Fn(_) => Some(k.name),
+                    _ => None,
+                });
+                Err(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "unknown generated method kind `{}` for {}{}",
+                        name,
+                        self.kind.description(),
+                        DidYouMean(suggest(&name, candidates))
+                    ),
+                ))
+            }
+        }
+    }
+
+    /// Checks the item kind marker.
+    fn check_marker(
+        &self,
+        kind: SpecialItemType,
+        meta: &Meta,
+        seen_keys: &mut Vec<String>,
+    ) -> Option<syn::Error> {
+        let ident = meta.name();
+        if kind != self.kind {
+            return Some(syn::Error::new_spanned(
+                &ident,
+                format!(
+                    "`{}` conflicts with `{}`: an item can be only one of `owned`, `slice`, `validator`, or `normalizer`",
+                    kind.marker(),
+                    self.kind.marker()
+                ),
+            ));
+        }
+        if let Meta::Word(_) = meta {
+            let name = kind.marker().to_owned();
+            if seen_keys.contains(&name) {
+                return Some(duplicate_error(&ident, &name));
+            }
+            seen_keys.push(name);
+            return None;
+        }
+        Some(syn::Error::new_spanned(
+            meta,
+            format!("expected `{}` without value", kind.marker()),
+        ))
+    }
+
+    /// Checks `derive(..)`.
+    fn check_derives(
+        &self,
+        attrs: &CustomSliceAttrs,
+        meta: &Meta,
+        seen_derives: &mut Vec<String>,
+        errors: &mut Vec<syn::Error>,
+    ) {
+        let list = match meta {
+            Meta::List(list) => list,
+            _ => {
+                errors.push(syn::Error::new_spanned(
+                    meta,
+                    "expected `derive(Target, ..)`",
+                ));
+                return;
+            }
+        };
+        for nested_meta in &list.nested {
+            let target = match nested_meta {
+                NestedMeta::Meta(Meta::Word(ident)) => ident,
+                _ => {
+                    errors.push(syn::Error::new_spanned(
+                        nested_meta,
+                        "expected derive target name",
+                    ));
+                    continue;
+                }
+            };
+            let name = target.unraw().to_string();
+            if !self.derives.contains(&name.as_str()) {
+                errors.push(self.unknown_derive_error(target, &name));
+                continue;
+            }
+            if seen_derives.contains(&name) {
+                errors.push(syn::Error::new_spanned(
+                    target,
+                    format!("duplicate derive target `{}`", name),
+                ));
+                continue;
+            }
+            match attrs.derive_types(&name) {
+                Some(_) if !DERIVES_WITH_TYPES.contains(&name.as_str()) => {
+                    errors.push(syn::Error::new_spanned(
+                        target,
+                        format!("derive target `{}` does not take types", name),
+                    ));
+                }
+                Some(types) if !types.is_empty() => {}
+                _ if DERIVES_WITH_TYPES.contains(&name.as_str()) => {
+                    errors.push(syn::Error::new_spanned(
+                        target,
+                        format!(
+                            "derive target `{}` requires types, such as `{}(Type, ..)`",
+                            name, name
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+            seen_derives.push(name);
+        }
+    }
+
+    /// Creates an error for an unknown key.
+    fn unknown_key_error(&self, ident: &Ident, name: &str) -> syn::Error {
+        let available_elsewhere = schemas_with_key(self.kind, name)
+            .map(|schema| schema.kind.description())
+            .collect::<Vec<_>>();
+        if !available_elsewhere.is_empty() {
+            return syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`{}` is not available for {}, only for {}",
+                    name,
+                    self.kind.description(),
+                    available_elsewhere.join(" and ")
+                ),
+            );
+        }
+
+        let candidates = self
+            .keys
+            .iter()
+            .map(|key| key.name)
+            .chain(Some(self.kind.marker()));
+        syn::Error::new_spanned(
+            ident,
+            format!(
+                "unknown key `{}` for {}{}",
+                name,
+                self.kind.description(),
+                DidYouMean(suggest(name, candidates))
+            ),
+        )
+    }
+
+    /// Creates an error for an unknown derive target.
+    fn unknown_derive_error(&self, ident: &Ident, name: &str) -> syn::Error {
+        let available_elsewhere = ALL
+            .iter()
+            .filter(|schema| schema.kind != self.kind)
+            .any(|schema| schema.derives.contains(&name));
+        if available_elsewhere {
+            return syn::Error::new_spanned(
+                ident,
+                format!(
+                    "derive target `{}` is not available for {}",
+                    name,
+                    self.kind.description()
+                ),
+            );
+        }
+
+        let candidates = self.derives.iter().cloned();
+        syn::Error::new_spanned(
+            ident,
+            format!(
+                "unknown derive target `{}` for {}{}",
+                name,
+                self.kind.description(),
+                DidYouMean(suggest(name, candidates))
+            ),
+        )
+    }
+}
+
+/// Checks the value of the key.
+fn check_value(key: &str, value: ValueKind, meta: &Meta, errors: &mut Vec<syn::Error>) {
+    match (value, meta) {
+        (ValueKind::Str, Meta::NameValue(nv)) | (ValueKind::Fn(_), Meta::NameValue(nv)) => {
+            match nv.lit {
+                Lit::Str(_) => {}
+                ref lit => errors.push(syn::Error::new_spanned(
+                    lit,
+                    format!("expected string literal for `{}`", key),
+                )),
+            }
+        }
+        (ValueKind::Str, _) | (ValueKind::Fn(_), _) => errors.push(syn::Error::new_spanned(
+            meta,
+            format!("expected `{} = \"...\"`", key),
+        )),
+        (ValueKind::Flag, Meta::Word(_)) => {}
+        (ValueKind::Flag, _) => errors.push(syn::Error::new_spanned(
+            meta,
+            format!("expected `{}` without value", key),
+        )),
+        (ValueKind::Int, Meta::NameValue(nv)) => match nv.lit {
+            Lit::Int(_) => {}
+            ref lit => errors.push(syn::Error::new_spanned(
+                lit,
+                format!("expected integer literal for `{}`", key),
+            )),
+        },
+        (ValueKind::Int, _) => errors.push(syn::Error::new_spanned(
+            meta,
+            format!("expected `{} = 42`", key),
+        )),
+        (ValueKind::FlagOrStr, Meta::Word(_)) => {}
+        (ValueKind::FlagOrStr, Meta::NameValue(_)) => {
+            check_value(key, ValueKind::Str, meta, errors)
+        }
+        (ValueKind::FlagOrStr, _) => errors.push(syn::Error::new_spanned(
+            meta,
+            format!("expected `{}` or `{} = \"...\"`", key, key),
+        )),
+        (ValueKind::List(keys), Meta::List(list)) => {
+            let mut seen = Vec::new();
+            for nested_meta in &list.nested {
+                let inner = match nested_meta {
+                    NestedMeta::Meta(inner) => inner,
+                    NestedMeta::Literal(lit) => {
+                        errors.push(syn::Error::new_spanned(
+                            lit,
+                            format!("unexpected literal in `{}(..)`, expected a key", key),
+                        ));
+                        continue;
+                    }
+                };
+                let ident = inner.name();
+                let name = ident.unraw().to_string();
+                let inner_key = match keys.iter().find(|k| k.name == name) {
+                    Some(v) => v,
+                    None => {
+                        let candidates = keys.iter().map(|k| k.name);
+                        errors.push(syn::Error::new_spanned(
+                            &ident,
+                            format!(
+                                "unknown key `{}` in `{}(..)`{}",
+                                name,
+                                key,
+                                DidYouMean(suggest(&name, candidates))
+                            ),
+                        ));
+                        continue;
+                    }
+                };
+                if seen.contains(&name) {
+                    errors.push(duplicate_error(&ident, &name));
+                    continue;
+                }
+                seen.push(name);
+                check_value(inner_key.name, inner_key.value, inner, errors);
+            }
+        }
+        (ValueKind::List(_), _) => errors.push(syn::Error::new_spanned(
+            meta,
+            format!("expected `{}(..)`", key),
+        )),
+        (ValueKind::StrOrList(_), Meta::NameValue(_)) => {
+            check_value(key, ValueKind::Str, meta, errors)
+        }
+        (ValueKind::StrOrList(keys), Meta::List(_)) => {
+            check_value(key, ValueKind::List(keys), meta, errors)
+        }
+        (ValueKind::StrOrList(_), _) => errors.push(syn::Error::new_spanned(
+            meta,
+            format!("expected `{} = \"...\"` or `{}(..)`", key, key),
+        )),
+        (ValueKind::Derives, _) => unreachable!("Derive targets should be checked separately"),
+    }
+}
+
+/// Returns the schemas for the other item kinds which have the given key.
+fn schemas_with_key<'a>(
+    kind: SpecialItemType,
+    name: &'a str,
+) -> impl Iterator<Item = &'static ItemSchema> + 'a {
+    ALL.iter()
+        .cloned()
+        .filter(move |schema| schema.kind != kind)
+        .filter(move |schema| schema.keys.iter().any(|key| key.name == name))
+}
+
+/// Checks whether the key is available for the other item kinds.
+fn is_key_elsewhere(kind: SpecialItemType, name: &str) -> bool {
+    schemas_with_key(kind, name).next().is_some()
+}
+
+/// Checks whether the meta is a word without value.
+fn is_word(meta: &Meta) -> bool {
+    match meta {
+        Meta::Word(_) => true,
+        _ => false,
+    }
+}
+
+/// Creates an error for a duplicate key.
+fn duplicate_error(ident: &Ident, name: &str) -> syn::Error {
+    syn::Error::new_spanned(ident, format!("duplicate key `{}`", name))
+}
+
+/// Returns the most similar candidate, if it is similar enough.
+pub(crate) fn suggest<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let threshold = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(dist, _)| dist <= threshold)
+        .min_by_key(|&(dist, _)| dist)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns the edit distance of the given strings.
+///
+/// Transposition of adjacent characters is counted as a single edit.
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let lhs = lhs.chars().collect::<Vec<_>>();
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut dist = vec![vec![0; rhs.len() + 1]; lhs.len() + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=lhs.len() {
+        for j in 1..=rhs.len() {
+            let cost = if lhs[i - 1] == rhs[j - 1] { 0 } else { 1 };
+            let mut d = (dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1)
+                .min(dist[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && lhs[i - 1] == rhs[j - 2] && lhs[i - 2] == rhs[j - 1] {
+                d = d.min(dist[i - 2][j - 2] + 1);
+            }
+            dist[i][j] = d;
+        }
+    }
+    dist[lhs.len()][rhs.len()]
+}
+
+/// Formats "did you mean" suffix.
+pub(crate) struct DidYouMean<'a>(pub(crate) Option<&'a str>);
+
+impl fmt::Display for DidYouMean<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(candidate) => write!(f, "; did you mean `{}`?", candidate),
+            None => Ok(()),
+        }
+    }
+}
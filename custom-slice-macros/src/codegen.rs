@@ -1,5 +1,6 @@
 //! Code generators.
 
+pub(crate) mod error;
 pub(crate) mod expr;
 pub(crate) mod props;
 pub(crate) mod traits;
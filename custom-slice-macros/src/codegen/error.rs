@@ -0,0 +1,58 @@
+//! Error types.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::defs::Definitions;
+
+/// Generates the error type for the owned type.
+///
+/// The error type is specified by `#[custom_slice(error(generate = ".."))]`.
+pub(crate) fn gen_owned_error(defs: &Definitions) -> Option<TokenStream> {
+    let name = defs.generated_owned_error()?;
+    let ty_validator_error = defs
+        .ty_owned_validator_error()
+        .expect("Should never fail: validator error type is already checked");
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let vis = defs.vis_owned();
+    let doc = format!(
+        "Error for `{}` creation, with the rejected value.",
+        quote!(#ty_owned)
+    );
+
+    Some(quote! {
+        #[doc = #doc]
+        #[derive(Debug)]
+        #vis struct #name {
+            /// Validation error.
+            error: #ty_validator_error,
+            /// Rejected value.
+            inner: #ty_owned_inner,
+        }
+
+        impl #name {
+            /// Returns the validation error.
+            #vis fn error(&self) -> &#ty_validator_error {
+                &self.error
+            }
+
+            /// Returns the rejected value.
+            #vis fn into_inner(self) -> #ty_owned_inner {
+                self.inner
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.error, f)
+            }
+        }
+
+        impl ::std::error::Error for #name {
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                ::core::option::Option::Some(&self.error)
+            }
+        }
+    })
+}
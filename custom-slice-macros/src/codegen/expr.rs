@@ -50,6 +50,11 @@ impl<T: ToTokens> OwnedInner<T> {
         conv_trait: OwnedToSliceTrait,
         mutability: M,
     ) -> SliceInner<impl ToTokens, M> {
+        // Byte buffers are converted through `Deref`.
+        let conv_trait = match defs.owned_inner_bytes_buf() {
+            Some(_) => OwnedToSliceTrait::Deref,
+            None => conv_trait,
+        };
         SliceInner::new(
             conv_trait.expr_call(
                 defs.ty_owned_inner(),
@@ -117,9 +122,12 @@ impl<T: ToTokens, M: Mutability> SliceInner<T, M> {
     }
 
     pub(crate) fn to_owned_inner(&self, defs: &Definitions) -> OwnedInner<TokenStream> {
+        if let Some(buf) = defs.owned_inner_bytes_buf() {
+            return OwnedInner(buf.expr_copy_from_slice(defs.ty_owned_inner(), quote!(&#self)));
+        }
         let ty_slice_inner = defs.ty_slice_inner();
         OwnedInner(quote! {
-            <#ty_slice_inner as std::borrow::ToOwned>::to_owned(&#self)
+            <#ty_slice_inner as ::std::borrow::ToOwned>::to_owned(&#self)
         })
     }
 
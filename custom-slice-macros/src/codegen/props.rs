@@ -114,7 +114,6 @@ pub(crate) enum Safety {
 }
 
 impl Safety {
-    #[allow(dead_code)]
     pub(crate) fn is_safe(self) -> bool {
         self == Safety::Safe
     }
@@ -1,5 +1,6 @@
 //! Trait impls.
 
+use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use crate::{
@@ -7,12 +8,124 @@ use crate::{
         props::{DynMutability, Mutability},
         types::RefType,
     },
-    defs::Definitions,
+    defs::{Definitions, SchemaValue},
 };
 
 pub(crate) mod owned;
 pub(crate) mod slice;
 
+/// Message for panics of `ArbitraryFuzz` derive target.
+const FUZZ_REPAIR_MESSAGE: &str = "the repaired value should pass the validator";
+
+/// Message for debug assertions of `Equivalent` derive target.
+const EQUIVALENT_HASH_MESSAGE: &str = "equivalent values should have the same hash";
+
+/// Implements `postgres_types::ToSql` for `ty` by forwarding to `ty_inner`.
+///
+/// `self_inner` is an expression of `&ty_inner`.
+fn impl_postgres_to_sql(
+    generics: impl ToTokens,
+    ty: impl ToTokens,
+    ty_inner: impl ToTokens,
+    self_inner: impl ToTokens,
+) -> TokenStream {
+    let ty_buf = quote!(::postgres_types::private::BytesMut);
+    let ty_result = quote! {
+        ::core::result::Result<
+            ::postgres_types::IsNull,
+            ::std::boxed::Box<dyn ::std::error::Error + ::core::marker::Sync + ::core::marker::Send>,
+        >
+    };
+    quote! {
+        impl #generics ::postgres_types::ToSql for #ty {
+            fn to_sql(&self, ty: &::postgres_types::Type, out: &mut #ty_buf) -> #ty_result {
+                <#ty_inner as ::postgres_types::ToSql>::to_sql(#self_inner, ty, out)
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                <#ty_inner as ::postgres_types::ToSql>::accepts(ty)
+            }
+
+            fn to_sql_checked(&self, ty: &::postgres_types::Type, out: &mut #ty_buf) -> #ty_result {
+                <#ty_inner as ::postgres_types::ToSql>::to_sql_checked(#self_inner, ty, out)
+            }
+        }
+    }
+}
+
+/// Implements `schemars::JsonSchema` for the owned type or the slice type.
+///
+/// The schema for `SliceInner` (or the schema returned by
+/// `json_schema(with = "..")`) is annotated with the constraints of the
+/// validator and the description.
+fn impl_json_schema(
+    defs: &Definitions,
+    ty: impl ToTokens,
+    (description, with): (Option<String>, Option<syn::Expr>),
+) -> TokenStream {
+    let name = quote!(#ty).to_string();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let base = match with {
+        Some(with) => quote!((#with)(generator)),
+        None => quote!(<#ty_slice_inner as ::schemars::JsonSchema>::json_schema(generator)),
+    };
+    let mut keywords = defs.validator_schema_keywords();
+    if let Some(description) = description {
+        keywords.push(("description", SchemaValue::Str(description)));
+    }
+    let body = if keywords.is_empty() {
+        base
+    } else {
+        let inserts = keywords.into_iter().map(|(key, value)| {
+            let value = match value {
+                SchemaValue::Int(v) => {
+                    let v = v as u64;
+                    quote!(#v)
+                }
+                SchemaValue::Str(v) => quote!(#v),
+                SchemaValue::Patterns(patterns) => quote! {
+                    ::std::vec![#({
+                        let mut schema = <::schemars::Schema as ::core::default::Default>::default();
+                        ::schemars::Schema::insert(
+                            &mut schema,
+                            ::std::borrow::ToOwned::to_owned("pattern"),
+                            ::core::convert::From::from(#patterns),
+                        );
+                        ::schemars::Schema::to_value(schema)
+                    }),*]
+                },
+            };
+            quote! {
+                ::schemars::Schema::insert(
+                    &mut schema,
+                    ::std::borrow::ToOwned::to_owned(#key),
+                    ::core::convert::From::from(#value),
+                );
+            }
+        });
+        quote! {
+            let mut schema = #base;
+            #(#inserts)*
+            schema
+        }
+    };
+    quote! {
+        impl ::schemars::JsonSchema for #ty {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#name)
+            }
+
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(::core::concat!(::core::module_path!(), "::", #name))
+            }
+
+            fn json_schema(generator: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                #body
+            }
+        }
+    }
+}
+
 /// Traits to convert from owned type to slice type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum OwnedToSliceTrait {
@@ -27,12 +140,14 @@ pub(crate) enum OwnedToSliceTrait {
 impl OwnedToSliceTrait {
     fn trait_path(self, mutability: impl Mutability) -> impl ToTokens {
         match (self, mutability.into()) {
-            (OwnedToSliceTrait::AsRef, DynMutability::Constant) => quote!(std::convert::AsRef),
-            (OwnedToSliceTrait::AsRef, DynMutability::Mutable) => quote!(std::convert::AsMut),
-            (OwnedToSliceTrait::Borrow, DynMutability::Constant) => quote!(std::borrow::Borrow),
-            (OwnedToSliceTrait::Borrow, DynMutability::Mutable) => quote!(std::borrow::BorrowMut),
-            (OwnedToSliceTrait::Deref, DynMutability::Constant) => quote!(std::ops::Deref),
-            (OwnedToSliceTrait::Deref, DynMutability::Mutable) => quote!(std::ops::DerefMut),
+            (OwnedToSliceTrait::AsRef, DynMutability::Constant) => quote!(::core::convert::AsRef),
+            (OwnedToSliceTrait::AsRef, DynMutability::Mutable) => quote!(::core::convert::AsMut),
+            (OwnedToSliceTrait::Borrow, DynMutability::Constant) => quote!(::core::borrow::Borrow),
+            (OwnedToSliceTrait::Borrow, DynMutability::Mutable) => {
+                quote!(::core::borrow::BorrowMut)
+            }
+            (OwnedToSliceTrait::Deref, DynMutability::Constant) => quote!(::core::ops::Deref),
+            (OwnedToSliceTrait::Deref, DynMutability::Mutable) => quote!(::core::ops::DerefMut),
         }
     }
 
@@ -43,19 +158,19 @@ impl OwnedToSliceTrait {
     ) -> impl ToTokens {
         match (self, mutability.into()) {
             (OwnedToSliceTrait::AsRef, DynMutability::Constant) => {
-                quote!(std::convert::AsRef<#ty_slice>)
+                quote!(::core::convert::AsRef<#ty_slice>)
             }
             (OwnedToSliceTrait::AsRef, DynMutability::Mutable) => {
-                quote!(std::convert::AsMut<#ty_slice>)
+                quote!(::core::convert::AsMut<#ty_slice>)
             }
             (OwnedToSliceTrait::Borrow, DynMutability::Constant) => {
-                quote!(std::borrow::Borrow<#ty_slice>)
+                quote!(::core::borrow::Borrow<#ty_slice>)
             }
             (OwnedToSliceTrait::Borrow, DynMutability::Mutable) => {
-                quote!(std::borrow::BorrowMut<#ty_slice>)
+                quote!(::core::borrow::BorrowMut<#ty_slice>)
             }
-            (OwnedToSliceTrait::Deref, DynMutability::Constant) => quote!(std::ops::Deref),
-            (OwnedToSliceTrait::Deref, DynMutability::Mutable) => quote!(std::ops::DerefMut),
+            (OwnedToSliceTrait::Deref, DynMutability::Constant) => quote!(::core::ops::Deref),
+            (OwnedToSliceTrait::Deref, DynMutability::Mutable) => quote!(::core::ops::DerefMut),
         }
     }
 
@@ -104,8 +219,8 @@ pub enum CmpTrait {
 impl CmpTrait {
     fn trait_path(self) -> impl ToTokens {
         match self {
-            CmpTrait::PartialEq => quote!(std::cmp::PartialEq),
-            CmpTrait::PartialOrd => quote!(std::cmp::PartialOrd),
+            CmpTrait::PartialEq => quote!(::core::cmp::PartialEq),
+            CmpTrait::PartialOrd => quote!(::core::cmp::PartialOrd),
         }
     }
 
@@ -119,7 +234,7 @@ impl CmpTrait {
     fn ty_ret(self) -> impl ToTokens {
         match self {
             CmpTrait::PartialEq => quote!(bool),
-            CmpTrait::PartialOrd => quote!(Option<std::cmp::Ordering>),
+            CmpTrait::PartialOrd => quote!(::core::option::Option<::core::cmp::Ordering>),
         }
     }
 
@@ -1,18 +1,307 @@
 //! Trait impls for slice types.
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 
 use crate::{
     codegen::{
         expr::{Owned, OwnedInner, Slice, SliceInner},
         props::{Constant, DynMutability, Mutability, Safety},
-        traits::{CmpTrait, OwnedToSliceTrait},
+        traits::{CmpTrait, OwnedToSliceTrait, EQUIVALENT_HASH_MESSAGE, FUZZ_REPAIR_MESSAGE},
         types::RefType,
     },
     defs::Definitions,
 };
 
+/// Implements `Arbitrary` traits of the selected backends.
+pub(crate) fn impl_arbitrary(defs: &Definitions) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    if let Some(strategy) = defs.arbitrary_generator("proptest") {
+        impl_proptest_arbitrary(defs, strategy).to_tokens(&mut tokens);
+    }
+    if let Some(generator) = defs.arbitrary_generator("quickcheck") {
+        impl_quickcheck_arbitrary(defs, generator).to_tokens(&mut tokens);
+    }
+    tokens
+}
+
+/// Implements `proptest::arbitrary::Arbitrary`.
+///
+/// `strategy` is an expression of the strategy for `OwnedInner`.
+fn impl_proptest_arbitrary(defs: &Definitions, strategy: Option<syn::Expr>) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let strategy = match strategy {
+        Some(expr) => expr.into_token_stream(),
+        None => quote!(::proptest::arbitrary::any::<#ty_owned_inner>()),
+    };
+    let expr_owned_opt = inner_to_outer_opt(defs, arg_name.as_ref());
+    // Invalid values are rejected by the filter, which retries within the
+    // local rejection limit of the test runner. Shrinking is done by the
+    // strategy for `OwnedInner`, and invalid candidates are skipped.
+    quote! {
+        impl ::proptest::arbitrary::Arbitrary for #ty_owned {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                ::proptest::strategy::Strategy::boxed(
+                    ::proptest::strategy::Strategy::prop_filter_map(
+                        #strategy,
+                        "the value should pass the validator",
+                        |#arg_name: #ty_owned_inner| #expr_owned_opt,
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// Implements `quickcheck::Arbitrary`.
+///
+/// `generator` is an expression of the function taking `&mut Gen` and
+/// returning `OwnedInner`.
+fn impl_quickcheck_arbitrary(defs: &Definitions, generator: Option<syn::Expr>) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let gen = quote!(_g);
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let generated = match generator {
+        Some(expr) => quote!((#expr)(#gen)),
+        None => quote!(<#ty_owned_inner as ::quickcheck::Arbitrary>::arbitrary(#gen)),
+    };
+    let max_retries = defs.arbitrary_max_retries();
+    let message = format!(
+        "failed to generate a value passing the validator in {} attempts",
+        max_retries
+    );
+    let owned_inner = Owned::new(quote!(self)).to_owned_inner(defs);
+    let expr_owned_opt = inner_to_outer_opt(defs, arg_name.as_ref());
+    quote! {
+        impl ::quickcheck::Arbitrary for #ty_owned {
+            fn arbitrary(#gen: &mut ::quickcheck::Gen) -> Self {
+                for _ in 0..#max_retries {
+                    let #arg_name: #ty_owned_inner = #generated;
+                    if let ::core::option::Option::Some(_v) = #expr_owned_opt {
+                        return _v;
+                    }
+                }
+                ::core::panic!(#message)
+            }
+
+            fn shrink(&self) -> ::std::boxed::Box<dyn ::core::iter::Iterator<Item = Self>> {
+                // Shrink the inner value, and skip invalid candidates.
+                ::std::boxed::Box::new(::core::iter::Iterator::filter_map(
+                    <#ty_owned_inner as ::quickcheck::Arbitrary>::shrink(&#owned_inner),
+                    |#arg_name: #ty_owned_inner| #expr_owned_opt,
+                ))
+            }
+        }
+    }
+}
+
+/// Implements `arbitrary::Arbitrary`.
+///
+/// Invalid values are repaired by `fuzz_repair` or the normalizer if
+/// available, and rejected otherwise.
+pub(crate) fn impl_arbitrary_fuzz(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let expr_owned_opt = inner_to_outer_opt(defs, arg_name.as_ref());
+
+    let repaired = match (defs.owned_fuzz_repair(), defs.fn_normalizer()) {
+        _ if !defs.has_owned_validator() => None,
+        (Some(repair), _) => Some(quote!((#repair)(#arg_name))),
+        (None, Some(normalizer)) => {
+            let expr_slice_inner_ref =
+                arg_name.to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
+            Some(quote!({
+                let _normalized = match #normalizer(#expr_slice_inner_ref) {
+                    ::std::borrow::Cow::Borrowed(_) => ::core::option::Option::None,
+                    ::std::borrow::Cow::Owned(_v) => ::core::option::Option::Some(_v),
+                };
+                match _normalized {
+                    ::core::option::Option::Some(_v) => ::core::convert::From::from(_v),
+                    ::core::option::Option::None => #arg_name,
+                }
+            }))
+        }
+        (None, None) => None,
+    };
+    let body = match repaired {
+        Some(repaired) => {
+            let is_valid = expr_owned_inner_is_valid(defs, &arg_name);
+            quote! {
+                let #arg_name: #ty_owned_inner = if #is_valid { #arg_name } else { #repaired };
+                match #expr_owned_opt {
+                    ::core::option::Option::Some(_o) => ::core::result::Result::Ok(_o),
+                    ::core::option::Option::None => ::core::panic!(#FUZZ_REPAIR_MESSAGE),
+                }
+            }
+        }
+        None => quote! {
+            match #expr_owned_opt {
+                ::core::option::Option::Some(_o) => ::core::result::Result::Ok(_o),
+                ::core::option::Option::None => {
+                    ::core::result::Result::Err(::arbitrary::Error::IncorrectFormat)
+                }
+            }
+        },
+    };
+    quote! {
+        impl<'a> ::arbitrary::Arbitrary<'a> for #ty_owned {
+            fn arbitrary(_u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+                let #arg_name: #ty_owned_inner =
+                    <#ty_owned_inner as ::arbitrary::Arbitrary<'a>>::arbitrary(_u)?;
+                #body
+            }
+
+            fn arbitrary_take_rest(_u: ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+                let #arg_name: #ty_owned_inner =
+                    <#ty_owned_inner as ::arbitrary::Arbitrary<'a>>::arbitrary_take_rest(_u)?;
+                #body
+            }
+
+            fn size_hint(_depth: usize) -> (usize, ::core::option::Option<usize>) {
+                <#ty_owned_inner as ::arbitrary::Arbitrary<'a>>::size_hint(_depth)
+            }
+        }
+    }
+}
+
+/// Implements `rkyv::Archive`, `rkyv::Serialize`, and `rkyv::Deserialize`.
+///
+/// The archived type `Archived{Owned}` is a transparent wrapper of the
+/// archived `OwnedInner`, and dereferences to the slice type.
+/// Its `CheckBytes` impl runs the slice validator on the archived inner
+/// value, so the validator error should implement `std::error::Error`.
+pub(crate) fn impl_archive(defs: &Definitions) -> TokenStream {
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let vis = defs.vis_owned();
+    let name = quote!(#ty_owned).to_string();
+    let ty_archived = syn::Ident::new(&format!("Archived{}", name), Span::call_site());
+    let doc = format!("Archived [`{}`].", name);
+    let ty_archived_inner = quote!(::rkyv::Archived<#ty_owned_inner>);
+
+    let self_inner: OwnedInner<_> = Owned::new(quote!(self)).to_owned_inner(defs);
+    let arg_owned_inner = OwnedInner::new(quote!(_v));
+    let owned = arg_owned_inner.to_owned_unchecked(defs);
+    let arg_slice_inner = SliceInner::new(quote!(_v), Constant);
+    let slice = arg_slice_inner.to_slice_unchecked(defs, Safety::Safe);
+
+    let check_valid = if defs.has_validator() {
+        let error_var = &quote!(_e);
+        let (expr, _) = super::slice::inner_to_outer_checked(
+            defs,
+            arg_slice_inner.as_ref(),
+            error_var,
+            Safety::Safe,
+        );
+        quote! {
+            // SAFETY: The bytes of the archived inner value are checked above.
+            let #arg_slice_inner: &#ty_slice_inner = ::core::ops::Deref::deref(unsafe { &*_inner });
+            match #expr {
+                ::core::result::Result::Ok(_) => ::core::result::Result::Ok(()),
+                ::core::result::Result::Err(#error_var) => ::core::result::Result::Err(
+                    <<__C as ::rkyv::rancor::Fallible>::Error as ::rkyv::rancor::Source>::new(
+                        #error_var,
+                    ),
+                ),
+            }
+        }
+    } else {
+        quote!(::core::result::Result::Ok(()))
+    };
+
+    let archived = quote! {
+        #[doc = #doc]
+        #[repr(transparent)]
+        #vis struct #ty_archived(#ty_archived_inner);
+
+        // SAFETY: The archived type is a transparent wrapper of the portable type.
+        unsafe impl ::rkyv::Portable for #ty_archived {}
+
+        impl ::core::ops::Deref for #ty_archived {
+            type Target = #ty_slice;
+
+            fn deref(&self) -> &Self::Target {
+                let #arg_slice_inner: &#ty_slice_inner = ::core::ops::Deref::deref(&self.0);
+                #slice
+            }
+        }
+    };
+    let ty_context_error = quote!(<__C as ::rkyv::rancor::Fallible>::Error);
+    let check_bytes = quote! {
+        // SAFETY: The inner value is checked by its `CheckBytes` impl, and then
+        // by the validator.
+        unsafe impl<__C> ::rkyv::bytecheck::CheckBytes<__C> for #ty_archived
+        where
+            __C: ::rkyv::rancor::Fallible + ?::core::marker::Sized,
+            #ty_context_error: ::rkyv::rancor::Source,
+            #ty_archived_inner: ::rkyv::bytecheck::CheckBytes<__C>,
+        {
+            unsafe fn check_bytes(
+                value: *const Self,
+                context: &mut __C,
+            ) -> ::core::result::Result<(), #ty_context_error> {
+                let _inner = value as *const #ty_archived_inner;
+                // SAFETY: The archived type has the same layout as the inner value.
+                unsafe {
+                    <#ty_archived_inner as ::rkyv::bytecheck::CheckBytes<__C>>::check_bytes(_inner, context)?;
+                }
+                #check_valid
+            }
+        }
+    };
+    let serialize = quote! {
+        impl ::rkyv::Archive for #ty_owned {
+            type Archived = #ty_archived;
+            type Resolver = ::rkyv::Resolver<#ty_owned_inner>;
+
+            fn resolve(&self, resolver: Self::Resolver, out: ::rkyv::Place<Self::Archived>) {
+                // SAFETY: The archived type has the same layout as the inner value.
+                let out = unsafe { ::rkyv::Place::cast_unchecked::<#ty_archived_inner>(&out) };
+                <#ty_owned_inner as ::rkyv::Archive>::resolve(&#self_inner, resolver, out);
+            }
+        }
+
+        impl<__S> ::rkyv::Serialize<__S> for #ty_owned
+        where
+            __S: ::rkyv::rancor::Fallible + ?::core::marker::Sized,
+            #ty_owned_inner: ::rkyv::Serialize<__S>,
+        {
+            fn serialize(
+                &self,
+                serializer: &mut __S,
+            ) -> ::core::result::Result<Self::Resolver, <__S as ::rkyv::rancor::Fallible>::Error> {
+                <#ty_owned_inner as ::rkyv::Serialize<__S>>::serialize(&#self_inner, serializer)
+            }
+        }
+    };
+    let deserialize = quote! {
+        impl<__D> ::rkyv::Deserialize<#ty_owned, __D> for #ty_archived
+        where
+            __D: ::rkyv::rancor::Fallible + ?::core::marker::Sized,
+            #ty_archived_inner: ::rkyv::Deserialize<#ty_owned_inner, __D>,
+        {
+            fn deserialize(
+                &self,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<#ty_owned, <__D as ::rkyv::rancor::Fallible>::Error> {
+                let #arg_owned_inner = <#ty_archived_inner as ::rkyv::Deserialize<#ty_owned_inner, __D>>::deserialize(&self.0, deserializer)?;
+                // The archived value is already checked by the validator.
+                ::core::result::Result::Ok(#owned)
+            }
+        }
+    };
+    quote!(#archived #check_bytes #serialize #deserialize)
+}
+
 /// Implements `AsRef<Slice>` or `AsMut<Slice>`.
 pub(crate) fn impl_as_ref_slice(defs: &Definitions, mutability: impl Mutability) -> TokenStream {
     let trait_as_ref = OwnedToSliceTrait::AsRef.trait_path(mutability);
@@ -63,6 +352,30 @@ pub(crate) fn impl_as_ref_slice_inner(
     }
 }
 
+/// Implements `AsRef<T>` for each type of `AsRefVia(T, ..)`, through
+/// `SliceInner: AsRef<T>`.
+pub(crate) fn impl_as_ref_via(defs: &Definitions) -> TokenStream {
+    let ty_owned = defs.ty_owned();
+    let ty_slice_inner = defs.ty_slice_inner();
+
+    // `&Owned` -> `&OwnedInner` -> `&SliceInner` -> `&T`.
+    let self_inner: SliceInner<_, _> = Owned::new(quote!(self))
+        .to_owned_inner(defs)
+        .to_slice_inner_ref(defs, OwnedToSliceTrait::AsRef, Constant);
+    defs.owned_derive_types("AsRefVia")
+        .iter()
+        .map(|ty| {
+            quote! {
+                impl ::core::convert::AsRef<#ty> for #ty_owned {
+                    fn as_ref(&self) -> &#ty {
+                        <#ty_slice_inner as ::core::convert::AsRef<#ty>>::as_ref(#self_inner)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
 /// Implements `Borrow` or `BorrowMut`.
 pub(crate) fn impl_borrow(defs: &Definitions, mutability: impl Mutability) -> TokenStream {
     let trait_borrow = OwnedToSliceTrait::Borrow.trait_path(mutability);
@@ -87,6 +400,122 @@ pub(crate) fn impl_borrow(defs: &Definitions, mutability: impl Mutability) -> To
     }
 }
 
+/// Implements `bincode::BorrowDecode`.
+///
+/// The validator error should implement `Display`.
+pub(crate) fn impl_borrow_decode(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let body = inner_to_outer_mapped(
+        defs,
+        arg_name.as_ref(),
+        |e| quote!(::bincode::error::DecodeError::OtherString(::std::string::ToString::to_string(&#e))),
+    );
+    quote! {
+        impl<'de, __Context> ::bincode::BorrowDecode<'de, __Context> for #ty_owned
+        where
+            #ty_owned_inner: ::bincode::BorrowDecode<'de, __Context>,
+        {
+            fn borrow_decode<__D: ::bincode::de::BorrowDecoder<'de, Context = __Context>>(
+                decoder: &mut __D,
+            ) -> ::core::result::Result<Self, ::bincode::error::DecodeError> {
+                let #arg_name = <#ty_owned_inner as ::bincode::BorrowDecode<'de, __Context>>::borrow_decode(decoder)?;
+                #body
+            }
+        }
+    }
+}
+
+/// Implements `borsh::BorshDeserialize`.
+///
+/// The validator error should implement
+/// `Into<Box<dyn std::error::Error + Send + Sync>>`.
+pub(crate) fn impl_borsh_deserialize(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let body = inner_to_outer_mapped(
+        defs,
+        arg_name.as_ref(),
+        |e| quote!(::borsh::io::Error::new(::borsh::io::ErrorKind::InvalidData, #e)),
+    );
+    quote! {
+        impl ::borsh::BorshDeserialize for #ty_owned {
+            fn deserialize_reader<__R: ::borsh::io::Read>(
+                reader: &mut __R,
+            ) -> ::borsh::io::Result<Self> {
+                let #arg_name = <#ty_owned_inner as ::borsh::BorshDeserialize>::deserialize_reader(reader)?;
+                #body
+            }
+        }
+    }
+}
+
+/// Implements `borsh::BorshSerialize`.
+pub(crate) fn impl_borsh_serialize(defs: &Definitions) -> TokenStream {
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let self_inner: OwnedInner<_> = Owned::new(quote!(self)).to_owned_inner(defs);
+    quote! {
+        impl ::borsh::BorshSerialize for #ty_owned {
+            fn serialize<__W: ::borsh::io::Write>(&self, writer: &mut __W) -> ::borsh::io::Result<()> {
+                <#ty_owned_inner as ::borsh::BorshSerialize>::serialize(&#self_inner, writer)
+            }
+        }
+    }
+}
+
+/// Implements `clap::builder::ValueParserFactory`.
+///
+/// `OwnedInner` should be `String` or `OsString`, and the validator error
+/// should implement `Display`.
+pub(crate) fn impl_clap_value_parser(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let error_var = &quote!(_e);
+
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let base_parser = match defs.ty_owned_inner_name() {
+        Some(name) if name == "OsString" => quote!(::clap::builder::OsStringValueParser::new()),
+        _ => quote!(::clap::builder::StringValueParser::new()),
+    };
+
+    let parser = if defs.has_owned_validator() {
+        let (body, ty_error) = inner_to_outer_checked(defs, arg_name.as_ref(), error_var);
+        // The message is used as the reason of the value validation error,
+        // and clap reports it with the argument name and the value.
+        quote! {
+            ::clap::builder::TypedValueParser::try_map(
+                #base_parser,
+                |#arg_name: #ty_owned_inner| {
+                    ::core::result::Result::map_err(
+                        #body,
+                        |#error_var: #ty_error| ::std::string::ToString::to_string(&#error_var),
+                    )
+                },
+            )
+        }
+    } else {
+        let owned = arg_name.to_owned_unchecked(defs);
+        quote! {
+            ::clap::builder::TypedValueParser::map(
+                #base_parser,
+                |#arg_name: #ty_owned_inner| #owned,
+            )
+        }
+    };
+    quote! {
+        impl ::clap::builder::ValueParserFactory for #ty_owned {
+            type Parser = ::clap::builder::ValueParser;
+
+            fn value_parser() -> Self::Parser {
+                ::clap::builder::ValueParser::new(#parser)
+            }
+        }
+    }
+}
+
 /// Implements `PartialEq` and `PartialOrd` using comparison of `Slice` type.
 pub(crate) fn impl_cmp(defs: &Definitions, target: CmpTrait) -> TokenStream {
     target
@@ -151,6 +580,33 @@ pub(crate) fn impl_cmp_inner_bulk(defs: &Definitions, target: CmpTrait) -> Token
     tokens
 }
 
+/// Implements `bincode::Decode`.
+///
+/// The validator error should implement `Display`.
+pub(crate) fn impl_decode(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let body = inner_to_outer_mapped(
+        defs,
+        arg_name.as_ref(),
+        |e| quote!(::bincode::error::DecodeError::OtherString(::std::string::ToString::to_string(&#e))),
+    );
+    quote! {
+        impl<__Context> ::bincode::Decode<__Context> for #ty_owned
+        where
+            #ty_owned_inner: ::bincode::Decode<__Context>,
+        {
+            fn decode<__D: ::bincode::de::Decoder<Context = __Context>>(
+                decoder: &mut __D,
+            ) -> ::core::result::Result<Self, ::bincode::error::DecodeError> {
+                let #arg_name = <#ty_owned_inner as ::bincode::Decode<__Context>>::decode(decoder)?;
+                #body
+            }
+        }
+    }
+}
+
 /// Implements `Deref` or `DerefMut`.
 pub(crate) fn impl_deref(defs: &Definitions, mutability: impl Mutability) -> TokenStream {
     let trait_deref = OwnedToSliceTrait::Deref.trait_path(mutability);
@@ -183,19 +639,284 @@ pub(crate) fn impl_deref(defs: &Definitions, mutability: impl Mutability) -> Tok
     }
 }
 
+/// Implements `bincode::Encode`.
+pub(crate) fn impl_encode(defs: &Definitions) -> TokenStream {
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let self_inner: OwnedInner<_> = Owned::new(quote!(self)).to_owned_inner(defs);
+    quote! {
+        impl ::bincode::Encode for #ty_owned {
+            fn encode<__E: ::bincode::enc::Encoder>(
+                &self,
+                encoder: &mut __E,
+            ) -> ::core::result::Result<(), ::bincode::error::EncodeError> {
+                <#ty_owned_inner as ::bincode::Encode>::encode(&#self_inner, encoder)
+            }
+        }
+    }
+}
+
+/// Implements `Equivalent<Owned>` of the selected backends for `SliceInner`,
+/// and for `Slice` if possible.
+///
+/// The impl for `Slice` is generated only if the slice type derives `Hash`
+/// (so that the hash is same as `SliceInner`) but not `Eq` (otherwise it
+/// conflicts with the blanket impl using `Borrow<Slice>`).
+pub(crate) fn impl_equivalent(defs: &Definitions) -> TokenStream {
+    let ty_owned = defs.ty_owned();
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let key_inner = Owned::new(quote!(key))
+        .to_owned_inner(defs)
+        .to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
+    let self_inner = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+    let impl_slice = defs.slice_has_std_derive("Hash") && !defs.slice_has_std_derive("Eq");
+
+    // Equivalent values are checked to have the same hash in debug build.
+    let body = |lhs: &dyn ToTokens| {
+        quote! {
+            fn hash<T: ?::core::marker::Sized + ::core::hash::Hash>(v: &T) -> u64 {
+                let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+                ::core::hash::Hash::hash(v, &mut hasher);
+                ::core::hash::Hasher::finish(&hasher)
+            }
+
+            let _eq = <#ty_slice_inner as ::core::cmp::PartialEq>::eq(#lhs, #key_inner);
+            if _eq {
+                ::core::debug_assert_eq!(hash(self), hash(key), #EQUIVALENT_HASH_MESSAGE);
+            }
+            _eq
+        }
+    };
+    let body_slice_inner = body(&quote!(self));
+    let body_slice = body(&self_inner);
+
+    let mut tokens = TokenStream::new();
+    for backend in defs.equivalent_backends() {
+        let backend = syn::Ident::new(backend, Span::call_site());
+        let trait_equivalent = quote!(::#backend::Equivalent<#ty_owned>);
+        tokens.extend(quote! {
+            impl #trait_equivalent for #ty_slice_inner {
+                fn equivalent(&self, key: &#ty_owned) -> bool {
+                    #body_slice_inner
+                }
+            }
+        });
+        if impl_slice {
+            tokens.extend(quote! {
+                impl #trait_equivalent for #ty_slice {
+                    fn equivalent(&self, key: &#ty_owned) -> bool {
+                        #body_slice
+                    }
+                }
+            });
+        }
+    }
+    tokens
+}
+
 /// Implements `From<OwnedInner>`.
 pub(crate) fn impl_from_inner(defs: &Definitions) -> TokenStream {
-    if defs.has_validator() {
+    if defs.has_owned_validator() && !defs.owned_allows_safe_unchecked() {
         panic!("`From<OwnedInner>` cannot be implemented because a validator is specified");
     }
 
     let arg_name = OwnedInner::new(quote!(_v));
     let ty_owned = defs.ty_owned();
     let ty_owned_inner = defs.ty_owned_inner();
+    let debug_assertion = defs.debug_validate_owned_inner(&arg_name);
     let body: Owned<_> = arg_name.to_owned_unchecked(defs);
     quote! {
-        impl std::convert::From<#ty_owned_inner> for #ty_owned {
+        impl ::core::convert::From<#ty_owned_inner> for #ty_owned {
             fn from(#arg_name: #ty_owned_inner) -> Self {
+                #debug_assertion
+                #body
+            }
+        }
+    }
+}
+
+/// Implements `fmt::Write`.
+///
+/// `SliceInner` should be `str`.
+pub(crate) fn impl_fmt_write(defs: &Definitions) -> TokenStream {
+    let arg_name = quote!(_v);
+    let ty_owned = defs.ty_owned();
+    let (expr, _) = expr_append_checked(defs, quote!(::core::iter::once(#arg_name)));
+    quote! {
+        impl ::core::fmt::Write for #ty_owned {
+            fn write_str(&mut self, #arg_name: &str) -> ::core::fmt::Result {
+                ::core::result::Result::map_err(#expr, |_| ::core::fmt::Error)
+            }
+        }
+    }
+}
+
+/// Implements `io::Write`.
+///
+/// `SliceInner` should be `[u8]`, and the error of the incremental validator
+/// should implement `Into<Box<dyn std::error::Error + Send + Sync>>`.
+pub(crate) fn impl_io_write(defs: &Definitions) -> TokenStream {
+    let arg_name = quote!(_v);
+    let bufs = quote!(_bufs);
+    let ty_owned = defs.ty_owned();
+    let validator = incremental_validator(defs);
+    let current = Owned::new(quote!(self))
+        .to_owned_inner(defs)
+        .to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
+    let (expr, _) = expr_append_checked(defs, quote!(::core::iter::once(#arg_name)));
+    // Chunk boundaries between buffers are checked by the incremental
+    // validator, so the buffers are validated as a whole.
+    let (expr_vectored, _) = expr_append_checked(defs, &bufs);
+    quote! {
+        impl ::std::io::Write for #ty_owned {
+            fn write(&mut self, #arg_name: &[u8]) -> ::std::io::Result<usize> {
+                // Incomplete data at the end (such as a partial multi-byte
+                // character) is left for the next write.
+                let _len = {
+                    let mut _state = #validator::start(#current);
+                    match #validator::feed(&mut _state, #arg_name) {
+                        ::core::result::Result::Ok(_) => {
+                            <[u8]>::len(#arg_name) - #validator::pending_len(&_state)
+                        }
+                        ::core::result::Result::Err(_e) => {
+                            return ::core::result::Result::Err(::std::io::Error::new(
+                                ::std::io::ErrorKind::InvalidData,
+                                _e,
+                            ));
+                        }
+                    }
+                };
+                // Without complete data, the whole chunk is checked to report
+                // the error.
+                let #arg_name = if _len == 0 { #arg_name } else { &#arg_name[.._len] };
+                match #expr {
+                    ::core::result::Result::Ok(_) => ::core::result::Result::Ok(<[u8]>::len(#arg_name)),
+                    ::core::result::Result::Err(_e) => ::core::result::Result::Err(
+                        ::std::io::Error::new(::std::io::ErrorKind::InvalidData, _e),
+                    ),
+                }
+            }
+
+            fn write_vectored(
+                &mut self,
+                #bufs: &[::std::io::IoSlice<'_>],
+            ) -> ::std::io::Result<usize> {
+                match #expr_vectored {
+                    ::core::result::Result::Ok(_) => {
+                        let mut _len = 0;
+                        for _buf in #bufs {
+                            _len += <[u8]>::len(_buf);
+                        }
+                        ::core::result::Result::Ok(_len)
+                    }
+                    ::core::result::Result::Err(_e) => ::core::result::Result::Err(
+                        ::std::io::Error::new(::std::io::ErrorKind::InvalidData, _e),
+                    ),
+                }
+            }
+
+            fn flush(&mut self) -> ::std::io::Result<()> {
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// Implements `FromSql` traits of the selected backends.
+///
+/// The validator error should implement
+/// `std::error::Error + Send + Sync + 'static`.
+pub(crate) fn impl_from_sql(defs: &Definitions) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    for backend in defs.owned_sql_backends() {
+        match backend {
+            "postgres" => impl_postgres_from_sql(defs).to_tokens(&mut tokens),
+            "rusqlite" => impl_rusqlite_from_sql(defs).to_tokens(&mut tokens),
+            backend => unreachable!("Unknown SQL backend: {:?}", backend),
+        }
+    }
+    tokens
+}
+
+/// Implements `postgres_types::FromSql`.
+fn impl_postgres_from_sql(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let body = inner_to_outer_mapped(
+        defs,
+        arg_name.as_ref(),
+        |e| quote!(::core::convert::From::from(#e)),
+    );
+    quote! {
+        impl<'a> ::postgres_types::FromSql<'a> for #ty_owned {
+            fn from_sql(
+                ty: &::postgres_types::Type,
+                raw: &'a [u8],
+            ) -> ::core::result::Result<
+                Self,
+                ::std::boxed::Box<dyn ::std::error::Error + ::core::marker::Sync + ::core::marker::Send>,
+            > {
+                let #arg_name = <#ty_owned_inner as ::postgres_types::FromSql<'a>>::from_sql(ty, raw)?;
+                #body
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                <#ty_owned_inner as ::postgres_types::FromSql<'a>>::accepts(ty)
+            }
+        }
+    }
+}
+
+/// Implements `rusqlite::types::FromSql`.
+fn impl_rusqlite_from_sql(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let body = inner_to_outer_mapped(
+        defs,
+        arg_name.as_ref(),
+        |e| quote!(::rusqlite::types::FromSqlError::Other(::std::boxed::Box::new(#e))),
+    );
+    quote! {
+        impl ::rusqlite::types::FromSql for #ty_owned {
+            fn column_result(
+                value: ::rusqlite::types::ValueRef<'_>,
+            ) -> ::rusqlite::types::FromSqlResult<Self> {
+                let #arg_name = <#ty_owned_inner as ::rusqlite::types::FromSql>::column_result(value)?;
+                #body
+            }
+        }
+    }
+}
+
+/// Implements `FromStr`.
+///
+/// `OwnedInner` should implement `From<&str>`.
+pub(crate) fn impl_from_str(defs: &Definitions) -> TokenStream {
+    let arg_name = OwnedInner::new(quote!(_v));
+    let error_var = &quote!(_e);
+
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+
+    let (body, ty_error) = if defs.has_owned_validator() {
+        let (body, ty_error) = inner_to_outer_checked(defs, arg_name.as_ref(), error_var);
+        (body, ty_error.into_token_stream())
+    } else {
+        let owned = arg_name.to_owned_unchecked(defs);
+        (
+            quote!(::core::result::Result::Ok(#owned)),
+            quote!(::core::convert::Infallible),
+        )
+    };
+    quote! {
+        impl ::core::str::FromStr for #ty_owned {
+            type Err = #ty_error;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                let #arg_name: #ty_owned_inner = ::core::convert::From::from(s);
                 #body
             }
         }
@@ -204,19 +925,66 @@ pub(crate) fn impl_from_inner(defs: &Definitions) -> TokenStream {
 
 /// Implements `Into<OwnedInner>` (actually `From<Owned> for OwnedInner`).
 pub(crate) fn impl_into_inner(defs: &Definitions) -> TokenStream {
+    impl_into(defs, defs.ty_owned_inner())
+}
+
+/// Implements `Into<T>` (actually `From<Owned> for T`) for each type of
+/// `IntoVia(T, ..)`, through `OwnedInner: Into<T>`.
+pub(crate) fn impl_into_via(defs: &Definitions) -> TokenStream {
+    defs.owned_derive_types("IntoVia")
+        .iter()
+        .map(|ty| impl_into(defs, ty))
+        .collect()
+}
+
+/// Implements `From<Owned> for T` through `OwnedInner: Into<T>`.
+fn impl_into(defs: &Definitions, ty: impl ToTokens) -> TokenStream {
     let ty_owned = defs.ty_owned();
     let ty_owned_inner = defs.ty_owned_inner();
     let arg_name = Owned::new(quote!(_v));
     let body: OwnedInner<_> = arg_name.to_owned_inner(defs);
     quote! {
-        impl std::convert::From<#ty_owned> for #ty_owned_inner {
+        impl ::core::convert::From<#ty_owned> for #ty {
             fn from(#arg_name: #ty_owned) -> Self {
-                #body
+                <#ty_owned_inner as ::core::convert::Into<#ty>>::into(#body)
             }
         }
     }
 }
 
+/// Implements `schemars::JsonSchema`.
+pub(crate) fn impl_json_schema(defs: &Definitions) -> TokenStream {
+    super::impl_json_schema(defs, defs.ty_owned(), defs.owned_json_schema_conf())
+}
+
+/// Implements `ToSql` traits of the selected backends.
+pub(crate) fn impl_to_sql(defs: &Definitions) -> TokenStream {
+    let ty_owned = defs.ty_owned();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let self_inner: OwnedInner<_> = Owned::new(quote!(self)).to_owned_inner(defs);
+    let mut tokens = TokenStream::new();
+    for backend in defs.owned_sql_backends() {
+        match backend {
+            "postgres" => super::impl_postgres_to_sql(
+                quote!(),
+                &ty_owned,
+                &ty_owned_inner,
+                quote!(&#self_inner),
+            )
+            .to_tokens(&mut tokens),
+            "rusqlite" => tokens.extend(quote! {
+                impl ::rusqlite::types::ToSql for #ty_owned {
+                    fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                        <#ty_owned_inner as ::rusqlite::types::ToSql>::to_sql(&#self_inner)
+                    }
+                }
+            }),
+            backend => unreachable!("Unknown SQL backend: {:?}", backend),
+        }
+    }
+    tokens
+}
+
 /// Implements `TryFrom<OwnedInner>`.
 pub(crate) fn impl_try_from_inner(defs: &Definitions) -> TokenStream {
     let arg_name = OwnedInner::new(quote!(_v));
@@ -227,10 +995,10 @@ pub(crate) fn impl_try_from_inner(defs: &Definitions) -> TokenStream {
 
     let (body, ty_error) = inner_to_outer_checked(defs, arg_name.as_ref(), error_var);
     quote! {
-        impl std::convert::TryFrom<#ty_owned_inner> for #ty_owned {
+        impl ::core::convert::TryFrom<#ty_owned_inner> for #ty_owned {
             type Error = #ty_error;
 
-            fn try_from(#arg_name: #ty_owned_inner) -> std::result::Result<Self, Self::Error> {
+            fn try_from(#arg_name: #ty_owned_inner) -> ::core::result::Result<Self, Self::Error> {
                 #body
             }
         }
@@ -246,6 +1014,28 @@ pub(crate) fn inner_to_outer_checked(
     let (ty_error, mapped_error) = defs.owned_error_ty_and_val(&error_var, inner_var.as_ref());
 
     let expr_owned = inner_var.to_owned_unchecked(defs);
+    if let Some(validator) = defs.owned_validator() {
+        let fn_validate = validator.name();
+        let expr = if validator.takes_value() {
+            // The validator returns the value on success, and the error is used
+            // as is.
+            quote! {
+                match #fn_validate(#inner_var) {
+                    ::core::result::Result::Ok(#inner_var) => ::core::result::Result::Ok(#expr_owned),
+                    ::core::result::Result::Err(#error_var) => ::core::result::Result::Err(#error_var),
+                }
+            }
+        } else {
+            quote! {
+                match #fn_validate(&#inner_var) {
+                    ::core::result::Result::Ok(_) => ::core::result::Result::Ok(#expr_owned),
+                    ::core::result::Result::Err(#error_var) => ::core::result::Result::Err(#mapped_error),
+                }
+            }
+        };
+        return (expr, ty_error);
+    }
+
     let expr_slice_inner_ref =
         inner_var.to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
     let fn_validate = defs.fn_validator().unwrap_or_else(|| {
@@ -253,9 +1043,146 @@ pub(crate) fn inner_to_outer_checked(
     });
     let expr = quote! {
         match #fn_validate(#expr_slice_inner_ref) {
-            Ok(_) => Ok(#expr_owned),
-            Err(#error_var) => Err(#mapped_error),
+            ::core::result::Result::Ok(_) => ::core::result::Result::Ok(#expr_owned),
+            ::core::result::Result::Err(#error_var) => ::core::result::Result::Err(#mapped_error),
         }
     };
     (expr, ty_error)
 }
+
+/// Returns the expression of `Result<Owned, E>`, where the validator error is
+/// converted into `E` by `map_error`.
+///
+/// If the owned type has no validator, the value is always accepted.
+fn inner_to_outer_mapped(
+    defs: &Definitions,
+    inner_var: OwnedInner<impl ToTokens>,
+    map_error: impl FnOnce(&TokenStream) -> TokenStream,
+) -> TokenStream {
+    if !defs.has_owned_validator() {
+        let expr_owned = inner_var.to_owned_unchecked(defs);
+        return quote!(::core::result::Result::Ok(#expr_owned));
+    }
+    let error_var = quote!(_e);
+    let (expr, ty_error) = inner_to_outer_checked(defs, inner_var, &error_var);
+    let mapped_error = map_error(&error_var);
+    quote! {
+        ::core::result::Result::map_err(#expr, |#error_var: #ty_error| #mapped_error)
+    }
+}
+
+/// Returns the expression of `Option<Owned>`, which is `None` if the value
+/// does not pass the validator.
+fn inner_to_outer_opt(defs: &Definitions, inner_var: OwnedInner<impl ToTokens>) -> TokenStream {
+    let expr_owned = inner_var.to_owned_unchecked(defs);
+    if let Some(validator) = defs.owned_validator() {
+        let fn_validate = validator.name();
+        if validator.takes_value() {
+            return quote! {
+                match #fn_validate(#inner_var) {
+                    ::core::result::Result::Ok(#inner_var) => ::core::option::Option::Some(#expr_owned),
+                    ::core::result::Result::Err(_) => ::core::option::Option::None,
+                }
+            };
+        }
+        return quote! {
+            match #fn_validate(&#inner_var) {
+                ::core::result::Result::Ok(_) => ::core::option::Option::Some(#expr_owned),
+                ::core::result::Result::Err(_) => ::core::option::Option::None,
+            }
+        };
+    }
+
+    let fn_validate = match defs.fn_validator() {
+        Some(v) => v,
+        None => return quote!(::core::option::Option::Some(#expr_owned)),
+    };
+    let expr_slice_inner_ref =
+        inner_var.to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
+    quote! {
+        match #fn_validate(#expr_slice_inner_ref) {
+            ::core::result::Result::Ok(_) => ::core::option::Option::Some(#expr_owned),
+            ::core::result::Result::Err(_) => ::core::option::Option::None,
+        }
+    }
+}
+
+/// Returns the expression of `bool`, which is `true` if the value passes the
+/// validator.
+///
+/// `OwnedInner` should implement `Clone` if the owned validator takes the
+/// value.
+fn expr_owned_inner_is_valid(defs: &Definitions, inner: &OwnedInner<impl ToTokens>) -> TokenStream {
+    let expr_result = match defs.owned_validator() {
+        Some(validator) => {
+            let fn_validate = validator.name();
+            if validator.takes_value() {
+                quote!(#fn_validate(::core::clone::Clone::clone(&#inner)))
+            } else {
+                quote!(#fn_validate(&#inner))
+            }
+        }
+        None => {
+            let fn_validate = defs
+                .fn_validator()
+                .unwrap_or_else(|| panic!("Validator should be necessary to check values"));
+            let expr_slice_inner_ref =
+                inner.to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
+            quote!(#fn_validate(#expr_slice_inner_ref))
+        }
+    };
+    quote!(::core::result::Result::is_ok(&#expr_result))
+}
+
+/// Returns `<StateType as custom_slice::IncrementalValidator<SliceInner>>`.
+fn incremental_validator(defs: &Definitions) -> TokenStream {
+    let ty_validator = defs.ty_incremental_validator().unwrap_or_else(|| {
+        panic!("Incremental validator should be necessary for appending values")
+    });
+    let ty_slice_inner = defs.ty_slice_inner();
+    quote!(<#ty_validator as ::custom_slice::IncrementalValidator<#ty_slice_inner>>)
+}
+
+/// Returns `(expr_result, ty_error)` to validate the chunks by the incremental
+/// validator and append them to `self`.
+///
+/// `chunks` should be an expression of `IntoIterator` whose items can be
+/// coerced into `&SliceInner`, and it is evaluated twice.
+pub(crate) fn expr_append_checked(
+    defs: &Definitions,
+    chunks: impl ToTokens,
+) -> (TokenStream, TokenStream) {
+    let ty_slice_inner = defs.ty_slice_inner();
+    let ty_owned_inner = defs.ty_owned_inner();
+    let validator = incremental_validator(defs);
+    let owned_inner = Owned::new(quote!(self)).to_owned_inner(defs);
+    let current = owned_inner.to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant);
+    let debug_assertion = defs.debug_validate_owned_inner(&owned_inner);
+
+    let expr = quote! {{
+        let mut _state = #validator::start(#current);
+        let mut _result = ::core::result::Result::Ok(());
+        for _item in #chunks {
+            let _chunk: &#ty_slice_inner = _item;
+            _result = #validator::feed(&mut _state, _chunk);
+            if ::core::result::Result::is_err(&_result) {
+                break;
+            }
+        }
+        match ::core::result::Result::and_then(_result, |_| #validator::finish(_state)) {
+            ::core::result::Result::Ok(_) => {
+                for _item in #chunks {
+                    let _chunk: &#ty_slice_inner = _item;
+                    <#ty_owned_inner as ::custom_slice::Append<#ty_slice_inner>>::append(
+                        &mut #owned_inner,
+                        _chunk,
+                    );
+                }
+                #debug_assertion
+                ::core::result::Result::Ok(())
+            }
+            ::core::result::Result::Err(_e) => ::core::result::Result::Err(_e),
+        }
+    }};
+    (expr, quote!(#validator::Error))
+}
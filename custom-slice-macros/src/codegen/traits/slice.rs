@@ -7,12 +7,81 @@ use crate::{
     codegen::{
         expr::{Owned, Slice, SliceInner},
         props::{Constant, DynMutability, Mutability, Safety},
-        traits::{CmpTrait, OwnedToSliceTrait},
+        traits::{CmpTrait, OwnedToSliceTrait, FUZZ_REPAIR_MESSAGE},
         types::{RefType, SmartPtr, SmartPtrExt},
     },
     defs::Definitions,
 };
 
+/// Implements `arbitrary::Arbitrary` for `&Slice`.
+///
+/// Invalid values are repaired by `fuzz_repair` if available, and rejected
+/// otherwise.
+pub(crate) fn impl_arbitrary_fuzz(defs: &Definitions) -> TokenStream {
+    let arg_name = SliceInner::new(quote!(_v), Constant);
+    let lt = quote!('a);
+    let ty_slice_ref = Constant.make_ref_with_lifetime(defs.ty_slice(), &lt);
+    let ty_slice_inner_ref = Constant.make_ref_with_lifetime(defs.ty_slice_inner(), &lt);
+    let expr_slice = arg_name.to_slice_unchecked(defs, Safety::Safe);
+
+    let body = match (defs.fn_validator(), defs.slice_fuzz_repair()) {
+        (None, _) => quote!(::core::result::Result::Ok(#expr_slice)),
+        (Some(fn_validate), Some(repair)) => quote! {
+            let #arg_name: #ty_slice_inner_ref = match #fn_validate(#arg_name) {
+                ::core::result::Result::Ok(_) => #arg_name,
+                ::core::result::Result::Err(_) => (#repair)(#arg_name),
+            };
+            match #fn_validate(#arg_name) {
+                ::core::result::Result::Ok(_) => ::core::result::Result::Ok(#expr_slice),
+                ::core::result::Result::Err(_) => ::core::panic!(#FUZZ_REPAIR_MESSAGE),
+            }
+        },
+        (Some(fn_validate), None) => quote! {
+            match #fn_validate(#arg_name) {
+                ::core::result::Result::Ok(_) => ::core::result::Result::Ok(#expr_slice),
+                ::core::result::Result::Err(_) => {
+                    ::core::result::Result::Err(::arbitrary::Error::IncorrectFormat)
+                }
+            }
+        },
+    };
+    quote! {
+        impl<#lt> ::arbitrary::Arbitrary<#lt> for #ty_slice_ref {
+            fn arbitrary(_u: &mut ::arbitrary::Unstructured<#lt>) -> ::arbitrary::Result<Self> {
+                let #arg_name: #ty_slice_inner_ref =
+                    <#ty_slice_inner_ref as ::arbitrary::Arbitrary<#lt>>::arbitrary(_u)?;
+                #body
+            }
+
+            fn arbitrary_take_rest(_u: ::arbitrary::Unstructured<#lt>) -> ::arbitrary::Result<Self> {
+                let #arg_name: #ty_slice_inner_ref =
+                    <#ty_slice_inner_ref as ::arbitrary::Arbitrary<#lt>>::arbitrary_take_rest(_u)?;
+                #body
+            }
+
+            fn size_hint(_depth: usize) -> (usize, ::core::option::Option<usize>) {
+                <#ty_slice_inner_ref as ::arbitrary::Arbitrary<#lt>>::size_hint(_depth)
+            }
+        }
+    }
+}
+
+/// Implements `custom_slice::AsBytes`.
+pub(crate) fn impl_as_bytes(defs: &Definitions) -> TokenStream {
+    let ty_slice = defs.ty_slice();
+    let ty_elem = defs
+        .ty_slice_inner_elem()
+        .unwrap_or_else(|| panic!("`AsBytes` requires `[T]` as the slice inner type"));
+    let body: SliceInner<_, _> = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+    quote! {
+        impl ::custom_slice::AsBytes for #ty_slice {
+            fn as_bytes(&self) -> &[u8] {
+                ::bytemuck::cast_slice::<#ty_elem, u8>(#body)
+            }
+        }
+    }
+}
+
 /// Implements `AsRef<Slice>` or `AsMut<Slice>`.
 pub(crate) fn impl_as_ref_slice(defs: &Definitions, mutability: impl Mutability) -> TokenStream {
     let trait_as_ref = OwnedToSliceTrait::AsRef.trait_path(mutability);
@@ -53,6 +122,76 @@ pub(crate) fn impl_as_ref_slice_inner(
     }
 }
 
+/// Implements `AsRef<T>` for each type of `AsRefVia(T, ..)`, through
+/// `SliceInner: AsRef<T>`.
+pub(crate) fn impl_as_ref_via(defs: &Definitions) -> TokenStream {
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+
+    let self_inner: SliceInner<_, _> = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+    defs.slice_derive_types("AsRefVia")
+        .iter()
+        .map(|ty| {
+            quote! {
+                impl ::core::convert::AsRef<#ty> for #ty_slice {
+                    fn as_ref(&self) -> &#ty {
+                        <#ty_slice_inner as ::core::convert::AsRef<#ty>>::as_ref(#self_inner)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Implements `bincode::BorrowDecode` for `&Slice`.
+///
+/// The validator error should implement `Display`.
+pub(crate) fn impl_borrow_decode(defs: &Definitions) -> TokenStream {
+    let arg_name = SliceInner::new(quote!(_v), Constant);
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let body = if defs.has_validator() {
+        let error_var = &quote!(_e);
+        let (expr, ty_error) =
+            inner_to_outer_checked(defs, arg_name.as_ref(), error_var, Safety::Safe);
+        quote! {
+            ::core::result::Result::map_err(#expr, |#error_var: #ty_error| {
+                ::bincode::error::DecodeError::OtherString(::std::string::ToString::to_string(&#error_var))
+            })
+        }
+    } else {
+        let expr_slice = arg_name.to_slice_unchecked(defs, Safety::Safe);
+        quote!(::core::result::Result::Ok(#expr_slice))
+    };
+    quote! {
+        impl<'a, 'de: 'a, __Context> ::bincode::BorrowDecode<'de, __Context> for &'a #ty_slice
+        where
+            &'a #ty_slice_inner: ::bincode::BorrowDecode<'de, __Context>,
+        {
+            fn borrow_decode<__D: ::bincode::de::BorrowDecoder<'de, Context = __Context>>(
+                decoder: &mut __D,
+            ) -> ::core::result::Result<Self, ::bincode::error::DecodeError> {
+                let #arg_name = <&'a #ty_slice_inner as ::bincode::BorrowDecode<'de, __Context>>::borrow_decode(decoder)?;
+                #body
+            }
+        }
+    }
+}
+
+/// Implements `borsh::BorshSerialize`.
+pub(crate) fn impl_borsh_serialize(defs: &Definitions) -> TokenStream {
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let self_inner: SliceInner<_, _> = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+    quote! {
+        impl ::borsh::BorshSerialize for #ty_slice {
+            fn serialize<__W: ::borsh::io::Write>(&self, writer: &mut __W) -> ::borsh::io::Result<()> {
+                <#ty_slice_inner as ::borsh::BorshSerialize>::serialize(#self_inner, writer)
+            }
+        }
+    }
+}
+
 /// Implements `PartialEq` and `PartialOrd` for many types.
 pub(crate) fn impl_cmp_bulk(defs: &Definitions, target: CmpTrait) -> TokenStream {
     let mut tokens = TokenStream::new();
@@ -127,14 +266,14 @@ pub(crate) fn impl_default_ref(defs: &Definitions, mutability: impl Mutability)
     let ty_slice_ref = mutability.make_ref(defs.ty_slice());
     let ty_slice_inner_ref = mutability.make_ref(defs.ty_slice_inner());
 
-    let body: Slice<_, _> = SliceInner::new(
-        quote!(<#ty_slice_inner_ref as std::default::Default>::default()),
-        mutability,
-    )
-    .to_slice_unchecked(defs, Safety::Safe);
+    let inner = SliceInner::new(quote!(_v), mutability);
+    let debug_assertion = defs.debug_validate_slice_inner(&inner);
+    let body: Slice<_, _> = inner.to_slice_unchecked(defs, Safety::Safe);
     quote! {
-        impl std::default::Default for #ty_slice_ref {
+        impl ::core::default::Default for #ty_slice_ref {
             fn default() -> Self {
+                let #inner = <#ty_slice_inner_ref as ::core::default::Default>::default();
+                #debug_assertion
                 #body
             }
         }
@@ -147,17 +286,18 @@ pub(crate) fn impl_default_smartptr(defs: &Definitions, smartptr: impl SmartPtr)
     let ty_slice_inner = defs.ty_slice_inner();
 
     let ty_smartptr_slice = smartptr.ty(&ty_slice);
+    let ty_smartptr_slice_inner = smartptr.ty(&ty_slice_inner);
+    let inner = quote!(_v);
+    let debug_assertion = defs.debug_validate_slice_inner(&SliceInner::new(&inner, Constant));
     let expr_from_raw = {
-        let default_smartptr_inner = {
-            let ty_smartptr_slice_inner = smartptr.ty(&ty_slice_inner);
-            quote!(<#ty_smartptr_slice_inner as std::default::Default>::default())
-        };
-        let expr_into_raw_inner = smartptr.expr_into_raw(ty_slice_inner, default_smartptr_inner);
+        let expr_into_raw_inner = smartptr.expr_into_raw(ty_slice_inner, &inner);
         smartptr.expr_from_raw(&ty_slice, quote!(#expr_into_raw_inner as *mut #ty_slice))
     };
     quote! {
-        impl std::default::Default for #ty_smartptr_slice {
+        impl ::core::default::Default for #ty_smartptr_slice {
             fn default() -> Self {
+                let #inner = <#ty_smartptr_slice_inner as ::core::default::Default>::default();
+                #debug_assertion
                 unsafe { #expr_from_raw }
             }
         }
@@ -192,9 +332,76 @@ pub(crate) fn impl_deref(defs: &Definitions, mutability: impl Mutability) -> Tok
     }
 }
 
+/// Implements `bincode::Encode`.
+pub(crate) fn impl_encode(defs: &Definitions) -> TokenStream {
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let self_inner: SliceInner<_, _> = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+    quote! {
+        impl ::bincode::Encode for #ty_slice {
+            fn encode<__E: ::bincode::enc::Encoder>(
+                &self,
+                encoder: &mut __E,
+            ) -> ::core::result::Result<(), ::bincode::error::EncodeError> {
+                <#ty_slice_inner as ::bincode::Encode>::encode(#self_inner, encoder)
+            }
+        }
+    }
+}
+
+/// Implements `custom_slice::FromBytes`.
+///
+/// The bytes are cast into the slice inner type `[T]` by `bytemuck`, and then
+/// checked by the validator.
+pub(crate) fn impl_from_bytes(defs: &Definitions) -> TokenStream {
+    let ty_slice = defs.ty_slice();
+    let ty_elem = defs
+        .ty_slice_inner_elem()
+        .unwrap_or_else(|| panic!("`FromBytes` requires `[T]` as the slice inner type"));
+    let arg_name = SliceInner::new(quote!(_v), Constant);
+    let error_var = &quote!(_e);
+
+    let (body, ty_error) = if defs.has_validator() {
+        let (expr, ty_error) =
+            inner_to_outer_checked(defs, arg_name.as_ref(), error_var, Safety::Safe);
+        let body = quote! {
+            ::core::result::Result::map_err(#expr, ::custom_slice::CastError::Invalid)
+        };
+        (body, ty_error.into_token_stream())
+    } else {
+        let expr_slice = arg_name.to_slice_unchecked(defs, Safety::Safe);
+        let body = quote!(::core::result::Result::Ok(#expr_slice));
+        (body, quote!(::core::convert::Infallible))
+    };
+    quote! {
+        impl ::custom_slice::FromBytes for #ty_slice {
+            type Error = #ty_error;
+
+            fn from_bytes(
+                _bytes: &[u8],
+            ) -> ::core::result::Result<&Self, ::custom_slice::CastError<Self::Error>> {
+                let #arg_name: &[#ty_elem] = match ::bytemuck::try_cast_slice::<u8, #ty_elem>(_bytes) {
+                    ::core::result::Result::Ok(v) => v,
+                    ::core::result::Result::Err(
+                        ::bytemuck::PodCastError::TargetAlignmentGreaterAndInputNotAligned,
+                    ) => {
+                        return ::core::result::Result::Err(::custom_slice::CastError::Misaligned);
+                    }
+                    ::core::result::Result::Err(_) => {
+                        return ::core::result::Result::Err(
+                            ::custom_slice::CastError::InvalidLength,
+                        );
+                    }
+                };
+                #body
+            }
+        }
+    }
+}
+
 /// Implements `TryFrom<SliceInner>`.
 pub(crate) fn impl_from_inner(defs: &Definitions, mutability: impl Mutability) -> TokenStream {
-    if defs.has_validator() {
+    if defs.has_validator() && !defs.slice_allows_safe_unchecked() {
         panic!("`From<SliceInner>` cannot be implemented because a validator is specified");
     }
 
@@ -202,10 +409,12 @@ pub(crate) fn impl_from_inner(defs: &Definitions, mutability: impl Mutability) -
     let ty_slice_ref = mutability.make_ref_with_lifetime(defs.ty_slice(), &lt);
     let ty_slice_inner_ref = mutability.make_ref_with_lifetime(defs.ty_slice_inner(), &lt);
     let arg_name = SliceInner::new(quote!(_v), mutability);
+    let debug_assertion = defs.debug_validate_slice_inner(&arg_name);
     let body = arg_name.to_slice_unchecked(defs, Safety::Safe);
     quote! {
-        impl<#lt> std::convert::From<#ty_slice_inner_ref> for #ty_slice_ref {
+        impl<#lt> ::core::convert::From<#ty_slice_inner_ref> for #ty_slice_ref {
             fn from(#arg_name: #ty_slice_inner_ref) -> Self {
+                #debug_assertion
                 #body
             }
         }
@@ -219,45 +428,125 @@ pub(crate) fn impl_into_smartptr(defs: &Definitions, smartptr: impl SmartPtr) ->
     let arg_name = Slice::new(quote!(_v), Constant);
 
     let ty_smartptr_slice = smartptr.ty(&ty_slice);
+    let arg_inner_ref: SliceInner<_, _> = arg_name.to_slice_inner_ref(defs);
+    let debug_assertion = defs.debug_validate_slice_inner(&arg_inner_ref);
     let expr_from_raw = {
         let expr_smartptr_inner = {
             let ty_smartptr_slice_inner = smartptr.ty(&ty_slice_inner);
-            let arg_inner_ref: SliceInner<_, _> = arg_name.to_slice_inner_ref(defs);
             quote!(<#ty_smartptr_slice_inner>::from(#arg_inner_ref))
         };
         let expr_into_raw_inner = smartptr.expr_into_raw(ty_slice_inner, expr_smartptr_inner);
         smartptr.expr_from_raw(&ty_slice, quote!(#expr_into_raw_inner as *mut #ty_slice))
     };
     quote! {
-        impl std::convert::From<&#ty_slice> for #ty_smartptr_slice {
+        impl ::core::convert::From<&#ty_slice> for #ty_smartptr_slice {
             fn from(#arg_name: &#ty_slice) -> Self {
+                #debug_assertion
                 unsafe { #expr_from_raw }
             }
         }
     }
 }
 
+/// Implements `From<&Slice> for T` for each type of `IntoVia(T, ..)`,
+/// through `&SliceInner: Into<T>`.
+pub(crate) fn impl_into_via(defs: &Definitions) -> TokenStream {
+    let lt = quote!('a);
+    let ty_slice_ref = Constant.make_ref_with_lifetime(defs.ty_slice(), &lt);
+    let ty_slice_inner_ref = Constant.make_ref_with_lifetime(defs.ty_slice_inner(), &lt);
+    let arg_name = Slice::new(quote!(_v), Constant);
+    let arg_inner_ref: SliceInner<_, _> = arg_name.to_slice_inner_ref(defs);
+    defs.slice_derive_types("IntoVia")
+        .iter()
+        .map(|ty| {
+            quote! {
+                impl<#lt> ::core::convert::From<#ty_slice_ref> for #ty {
+                    fn from(#arg_name: #ty_slice_ref) -> Self {
+                        <#ty_slice_inner_ref as ::core::convert::Into<#ty>>::into(#arg_inner_ref)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Implements `schemars::JsonSchema`.
+pub(crate) fn impl_json_schema(defs: &Definitions) -> TokenStream {
+    super::impl_json_schema(defs, defs.ty_slice(), defs.slice_json_schema_conf())
+}
+
 /// Implements `ToOwned`.
 pub(crate) fn impl_to_owned(defs: &Definitions) -> TokenStream {
     let ty_owned = defs.ty_owned();
     let ty_slice = defs.ty_slice();
 
     // `&Slice` -> `&SliceInner` -> `OwnedInner` -> `Owned`.
-    let body: Owned<_> = Slice::new(quote!(self), Constant)
-        .to_slice_inner_ref(defs)
+    let slice_inner_ref = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+    let debug_assertion = defs.debug_validate_slice_inner(&slice_inner_ref);
+    let body: Owned<_> = slice_inner_ref
         .to_owned_inner(defs)
         .to_owned_unchecked(defs);
     quote! {
-        impl std::borrow::ToOwned for #ty_slice {
+        impl ::std::borrow::ToOwned for #ty_slice {
             type Owned = #ty_owned;
 
             fn to_owned(&self) -> Self::Owned {
+                #debug_assertion
                 #body
             }
         }
     }
 }
 
+/// Implements `ToSql` traits of the selected backends.
+///
+/// `postgres_types::ToSql` is implemented for `&Slice`, and
+/// `rusqlite::types::ToSql` is implemented for `Slice`.
+pub(crate) fn impl_to_sql(defs: &Definitions) -> TokenStream {
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    let mut tokens = TokenStream::new();
+    for backend in defs.slice_sql_backends() {
+        match backend {
+            "postgres" => {
+                let self_inner = Slice::new(quote!(*self), Constant).to_slice_inner_ref(defs);
+                super::impl_postgres_to_sql(
+                    quote!(<'a>),
+                    quote!(&'a #ty_slice),
+                    quote!(&'a #ty_slice_inner),
+                    quote!(&#self_inner),
+                )
+                .to_tokens(&mut tokens)
+            }
+            "rusqlite" => {
+                let self_inner = Slice::new(quote!(self), Constant).to_slice_inner_ref(defs);
+                tokens.extend(quote! {
+                    impl ::rusqlite::types::ToSql for #ty_slice {
+                        fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                            <#ty_slice_inner as ::rusqlite::types::ToSql>::to_sql(#self_inner)
+                        }
+                    }
+                })
+            }
+            backend => unreachable!("Unknown SQL backend: {:?}", backend),
+        }
+    }
+    tokens
+}
+
+/// Implements `bytemuck::TransparentWrapper<SliceInner>`.
+pub(crate) fn impl_transparent_wrapper(defs: &Definitions) -> TokenStream {
+    if defs.has_validator() && !defs.slice_allows_safe_unchecked() {
+        panic!("`TransparentWrapper` cannot be implemented because a validator is specified");
+    }
+
+    let ty_slice = defs.ty_slice();
+    let ty_slice_inner = defs.ty_slice_inner();
+    quote! {
+        unsafe impl ::bytemuck::TransparentWrapper<#ty_slice_inner> for #ty_slice {}
+    }
+}
+
 /// Implements `TryFrom<SliceInner>`.
 pub(crate) fn impl_try_from_inner(defs: &Definitions, mutability: impl Mutability) -> TokenStream {
     let arg_name = SliceInner::new(quote!(_v), mutability);
@@ -269,10 +558,10 @@ pub(crate) fn impl_try_from_inner(defs: &Definitions, mutability: impl Mutabilit
 
     let (body, ty_error) = inner_to_outer_checked(defs, arg_name.as_ref(), error_var, Safety::Safe);
     quote! {
-        impl<#lt> std::convert::TryFrom<#ty_slice_inner_ref> for #ty_slice_ref {
+        impl<#lt> ::core::convert::TryFrom<#ty_slice_inner_ref> for #ty_slice_ref {
             type Error = #ty_error;
 
-            fn try_from(#arg_name: #ty_slice_inner_ref) -> std::result::Result<Self, Self::Error> {
+            fn try_from(#arg_name: #ty_slice_inner_ref) -> ::core::result::Result<Self, Self::Error> {
                 #body
             }
         }
@@ -294,8 +583,8 @@ pub(crate) fn inner_to_outer_checked(
     });
     let expr = quote! {
         match #fn_validate(#inner_var) {
-            Ok(_) => Ok(#expr_slice),
-            Err(#error_var) => Err(#mapped_error),
+            ::core::result::Result::Ok(_) => ::core::result::Result::Ok(#expr_slice),
+            ::core::result::Result::Err(#error_var) => ::core::result::Result::Err(#mapped_error),
         }
     };
     (expr, ty_error)
@@ -31,9 +31,9 @@ pub(crate) enum StdSmartPtr {
 impl SmartPtr for StdSmartPtr {
     fn ty(&self, ty_inner: impl ToTokens) -> TokenStream {
         match self {
-            StdSmartPtr::Arc => quote!(std::sync::Arc<#ty_inner>),
-            StdSmartPtr::Box => quote!(std::boxed::Box<#ty_inner>),
-            StdSmartPtr::Rc => quote!(std::rc::Rc<#ty_inner>),
+            StdSmartPtr::Arc => quote!(::std::sync::Arc<#ty_inner>),
+            StdSmartPtr::Box => quote!(::std::boxed::Box<#ty_inner>),
+            StdSmartPtr::Rc => quote!(::std::rc::Rc<#ty_inner>),
         }
     }
 
@@ -62,6 +62,30 @@ pub(crate) trait SmartPtrExt: SmartPtr {
 
 impl<T: SmartPtr> SmartPtrExt for T {}
 
+/// Byte buffer types of `bytes` crate, used as the owned inner type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BytesBuf {
+    /// `bytes::Bytes`.
+    Bytes,
+    /// `bytes::BytesMut`.
+    BytesMut,
+}
+
+impl BytesBuf {
+    /// Returns the expression of the buffer copied from `&[u8]`.
+    pub(crate) fn expr_copy_from_slice(
+        self,
+        ty_buf: impl ToTokens,
+        expr: impl ToTokens,
+    ) -> TokenStream {
+        match self {
+            // `From<&'static [u8]>` does not copy the bytes.
+            BytesBuf::Bytes => quote!(<#ty_buf>::copy_from_slice(#expr)),
+            BytesBuf::BytesMut => quote!(<#ty_buf as ::core::convert::From<&[u8]>>::from(#expr)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum RefType {
     /// `Slice`.
@@ -93,7 +117,7 @@ impl RefType {
             RefType::Owned => defs.ty_owned().into_token_stream(),
             RefType::CowSlice => {
                 let ty_slice = defs.ty_slice();
-                quote!(std::borrow::Cow<'_, #ty_slice>)
+                quote!(::std::borrow::Cow<'_, #ty_slice>)
             }
             RefType::SliceInner => defs.ty_slice_inner().into_token_stream(),
             RefType::RefSliceInner => {
@@ -103,7 +127,7 @@ impl RefType {
             RefType::OwnedInner => defs.ty_owned_inner().into_token_stream(),
             RefType::CowSliceInner => {
                 let ty_slice_inner = defs.ty_slice_inner();
-                quote!(std::borrow::Cow<'_, #ty_slice_inner>)
+                quote!(::std::borrow::Cow<'_, #ty_slice_inner>)
             }
         }
     }
@@ -115,7 +139,7 @@ impl RefType {
             RefType::Slice => expr.into_token_stream(),
             RefType::RefSlice => quote!(*#expr),
             RefType::Owned | RefType::CowSlice => {
-                quote!(std::borrow::Borrow::<#ty_slice>::borrow(#expr))
+                quote!(::core::borrow::Borrow::<#ty_slice>::borrow(#expr))
             }
             RefType::SliceInner
             | RefType::RefSliceInner
@@ -147,7 +171,7 @@ impl RefType {
                 .to_slice_inner_ref(defs, OwnedToSliceTrait::Borrow, Constant)
                 .into_token_stream(),
             RefType::CowSlice => Slice::new(
-                quote!(std::borrow::Borrow::<#ty_slice>::borrow(#expr)),
+                quote!(::core::borrow::Borrow::<#ty_slice>::borrow(#expr)),
                 Constant,
             )
             .to_slice_inner_ref(defs)
@@ -155,7 +179,7 @@ impl RefType {
             RefType::SliceInner => expr.into_token_stream(),
             RefType::RefSliceInner => quote!(*#expr),
             RefType::OwnedInner | RefType::CowSliceInner => {
-                quote!(std::borrow::Borrow::<#ty_slice_inner>::borrow(#expr))
+                quote!(::core::borrow::Borrow::<#ty_slice_inner>::borrow(#expr))
             }
         }
     }
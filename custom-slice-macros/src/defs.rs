@@ -4,21 +4,119 @@ use std::convert::TryFrom;
 
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse_quote, Field, Fields, Ident, ItemFn, ItemStruct, Type};
+use syn::{
+    parse_quote, Attribute, Field, Fields, Ident, Item, ItemFn, ItemStruct, Type, Visibility,
+};
 
 use crate::{
-    attrs::CustomSliceAttrs,
+    attrs::{CustomSliceAttrs, FnPrefix, FnShape},
     codegen::{
+        error,
         expr::{Owned, OwnedInner, Slice, SliceInner},
-        props::{Constant, Mutability, Mutable, Safety},
-        traits::{self, CmpTrait},
-        types::StdSmartPtr,
+        props::{Constant, DynMutability, Mutability, Mutable, Safety},
+        traits::{self, CmpTrait, OwnedToSliceTrait},
+        types::{BytesBuf, StdSmartPtr},
     },
 };
 
 use self::builder::{Builder, LoadError};
+pub(crate) use self::input::{FnStub, Input};
+use self::normalizer::Normalizer;
+pub(crate) use self::validator::SchemaValue;
+use self::validator::{OwnedValidator, Validator};
 
 mod builder;
+mod input;
+mod normalizer;
+mod validator;
+
+/// Message for debug assertions of normalizer outputs.
+const NORMALIZER_MESSAGE: &str = "the normalizer output should pass the validator";
+
+/// Message for panics of prefix scanning constructors.
+const VALID_PREFIX_MESSAGE: &str = "the valid prefix should pass the validator";
+
+/// `# Panics` section of the generated prefix scanning methods.
+const VALID_PREFIX_PANICS: &str = "Panics if the valid prefix is rejected by the validator.";
+
+/// `# Panics` section of the generated `slice_ref` methods.
+const SLICE_REF_PANICS: &str = "Panics if the argument is not a subslice of `self`.";
+
+/// `# Panics` section of the generated `slice_range` methods.
+const SLICE_RANGE_PANICS: &str = "Panics if the range is out of bounds.";
+
+/// Message for debug assertions of `#[custom_slice(subslice_closed)]`.
+const SUBSLICE_CLOSED_MESSAGE: &str =
+    "the subslice should pass the validator because `subslice_closed` is specified";
+
+/// Appends the `# Panics` section to the doc comment of the generated method.
+fn append_panics_doc(new_fn: &mut ItemFn, description: &str) {
+    let has_doc = new_fn.attrs.iter().any(|attr| attr.path.is_ident("doc"));
+    let description = format!(" {}", description);
+    let lines = ["", " # Panics", "", &description];
+    let lines = if has_doc { &lines[..] } else { &lines[1..] };
+    new_fn.attrs.extend(
+        lines
+            .iter()
+            .map(|line| -> Attribute { parse_quote!(#[doc = #line]) }),
+    );
+}
+
+/// Message for debug assertions of `#[custom_slice(debug_validate)]`.
+const DEBUG_VALIDATE_MESSAGE: &str = "the value should pass the validator";
+
+/// Backends of `Arbitrary` derive target, and whether the features for them
+/// are enabled.
+const ARBITRARY_BACKENDS: &[(&str, bool)] = &[
+    ("proptest", cfg!(feature = "proptest")),
+    ("quickcheck", cfg!(feature = "quickcheck")),
+];
+
+/// Whether the feature for `ArbitraryFuzz` derive target is enabled.
+const ARBITRARY_FUZZ_ENABLED: bool = cfg!(feature = "arbitrary");
+
+/// Whether the feature for `AsBytes`, `FromBytes`, and `TransparentWrapper`
+/// derive targets is enabled.
+const BYTEMUCK_ENABLED: bool = cfg!(feature = "bytemuck");
+
+/// Whether the feature for `bytes::{Bytes, BytesMut}` as the owned inner type
+/// is enabled.
+const BYTES_ENABLED: bool = cfg!(feature = "bytes");
+
+/// Whether the feature for `ClapValueParser` derive target is enabled.
+const CLAP_ENABLED: bool = cfg!(feature = "clap");
+
+/// Backends of `Equivalent` derive target, and whether the features for them
+/// are enabled.
+const EQUIVALENT_BACKENDS: &[(&str, bool)] = &[
+    ("hashbrown", cfg!(feature = "hashbrown")),
+    ("indexmap", cfg!(feature = "indexmap")),
+];
+
+/// Whether the feature for `JsonSchema` derive target is enabled.
+const SCHEMARS_ENABLED: bool = cfg!(feature = "schemars");
+
+/// Derive targets for binary serialization, the features for them, and
+/// whether the features are enabled.
+const SERIALIZATION_DERIVES: &[(&str, &str, bool)] = &[
+    ("Archive", "rkyv", cfg!(feature = "rkyv")),
+    ("BorrowDecode", "bincode", cfg!(feature = "bincode")),
+    ("BorshDeserialize", "borsh", cfg!(feature = "borsh")),
+    ("BorshSerialize", "borsh", cfg!(feature = "borsh")),
+    ("Decode", "bincode", cfg!(feature = "bincode")),
+    ("Encode", "bincode", cfg!(feature = "bincode")),
+];
+
+/// Backends of `ToSql` and `FromSql` derive targets, and whether the features
+/// for them are enabled.
+const SQL_BACKENDS: &[(&str, bool)] = &[
+    ("postgres", cfg!(feature = "postgres")),
+    ("rusqlite", cfg!(feature = "rusqlite")),
+];
+
+/// Default maximum number of retries to generate a valid value for
+/// `Arbitrary` derive target.
+const DEFAULT_ARBITRARY_MAX_RETRIES: u64 = 1000;
 
 /// Definitions.
 pub(crate) struct Definitions {
@@ -28,6 +126,12 @@ pub(crate) struct Definitions {
     slice: CustomType,
     /// Validator function definition.
     validator: Option<Validator>,
+    /// Validator function definition for the owned type.
+    owned_validator: Option<OwnedValidator>,
+    /// Normalizer function definition.
+    normalizer: Option<Normalizer>,
+    /// Other items, passed through unchanged.
+    items: Vec<Item>,
 }
 
 impl Definitions {
@@ -40,8 +144,21 @@ impl Definitions {
         self.slice.create_item().to_tokens(&mut tokens);
         // Validator function definition.
         if let Some(validator) = &self.validator {
+            validator.create_items().to_tokens(&mut tokens);
+        }
+        if let Some(validator) = &self.owned_validator {
             validator.create_item().to_tokens(&mut tokens);
         }
+        // Normalizer function definition.
+        if let Some(normalizer) = &self.normalizer {
+            normalizer.create_item().to_tokens(&mut tokens);
+        }
+        // Other items.
+        for item in &self.items {
+            item.to_tokens(&mut tokens);
+        }
+        // Error type definition for the owned type.
+        error::gen_owned_error(self).to_tokens(&mut tokens);
 
         // Methods for owned type.
         self.impl_methods_for_owned().to_tokens(&mut tokens);
@@ -63,9 +180,9 @@ impl Definitions {
         tokens
     }
 
-    /// Loads a `Definitions` from the given file content.
-    pub(crate) fn from_file(file: syn::File) -> Result<Self, LoadError> {
-        Builder::try_from(file)?.build()
+    /// Loads a `Definitions` from the given macro input.
+    pub(crate) fn from_input(input: Input) -> Result<Self, LoadError> {
+        Builder::try_from(input)?.build()
     }
 
     pub(crate) fn ty_owned(&self) -> impl ToTokens {
@@ -76,6 +193,18 @@ impl Definitions {
         self.owned.inner_type().into_token_stream()
     }
 
+    /// Returns the last path segment of the owned inner type, such as `String`
+    /// for `std::string::String`.
+    pub(crate) fn ty_owned_inner_name(&self) -> Option<&Ident> {
+        self.owned.inner_type_name()
+    }
+
+    /// Returns the byte buffer type of `bytes` crate used as the owned inner
+    /// type, if available.
+    pub(crate) fn owned_inner_bytes_buf(&self) -> Option<BytesBuf> {
+        self.owned.inner_bytes_buf()
+    }
+
     pub(crate) fn ty_slice(&self) -> impl ToTokens {
         self.slice.outer_type().into_token_stream()
     }
@@ -84,14 +213,192 @@ impl Definitions {
         self.slice.inner_type().into_token_stream()
     }
 
+    /// Returns `T` of the slice inner type `[T]`.
+    pub(crate) fn ty_slice_inner_elem(&self) -> Option<&Type> {
+        self.slice.inner_slice_elem()
+    }
+
     pub(crate) fn has_validator(&self) -> bool {
         self.validator.is_some()
     }
 
     pub(crate) fn fn_validator(&self) -> Option<impl ToTokens> {
+        self.validator.as_ref().map(Validator::path)
+    }
+
+    /// Returns JSON Schema keywords and values for the constraints checked by
+    /// the validator.
+    pub(crate) fn validator_schema_keywords(&self) -> Vec<(&'static str, SchemaValue)> {
         self.validator
             .as_ref()
-            .map(|v| v.name().into_token_stream())
+            .map_or_else(Vec::new, Validator::schema_keywords)
+    }
+
+    /// Checks whether the owned type has a validator (including the slice
+    /// validator).
+    pub(crate) fn has_owned_validator(&self) -> bool {
+        self.owned_validator.is_some() || self.validator.is_some()
+    }
+
+    /// Checks whether the owned type allows safe unchecked conversions even
+    /// if a validator is specified.
+    pub(crate) fn owned_allows_safe_unchecked(&self) -> bool {
+        self.owned.attrs.find_key("allow_safe_unchecked").is_some()
+    }
+
+    /// Checks whether the slice type allows safe unchecked conversions even
+    /// if a validator is specified.
+    pub(crate) fn slice_allows_safe_unchecked(&self) -> bool {
+        self.slice.attrs.find_key("allow_safe_unchecked").is_some()
+    }
+
+    /// Returns the incremental validator type of the owned type, if
+    /// available.
+    pub(crate) fn ty_incremental_validator(&self) -> Option<syn::Type> {
+        self.owned
+            .attrs
+            .get_incremental_validator()
+            .unwrap_or_else(|e| panic!("Failed to parse incremental validator type: {}", e))
+            .map(|(_, ty)| ty)
+    }
+
+    /// Returns the generator for the `Arbitrary` backend, if the backend is
+    /// selected.
+    ///
+    /// `Some(None)` means the default generator for the inner type.
+    /// If no backends are specified by `arbitrary(..)`, all backends enabled
+    /// by the features are selected.
+    pub(crate) fn arbitrary_generator(&self, backend: &str) -> Option<Option<syn::Expr>> {
+        let get = |backend| {
+            self.owned
+                .attrs
+                .get_arbitrary_backend(backend)
+                .unwrap_or_else(|e| panic!("Failed to parse `arbitrary({})`: {}", backend, e))
+                .map(|(_, generator)| generator)
+        };
+        if let Some(generator) = get(backend) {
+            return Some(generator);
+        }
+        let is_explicit = ARBITRARY_BACKENDS
+            .iter()
+            .any(|&(backend, _)| get(backend).is_some());
+        let is_enabled = ARBITRARY_BACKENDS
+            .iter()
+            .any(|&(name, enabled)| name == backend && enabled);
+        if is_explicit || !is_enabled {
+            return None;
+        }
+        Some(None)
+    }
+
+    /// Returns the crate names of the backends of `Equivalent` derive target.
+    ///
+    /// If no backends are specified by `equivalent(..)`, all backends enabled
+    /// by the features are selected.
+    pub(crate) fn equivalent_backends(&self) -> Vec<&'static str> {
+        let explicit = EQUIVALENT_BACKENDS
+            .iter()
+            .map(|&(backend, _)| backend)
+            .filter(|backend| self.owned.attrs.get_equivalent_backend(backend).is_some())
+            .collect::<Vec<_>>();
+        if !explicit.is_empty() {
+            return explicit;
+        }
+        EQUIVALENT_BACKENDS
+            .iter()
+            .filter(|&&(_, enabled)| enabled)
+            .map(|&(backend, _)| backend)
+            .collect()
+    }
+
+    /// Returns the backends of `ToSql` and `FromSql` derive targets for the
+    /// owned type.
+    pub(crate) fn owned_sql_backends(&self) -> Vec<&'static str> {
+        get_sql_backends(&self.owned.attrs)
+    }
+
+    /// Returns the backends of `ToSql` derive target for the slice type.
+    pub(crate) fn slice_sql_backends(&self) -> Vec<&'static str> {
+        get_sql_backends(&self.slice.attrs)
+    }
+
+    /// Returns the type arguments of the derive target for the owned type.
+    pub(crate) fn owned_derive_types(&self, target: &str) -> &[Type] {
+        self.owned.attrs.derive_types(target).unwrap_or_default()
+    }
+
+    /// Returns the type arguments of the derive target for the slice type.
+    pub(crate) fn slice_derive_types(&self, target: &str) -> &[Type] {
+        self.slice.attrs.derive_types(target).unwrap_or_default()
+    }
+
+    /// Checks whether the slice type has the standard `#[derive(name)]`.
+    pub(crate) fn slice_has_std_derive(&self, name: &str) -> bool {
+        self.slice.attrs.has_std_derive(name)
+    }
+
+    /// Returns the function to repair invalid values of the owned type
+    /// generated by fuzzers, if available.
+    pub(crate) fn owned_fuzz_repair(&self) -> Option<syn::Expr> {
+        get_fuzz_repair(&self.owned.attrs)
+    }
+
+    /// Returns the function to repair invalid values of the slice type
+    /// generated by fuzzers, if available.
+    pub(crate) fn slice_fuzz_repair(&self) -> Option<syn::Expr> {
+        get_fuzz_repair(&self.slice.attrs)
+    }
+
+    /// Returns the description and the schema function of the owned type for
+    /// `JsonSchema` derive target.
+    pub(crate) fn owned_json_schema_conf(&self) -> (Option<String>, Option<syn::Expr>) {
+        get_json_schema_conf(&self.owned.attrs)
+    }
+
+    /// Returns the description and the schema function of the slice type for
+    /// `JsonSchema` derive target.
+    pub(crate) fn slice_json_schema_conf(&self) -> (Option<String>, Option<syn::Expr>) {
+        get_json_schema_conf(&self.slice.attrs)
+    }
+
+    /// Returns the normalizer function name, if available.
+    pub(crate) fn fn_normalizer(&self) -> Option<&Ident> {
+        self.normalizer.as_ref().map(Normalizer::name)
+    }
+
+    /// Returns the maximum number of retries to generate a valid value.
+    pub(crate) fn arbitrary_max_retries(&self) -> u64 {
+        self.owned
+            .attrs
+            .get_arbitrary_max_retries()
+            .map_or(DEFAULT_ARBITRARY_MAX_RETRIES, |(_, n)| n)
+    }
+
+    /// Returns the owned validator, if available.
+    pub(crate) fn owned_validator(&self) -> Option<&OwnedValidator> {
+        self.owned_validator.as_ref()
+    }
+
+    /// Returns the error type of the validator for the owned type.
+    pub(crate) fn ty_owned_validator_error(&self) -> Option<syn::Type> {
+        match &self.owned_validator {
+            Some(v) => v.error_type(),
+            None => self.validator.as_ref().and_then(Validator::error_type),
+        }
+    }
+
+    /// Returns the name of the error type to be generated for the owned type.
+    pub(crate) fn generated_owned_error(&self) -> Option<Ident> {
+        self.owned
+            .attrs
+            .get_generated_error()
+            .unwrap_or_else(|e| panic!("Failed to parse generated error type name: {}", e))
+            .map(|(_, name)| name)
+    }
+
+    /// Returns the visibility of the owned type.
+    pub(crate) fn vis_owned(&self) -> &Visibility {
+        &self.owned.item.vis
     }
 
     pub(crate) fn owned_error_ty_and_val(
@@ -99,7 +406,12 @@ impl Definitions {
         error_var: impl ToTokens,
         arg_name: OwnedInner<impl ToTokens>,
     ) -> (syn::Type, TokenStream) {
-        get_error_ty_and_val(&self.owned.attrs, error_var, arg_name)
+        // The generated error type is for the slice validator.
+        let default_error = match self.owned_validator {
+            Some(_) => None,
+            None => self.generated_error_type(),
+        };
+        get_error_ty_and_val(&self.owned.attrs, default_error, error_var, arg_name)
     }
 
     pub(crate) fn slice_error_ty_and_val(
@@ -107,7 +419,19 @@ impl Definitions {
         error_var: impl ToTokens,
         arg_name: SliceInner<impl ToTokens, impl Mutability>,
     ) -> (syn::Type, TokenStream) {
-        get_error_ty_and_val(&self.slice.attrs, error_var, arg_name)
+        get_error_ty_and_val(
+            &self.slice.attrs,
+            self.generated_error_type(),
+            error_var,
+            arg_name,
+        )
+    }
+
+    /// Returns the error type generated for the validator, if available.
+    fn generated_error_type(&self) -> Option<&Ident> {
+        self.validator
+            .as_ref()
+            .and_then(Validator::generated_error_type)
     }
 
     pub(crate) fn expr_owned_to_inner(
@@ -140,14 +464,23 @@ impl Definitions {
             .to_tokens(&mut body);
         self.impl_owned_constructor_checked("new_checked")
             .to_tokens(&mut body);
+        self.impl_owned_constructor_normalized("from_inner_normalized")
+            .to_tokens(&mut body);
+        self.impl_owned_valid_prefix("into_valid_prefix")
+            .to_tokens(&mut body);
+        self.impl_owned_push_checked("push_checked")
+            .to_tokens(&mut body);
+        self.impl_owned_slice_ref("slice_ref").to_tokens(&mut body);
+        self.impl_owned_slice_range("slice_range")
+            .to_tokens(&mut body);
         self.impl_owned_accessor("get_ref", Constant)
             .to_tokens(&mut body);
         self.impl_owned_accessor("get_mut", Mutable)
             .to_tokens(&mut body);
-        if let Some(fn_prefix) = self.owned.attrs.get_fn_prefix("into_inner") {
+        if let Some(fn_prefix) = self.owned.fn_prefix("into_inner") {
             let owned_inner = Owned::new(quote!(self)).to_owned_inner(self);
             let new_fn = fn_prefix
-                .build_item_with_raw_args(quote!(self), self.owned.inner_type(), owned_inner)
+                .build_item_with_receiver(FnShape::SelfValue, self.owned.inner_type(), owned_inner)
                 .unwrap_or_else(|e| panic!("Failed to parse `into_inner` attribute: {}", e));
             new_fn.to_tokens(&mut body);
         }
@@ -160,51 +493,334 @@ impl Definitions {
     }
 
     fn impl_owned_constructor_unchecked(&self, attr_name: &str) -> Option<ItemFn> {
-        let fn_prefix = self.owned.attrs.get_fn_prefix(attr_name)?;
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
 
         let ty_owned_inner = self.owned.inner_type();
         let arg_name = OwnedInner::new(quote!(_v));
+        let debug_assertion = self.debug_validate_owned_inner(&arg_name);
+        let expr_owned = arg_name.to_owned_unchecked(self);
         let new_fn = fn_prefix
             .build_item_with_named_arg(
                 &arg_name,
                 ty_owned_inner,
                 quote!(Self),
-                arg_name.to_owned_unchecked(self),
+                quote!({
+                    #debug_assertion
+                    #expr_owned
+                }),
             )
             .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
         Some(new_fn)
     }
 
     fn impl_owned_constructor_checked(&self, attr_name: &str) -> Option<ItemFn> {
-        let fn_prefix = self.owned.attrs.get_fn_prefix(attr_name)?;
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
         let arg_name = OwnedInner::new(quote!(_v));
         let error_var = &quote!(_e);
 
         let (expr, ty_error) =
-            traits::owned::inner_to_outer_checked(self, arg_name.as_ref(), &error_var);
+            traits::owned::inner_to_outer_checked(self, arg_name.as_ref(), error_var);
         let block = quote!({ #expr });
         let new_fn = fn_prefix
             .build_item_with_named_arg(
                 arg_name,
                 self.owned.inner_type(),
-                quote!(std::result::Result<Self, #ty_error>),
+                quote!(::core::result::Result<Self, #ty_error>),
                 block,
             )
             .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
         Some(new_fn)
     }
 
+    fn impl_owned_constructor_normalized(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
+        let normalizer = self
+            .normalizer
+            .as_ref()
+            .map(Normalizer::name)
+            .unwrap_or_else(|| panic!("Normalizer should be necessary for `{}`", attr_name));
+        let arg_name = OwnedInner::new(quote!(_v));
+        let ty_owned_inner = self.owned.inner_type();
+        let expr_slice_inner_ref =
+            arg_name.to_slice_inner_ref(self, OwnedToSliceTrait::Borrow, Constant);
+        let debug_assertion = self.debug_assert_valid_owned_inner(&arg_name, NORMALIZER_MESSAGE);
+        let expr_owned = arg_name.to_owned_unchecked(self);
+
+        let block = quote!({
+            let _normalized = match #normalizer(#expr_slice_inner_ref) {
+                ::std::borrow::Cow::Borrowed(_) => ::core::option::Option::None,
+                ::std::borrow::Cow::Owned(_v) => ::core::option::Option::Some(_v),
+            };
+            let #arg_name: #ty_owned_inner = match _normalized {
+                ::core::option::Option::Some(_v) => ::core::convert::From::from(_v),
+                ::core::option::Option::None => #arg_name,
+            };
+            #debug_assertion
+            #expr_owned
+        });
+        let new_fn = fn_prefix
+            .build_item_with_named_arg(&arg_name, ty_owned_inner, quote!(Self), block)
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        Some(new_fn)
+    }
+
+    fn impl_owned_valid_prefix(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
+        let fn_validate = self
+            .fn_validator()
+            .unwrap_or_else(|| panic!("Validator should be necessary for `{}`", attr_name));
+        let valid_up_to = self.valid_up_to();
+        let arg_name = OwnedInner::new(quote!(_v));
+        let ty_owned_inner = self.owned.inner_type();
+        let expr_slice_inner_ref =
+            arg_name.to_slice_inner_ref(self, OwnedToSliceTrait::Borrow, Constant);
+        let expr_owned = arg_name.to_owned_unchecked(self);
+
+        // The truncated value should be checked again by the validator for
+        // the owned type.
+        let (check_slice, check_owned) = match &self.owned_validator {
+            None => (
+                quote! {
+                    if ::core::result::Result::is_err(&#fn_validate(#expr_slice_inner_ref)) {
+                        ::core::panic!(#VALID_PREFIX_MESSAGE);
+                    }
+                },
+                TokenStream::new(),
+            ),
+            Some(validator) => {
+                let fn_validate_owned = validator.name();
+                let check_owned = if validator.takes_value() {
+                    quote! {
+                        let #arg_name = match #fn_validate_owned(#arg_name) {
+                            ::core::result::Result::Ok(_v) => _v,
+                            ::core::result::Result::Err(_) => ::core::panic!(#VALID_PREFIX_MESSAGE),
+                        };
+                    }
+                } else {
+                    quote! {
+                        if ::core::result::Result::is_err(&#fn_validate_owned(&#arg_name)) {
+                            ::core::panic!(#VALID_PREFIX_MESSAGE);
+                        }
+                    }
+                };
+                (TokenStream::new(), check_owned)
+            }
+        };
+
+        let block = quote!({
+            let mut #arg_name = #arg_name;
+            if let ::core::result::Result::Err(_e) = #fn_validate(#expr_slice_inner_ref) {
+                <#ty_owned_inner>::truncate(&mut #arg_name, (#valid_up_to)(&_e));
+                #check_slice
+            }
+            #check_owned
+            #expr_owned
+        });
+        let mut new_fn = fn_prefix
+            .build_item_with_named_arg(&arg_name, ty_owned_inner, quote!(Self), block)
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        append_panics_doc(&mut new_fn, VALID_PREFIX_PANICS);
+        Some(new_fn)
+    }
+
+    fn impl_owned_push_checked(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
+        let arg_name = quote!(_v);
+        let ty_slice_inner = self.slice.inner_type();
+        let (expr, ty_error) =
+            traits::owned::expr_append_checked(self, quote!(::core::iter::once(#arg_name)));
+        let new_fn = fn_prefix
+            .build_item_with_receiver_and_arg(
+                FnShape::RefMutSelfArg,
+                &arg_name,
+                quote!(&#ty_slice_inner),
+                quote!(::core::result::Result<(), #ty_error>),
+                expr,
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        Some(new_fn)
+    }
+
+    /// Generates a method to get the owned value of a subslice of `self`
+    /// without copying.
+    ///
+    /// The argument is checked by the slice validator, so the result is
+    /// checked only by the owned validator.
+    ///
+    /// The generated method panics if the argument is not a subslice of
+    /// `self`, since `Bytes::slice_ref` does.
+    /// A valid `&Slice` may point to anywhere, so this is not prevented by
+    /// the validators, and the panic is documented on the generated method.
+    fn impl_owned_slice_ref(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
+        let arg_name = quote!(_v);
+        let ty_slice = self.ty_slice();
+        let ty_owned_inner = self.owned.inner_type();
+        let self_inner = Owned::new(quote!(self)).to_owned_inner(self);
+        let subset = Slice::new(&arg_name, Constant).to_slice_inner_ref(self);
+        let (ty_ret, body) = self.expr_owned_subslice(
+            quote!(<#ty_owned_inner>::slice_ref(&#self_inner, #subset)),
+            self.owned_validator.is_some(),
+        );
+        let mut new_fn = fn_prefix
+            .build_item_with_receiver_and_arg(
+                FnShape::RefSelfArg,
+                &arg_name,
+                quote!(&#ty_slice),
+                ty_ret,
+                body,
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        append_panics_doc(&mut new_fn, SLICE_REF_PANICS);
+        Some(new_fn)
+    }
+
+    /// Generates a method to get the owned value of the given range of `self`
+    /// without copying.
+    fn impl_owned_slice_range(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
+        let arg_name = quote!(_v);
+        let ty_owned_inner = self.owned.inner_type();
+        let self_inner = Owned::new(quote!(self)).to_owned_inner(self);
+        let (ty_ret, body) = self.expr_owned_subslice(
+            quote!(<#ty_owned_inner>::slice(&#self_inner, #arg_name)),
+            self.has_owned_validator(),
+        );
+        let mut new_fn = fn_prefix
+            .build_item_with_receiver_and_arg(
+                FnShape::RefSelfArg,
+                &arg_name,
+                quote!(impl ::core::ops::RangeBounds<usize>),
+                ty_ret,
+                body,
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        append_panics_doc(&mut new_fn, SLICE_RANGE_PANICS);
+        Some(new_fn)
+    }
+
+    /// Returns `(ty_ret, body)` of the method to create an owned value from
+    /// the subslice `expr_inner` of `OwnedInner`.
+    ///
+    /// The value is checked if `requires_check` is true and the owned type is
+    /// not declared as `subslice_closed`.
+    fn expr_owned_subslice(
+        &self,
+        expr_inner: TokenStream,
+        requires_check: bool,
+    ) -> (TokenStream, TokenStream) {
+        let inner = OwnedInner::new(quote!(_inner));
+        let is_closed = self.owned.attrs.find_key("subslice_closed").is_some();
+        if requires_check && !is_closed {
+            let (expr, ty_error) =
+                traits::owned::inner_to_outer_checked(self, inner.as_ref(), quote!(_e));
+            let body = quote! {
+                let #inner = #expr_inner;
+                #expr
+            };
+            return (quote!(::core::result::Result<Self, #ty_error>), body);
+        }
+        let debug_assertion = if is_closed {
+            self.debug_assert_valid_owned_inner(&inner, SUBSLICE_CLOSED_MESSAGE)
+        } else {
+            TokenStream::new()
+        };
+        let expr_owned = inner.to_owned_unchecked(self);
+        let body = quote! {
+            let #inner = #expr_inner;
+            #debug_assertion
+            #expr_owned
+        };
+        (quote!(Self), body)
+    }
+
+    /// Returns a debug assertion for `#[custom_slice(debug_validate)]` of the
+    /// owned type.
+    pub(crate) fn debug_validate_owned_inner(
+        &self,
+        inner: &OwnedInner<impl ToTokens>,
+    ) -> TokenStream {
+        if self.owned.attrs.find_key("debug_validate").is_none() {
+            return TokenStream::new();
+        }
+        self.debug_assert_valid_owned_inner(inner, DEBUG_VALIDATE_MESSAGE)
+    }
+
+    /// Returns a debug assertion for `#[custom_slice(debug_validate)]` of the
+    /// slice type.
+    pub(crate) fn debug_validate_slice_inner(
+        &self,
+        inner: &SliceInner<impl ToTokens, impl Mutability>,
+    ) -> TokenStream {
+        if self.slice.attrs.find_key("debug_validate").is_none() {
+            return TokenStream::new();
+        }
+        self.debug_assert_valid_slice_inner(inner, DEBUG_VALIDATE_MESSAGE)
+    }
+
+    /// Returns a debug assertion that the owned inner value passes the
+    /// validator, if available.
+    fn debug_assert_valid_owned_inner(
+        &self,
+        inner: &OwnedInner<impl ToTokens>,
+        message: &str,
+    ) -> TokenStream {
+        let expr_result = match &self.owned_validator {
+            Some(validator) if !validator.takes_value() => {
+                let fn_validate = validator.name();
+                quote!(#fn_validate(&#inner))
+            }
+            // Validators taking the value cannot be called without cloning
+            // the value, so the slice validator is used if available.
+            _ => match self.fn_validator() {
+                Some(fn_validate) => {
+                    let expr_slice_inner_ref =
+                        inner.to_slice_inner_ref(self, OwnedToSliceTrait::Borrow, Constant);
+                    quote!(#fn_validate(#expr_slice_inner_ref))
+                }
+                None => return TokenStream::new(),
+            },
+        };
+        quote! {
+            ::core::debug_assert!(::core::result::Result::is_ok(&#expr_result), #message);
+        }
+    }
+
+    /// Returns a debug assertion that the slice inner value passes the
+    /// validator, if available.
+    fn debug_assert_valid_slice_inner(
+        &self,
+        inner: &SliceInner<impl ToTokens, impl Mutability>,
+        message: &str,
+    ) -> TokenStream {
+        let fn_validate = match self.fn_validator() {
+            Some(v) => v,
+            None => return TokenStream::new(),
+        };
+        quote! {
+            ::core::debug_assert!(::core::result::Result::is_ok(&#fn_validate(&*#inner)), #message);
+        }
+    }
+
     fn impl_owned_accessor(&self, attr_name: &str, mutability: impl Mutability) -> Option<ItemFn> {
-        let fn_prefix = self.owned.attrs.get_fn_prefix(attr_name)?;
+        let fn_prefix = self.owned.fn_prefix(attr_name)?;
 
-        let self_ref = mutability.make_ref(quote!(self));
         let owned = Owned::new(quote!(self));
         let ty_owned_inner_ref = mutability.make_ref(self.owned.inner_type());
+        // Check the value before it is modified through the mutable reference.
+        let debug_assertion = match mutability.into() {
+            DynMutability::Constant => TokenStream::new(),
+            DynMutability::Mutable => self.debug_validate_owned_inner(&owned.to_owned_inner(self)),
+        };
+        let expr_inner_ref = mutability.make_ref(owned.to_owned_inner(self));
         let new_fn = fn_prefix
-            .build_item_with_raw_args(
-                &self_ref,
+            .build_item_with_receiver(
+                FnShape::ref_self(mutability),
                 ty_owned_inner_ref,
-                mutability.make_ref(owned.to_owned_inner(self)),
+                quote!({
+                    #debug_assertion
+                    #expr_inner_ref
+                }),
             )
             .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
         Some(new_fn)
@@ -221,6 +837,12 @@ impl Definitions {
             .to_tokens(&mut body);
         self.impl_slice_constructor_checked("new_checked_mut", Mutable)
             .to_tokens(&mut body);
+        self.impl_slice_constructor_normalized("from_inner_normalized")
+            .to_tokens(&mut body);
+        self.impl_slice_from_prefix("from_prefix")
+            .to_tokens(&mut body);
+        self.impl_slice_split_valid_prefix("split_valid_prefix")
+            .to_tokens(&mut body);
         self.impl_slice_accessor("get_ref", Constant)
             .to_tokens(&mut body);
         self.impl_slice_accessor("get_mut", Mutable)
@@ -242,12 +864,19 @@ impl Definitions {
         let ty_slice_inner_ref = mutability.make_ref(self.slice.inner_type());
         let ty_slice_ref = mutability.make_ref(self.slice.outer_type());
 
-        let fn_prefix = self.slice.attrs.get_fn_prefix(attr_name)?;
-        let mut new_fn = fn_prefix
-            .build_item_with_named_arg(&arg_name, ty_slice_inner_ref, ty_slice_ref, quote!())
+        let fn_prefix = self.slice.fn_prefix(attr_name)?;
+        let safety = fn_prefix
+            .safety()
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        let debug_assertion = self.debug_validate_slice_inner(&arg_name);
+        let expr_slice = arg_name.to_slice_unchecked(self, safety);
+        let block = quote!({
+            #debug_assertion
+            #expr_slice
+        });
+        let new_fn = fn_prefix
+            .build_item_with_named_arg(&arg_name, ty_slice_inner_ref, ty_slice_ref, block)
             .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
-        let block = arg_name.to_slice_unchecked(self, Safety::from(&new_fn.unsafety));
-        *new_fn.block = parse_quote!({ #block });
         Some(new_fn)
     }
 
@@ -256,7 +885,7 @@ impl Definitions {
         attr_name: &str,
         mutability: impl Mutability,
     ) -> Option<ItemFn> {
-        let fn_prefix = self.slice.attrs.get_fn_prefix(attr_name)?;
+        let fn_prefix = self.slice.fn_prefix(attr_name)?;
         let arg_name = SliceInner::new(quote!(_v), mutability);
         let error_var = &quote!(_e);
 
@@ -267,30 +896,170 @@ impl Definitions {
             traits::slice::inner_to_outer_checked(self, arg_name.as_ref(), error_var, Safety::Safe);
 
         let ty_slice_ref = mutability.make_ref(self.slice.outer_type());
-        let mut new_fn = fn_prefix
+        let new_fn = fn_prefix
             .build_item_with_named_arg(
                 arg_name.as_ref(),
                 mutability.make_ref(self.slice.inner_type()),
-                quote!(std::result::Result<#ty_slice_ref, #ty_error>),
-                quote!(),
+                quote!(::core::result::Result<#ty_slice_ref, #ty_error>),
+                expr,
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        Some(new_fn)
+    }
+
+    fn impl_slice_constructor_normalized(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.slice.fn_prefix(attr_name)?;
+        let normalizer = self
+            .normalizer
+            .as_ref()
+            .map(Normalizer::name)
+            .unwrap_or_else(|| panic!("Normalizer should be necessary for `{}`", attr_name));
+        let arg_name = SliceInner::new(quote!(_v), Constant);
+        let ty_slice = self.slice.outer_type();
+        let ty_owned_inner = self.owned.inner_type();
+        let owned_inner = OwnedInner::new(quote!(_v));
+        let debug_assertion_borrowed =
+            self.debug_assert_valid_slice_inner(&arg_name, NORMALIZER_MESSAGE);
+        let debug_assertion_owned =
+            self.debug_assert_valid_owned_inner(&owned_inner, NORMALIZER_MESSAGE);
+        let expr_slice = arg_name.to_slice_unchecked(self, Safety::Safe);
+        let expr_owned = owned_inner.to_owned_unchecked(self);
+
+        let block = quote!({
+            match #normalizer(#arg_name) {
+                ::std::borrow::Cow::Borrowed(#arg_name) => {
+                    #debug_assertion_borrowed
+                    ::std::borrow::Cow::Borrowed(#expr_slice)
+                }
+                ::std::borrow::Cow::Owned(_v) => {
+                    let #owned_inner: #ty_owned_inner = ::core::convert::From::from(_v);
+                    #debug_assertion_owned
+                    ::std::borrow::Cow::Owned(#expr_owned)
+                }
+            }
+        });
+        let new_fn = fn_prefix
+            .build_item_with_named_arg(
+                &arg_name,
+                Constant.make_ref(self.slice.inner_type()),
+                quote!(::std::borrow::Cow<'_, #ty_slice>),
+                block,
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        Some(new_fn)
+    }
+
+    fn impl_slice_from_prefix(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.slice.fn_prefix(attr_name)?;
+        let arg_name = SliceInner::new(quote!(_v), Constant);
+        let ty_slice_inner = self.slice.inner_type();
+        let ty_slice = self.slice.outer_type();
+        let expr = self.expr_split_prefix(&arg_name);
+        let new_fn = fn_prefix
+            .build_item_with_named_arg(
+                &arg_name,
+                quote!(&#ty_slice_inner),
+                quote!(::core::option::Option<(&#ty_slice, &#ty_slice_inner)>),
+                quote!({ #expr }),
             )
             .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
-        let block = parse_quote!({ #expr });
-        *new_fn.block = block;
         Some(new_fn)
     }
 
+    fn impl_slice_split_valid_prefix(&self, attr_name: &str) -> Option<ItemFn> {
+        let fn_prefix = self.slice.fn_prefix(attr_name)?;
+        let arg_name = SliceInner::new(quote!(_v), Constant);
+        let ty_slice_inner = self.slice.inner_type();
+        let ty_slice = self.slice.outer_type();
+        let expr = self.expr_split_prefix(&arg_name);
+        let block = quote!({
+            match #expr {
+                ::core::option::Option::Some(_v) => _v,
+                ::core::option::Option::None => ::core::panic!(#VALID_PREFIX_MESSAGE),
+            }
+        });
+        let mut new_fn = fn_prefix
+            .build_item_with_named_arg(
+                &arg_name,
+                quote!(&#ty_slice_inner),
+                quote!((&#ty_slice, &#ty_slice_inner)),
+                block,
+            )
+            .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
+        append_panics_doc(&mut new_fn, VALID_PREFIX_PANICS);
+        Some(new_fn)
+    }
+
+    /// Returns an expression to split the slice inner value into the longest
+    /// valid prefix and the rest.
+    ///
+    /// The expression has the type `Option<(&Slice, &SliceInner)>`, and it
+    /// is `None` if the prefix does not pass the validator.
+    fn expr_split_prefix(&self, inner: &SliceInner<impl ToTokens, Constant>) -> TokenStream {
+        let fn_validate = self
+            .fn_validator()
+            .unwrap_or_else(|| panic!("Validator should be necessary for prefix scanning"));
+        let valid_up_to = self.valid_up_to();
+        let ty_slice_inner = self.slice.inner_type();
+        let prefix = SliceInner::new(quote!(_prefix), Constant);
+        let expr_slice = inner.to_slice_unchecked(self, Safety::Safe);
+        let expr_prefix = prefix.to_slice_unchecked(self, Safety::Safe);
+        quote! {
+            match #fn_validate(#inner) {
+                ::core::result::Result::Ok(_) => {
+                    let _len = <#ty_slice_inner>::len(#inner);
+                    ::core::option::Option::Some((
+                        #expr_slice,
+                        ::core::ops::Index::index(#inner, _len..),
+                    ))
+                }
+                ::core::result::Result::Err(_e) => {
+                    let _len = (#valid_up_to)(&_e);
+                    let #prefix: &#ty_slice_inner = ::core::ops::Index::index(#inner, .._len);
+                    match #fn_validate(#prefix) {
+                        ::core::result::Result::Ok(_) => ::core::option::Option::Some((
+                            #expr_prefix,
+                            ::core::ops::Index::index(#inner, _len..),
+                        )),
+                        ::core::result::Result::Err(_) => ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the function to get the length of the valid prefix from the
+    /// validator error.
+    fn valid_up_to(&self) -> syn::Expr {
+        self.slice
+            .attrs
+            .get_valid_up_to()
+            .unwrap_or_else(|e| panic!("Failed to parse `valid_up_to`: {}", e))
+            .map(|(_, expr)| expr)
+            .unwrap_or_else(|| panic!("`valid_up_to` should be necessary for prefix scanning"))
+    }
+
     fn impl_slice_accessor(&self, attr_name: &str, mutability: impl Mutability) -> Option<ItemFn> {
-        let fn_prefix = self.slice.attrs.get_fn_prefix(attr_name)?;
+        let fn_prefix = self.slice.fn_prefix(attr_name)?;
 
-        let self_ref = mutability.make_ref(quote!(self));
         let slice = Slice::new(quote!(self), mutability);
         let ty_slice_inner_ref = mutability.make_ref(self.slice.inner_type());
+        // Check the value before it is modified through the mutable reference.
+        let debug_assertion = match mutability.into() {
+            DynMutability::Constant => TokenStream::new(),
+            DynMutability::Mutable => self.debug_validate_slice_inner(
+                &Slice::new(quote!(self), Constant).to_slice_inner_ref(self),
+            ),
+        };
+        let expr_inner_ref = slice.to_slice_inner_ref(self);
         let new_fn = fn_prefix
-            .build_item_with_raw_args(
-                &self_ref,
+            .build_item_with_receiver(
+                FnShape::ref_self(mutability),
                 ty_slice_inner_ref,
-                slice.to_slice_inner_ref(self),
+                quote!({
+                    #debug_assertion
+                    #expr_inner_ref
+                }),
             )
             .unwrap_or_else(|e| panic!("Failed to parse `{}` attribute: {}", attr_name, e));
         Some(new_fn)
@@ -302,14 +1071,30 @@ impl Definitions {
         self.owned.attrs.derives().map(move |derive| {
             let derive = derive.to_string();
             match derive.as_str() {
+                "Arbitrary" => traits::owned::impl_arbitrary(self),
+                "ArbitraryFuzz" => traits::owned::impl_arbitrary_fuzz(self),
                 "AsRefSlice" => traits::owned::impl_as_ref_slice(self, Constant),
                 "AsRefSliceInner" => traits::owned::impl_as_ref_slice_inner(self, Constant),
                 "AsMutSlice" => traits::owned::impl_as_ref_slice(self, Mutable),
                 "AsMutSliceInner" => traits::owned::impl_as_ref_slice_inner(self, Mutable),
+                "AsRefVia" => traits::owned::impl_as_ref_via(self),
+                "Archive" => traits::owned::impl_archive(self),
+                "BorrowDecode" => traits::owned::impl_borrow_decode(self),
                 "BorrowMut" => traits::owned::impl_borrow(self, Mutable),
+                "BorshDeserialize" => traits::owned::impl_borsh_deserialize(self),
+                "BorshSerialize" => traits::owned::impl_borsh_serialize(self),
+                "ClapValueParser" => traits::owned::impl_clap_value_parser(self),
                 "Deref" => traits::owned::impl_deref(self, Constant),
                 "DerefMut" => traits::owned::impl_deref(self, Mutable),
+                "Decode" => traits::owned::impl_decode(self),
+                "Encode" => traits::owned::impl_encode(self),
+                "Equivalent" => traits::owned::impl_equivalent(self),
                 "FromInner" => traits::owned::impl_from_inner(self),
+                "FmtWrite" => traits::owned::impl_fmt_write(self),
+                "FromSql" => traits::owned::impl_from_sql(self),
+                "FromStr" => traits::owned::impl_from_str(self),
+                "IoWrite" => traits::owned::impl_io_write(self),
+                "JsonSchema" => traits::owned::impl_json_schema(self),
                 "PartialEq" => traits::owned::impl_cmp(self, CmpTrait::PartialEq),
                 "PartialEqBulk" => traits::owned::impl_cmp_bulk(self, CmpTrait::PartialEq),
                 "PartialEqInnerBulk" => {
@@ -321,6 +1106,8 @@ impl Definitions {
                     traits::owned::impl_cmp_inner_bulk(self, CmpTrait::PartialOrd)
                 }
                 "IntoInner" => traits::owned::impl_into_inner(self),
+                "IntoVia" => traits::owned::impl_into_via(self),
+                "ToSql" => traits::owned::impl_to_sql(self),
                 "TryFromInner" => traits::owned::impl_try_from_inner(self),
                 derive => panic!("Unknown derive target for slice type: {:?}", derive),
             }
@@ -333,10 +1120,15 @@ impl Definitions {
         self.slice.attrs.derives().map(move |derive| {
             let derive = derive.to_string();
             match derive.as_str() {
+                "ArbitraryFuzz" => traits::slice::impl_arbitrary_fuzz(self),
+                "AsBytes" => traits::slice::impl_as_bytes(self),
                 "AsRefSlice" => traits::slice::impl_as_ref_slice(self, Constant),
                 "AsRefSliceInner" => traits::slice::impl_as_ref_slice_inner(self, Constant),
                 "AsMutSlice" => traits::slice::impl_as_ref_slice(self, Mutable),
                 "AsMutSliceInner" => traits::slice::impl_as_ref_slice_inner(self, Mutable),
+                "AsRefVia" => traits::slice::impl_as_ref_via(self),
+                "BorrowDecode" => traits::slice::impl_borrow_decode(self),
+                "BorshSerialize" => traits::slice::impl_borsh_serialize(self),
                 "DefaultArc" => traits::slice::impl_default_smartptr(self, StdSmartPtr::Arc),
                 "DefaultBox" => traits::slice::impl_default_smartptr(self, StdSmartPtr::Box),
                 "DefaultRc" => traits::slice::impl_default_smartptr(self, StdSmartPtr::Rc),
@@ -344,11 +1136,15 @@ impl Definitions {
                 "DefaultRefMut" => traits::slice::impl_default_ref(self, Mutable),
                 "Deref" => traits::slice::impl_deref(self, Constant),
                 "DerefMut" => traits::slice::impl_deref(self, Mutable),
+                "Encode" => traits::slice::impl_encode(self),
+                "FromBytes" => traits::slice::impl_from_bytes(self),
                 "FromInner" => traits::slice::impl_from_inner(self, Constant),
                 "FromInnerMut" => traits::slice::impl_from_inner(self, Mutable),
                 "IntoArc" => traits::slice::impl_into_smartptr(self, StdSmartPtr::Arc),
                 "IntoBox" => traits::slice::impl_into_smartptr(self, StdSmartPtr::Box),
                 "IntoRc" => traits::slice::impl_into_smartptr(self, StdSmartPtr::Rc),
+                "IntoVia" => traits::slice::impl_into_via(self),
+                "JsonSchema" => traits::slice::impl_json_schema(self),
                 "PartialEqBulk" => traits::slice::impl_cmp_bulk(self, CmpTrait::PartialEq),
                 "PartialEqInnerBulk" => {
                     traits::slice::impl_cmp_inner_bulk(self, CmpTrait::PartialEq)
@@ -357,7 +1153,9 @@ impl Definitions {
                 "PartialOrdInnerBulk" => {
                     traits::slice::impl_cmp_inner_bulk(self, CmpTrait::PartialOrd)
                 }
+                "ToSql" => traits::slice::impl_to_sql(self),
                 "TryFromInner" => traits::slice::impl_try_from_inner(self, Constant),
+                "TransparentWrapper" => traits::slice::impl_transparent_wrapper(self),
                 "TryFromInnerMut" => traits::slice::impl_try_from_inner(self, Mutable),
                 derive => panic!("Unknown derive target for slice type: {:?}", derive),
             }
@@ -373,6 +1171,8 @@ struct CustomType {
     attrs: CustomSliceAttrs,
     /// Inner field.
     inner_field: Field,
+    /// Declared methods with their kinds.
+    fn_stubs: Vec<(String, FnStub)>,
 }
 
 impl CustomType {
@@ -399,6 +1199,7 @@ impl CustomType {
             item,
             attrs,
             inner_field,
+            fn_stubs: Vec::new(),
         })
     }
 
@@ -410,6 +1211,15 @@ impl CustomType {
         }
     }
 
+    /// Returns the generated function declaration for the given kind.
+    pub(crate) fn fn_prefix(&self, kind: &str) -> Option<FnPrefix> {
+        self.fn_stubs
+            .iter()
+            .find(|(k, _)| k == kind)
+            .map(|(_, stub)| FnPrefix::Stub(Box::new(stub.clone())))
+            .or_else(|| self.attrs.get_fn_prefix(kind))
+    }
+
     /// Returns the outer type.
     pub(crate) fn outer_type(&self) -> &Ident {
         &self.item.ident
@@ -420,6 +1230,40 @@ impl CustomType {
         &self.inner_field.ty
     }
 
+    /// Returns `T` if the inner type is `[T]`.
+    pub(crate) fn inner_slice_elem(&self) -> Option<&Type> {
+        match self.inner_type() {
+            Type::Slice(ty) => Some(&ty.elem),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte buffer type of `bytes` crate, if the inner type is
+    /// `Bytes` or `BytesMut` and `bytes` feature is enabled.
+    pub(crate) fn inner_bytes_buf(&self) -> Option<BytesBuf> {
+        if !BYTES_ENABLED {
+            return None;
+        }
+        match self.inner_type_name()?.to_string().as_str() {
+            "Bytes" => Some(BytesBuf::Bytes),
+            "BytesMut" => Some(BytesBuf::BytesMut),
+            _ => None,
+        }
+    }
+
+    /// Returns the last path segment of the inner type, if the inner type is a
+    /// path.
+    pub(crate) fn inner_type_name(&self) -> Option<&Ident> {
+        match self.inner_type() {
+            Type::Path(ty) if ty.qself.is_none() => ty
+                .path
+                .segments
+                .last()
+                .map(|segment| &segment.value().ident),
+            _ => None,
+        }
+    }
+
     /// Returns the inner field name or the index.
     pub(crate) fn field_name(&self) -> TokenStream {
         self.inner_field
@@ -436,37 +1280,64 @@ impl CustomType {
     }
 }
 
-/// Validator specification.
-pub(crate) struct Validator {
-    /// Item.
-    item: ItemFn,
-    /// Attributes.
-    attrs: CustomSliceAttrs,
+/// Returns the function of `#[custom_slice(fuzz_repair = "...")]`.
+fn get_fuzz_repair(attrs: &CustomSliceAttrs) -> Option<syn::Expr> {
+    attrs
+        .get_fuzz_repair()
+        .unwrap_or_else(|e| panic!("Failed to parse `fuzz_repair`: {}", e))
+        .map(|(_, expr)| expr)
 }
 
-impl Validator {
-    /// Creates an item.
-    fn create_item(&self) -> ItemFn {
-        ItemFn {
-            attrs: self.attrs.raw.clone(),
-            ..self.item.clone()
-        }
+/// Returns the backends of `ToSql` and `FromSql` derive targets.
+///
+/// If no backends are specified by `sql(..)`, all backends enabled by the
+/// features are selected.
+fn get_sql_backends(attrs: &CustomSliceAttrs) -> Vec<&'static str> {
+    let explicit = SQL_BACKENDS
+        .iter()
+        .map(|&(backend, _)| backend)
+        .filter(|backend| attrs.get_sql_backend(backend).is_some())
+        .collect::<Vec<_>>();
+    if !explicit.is_empty() {
+        return explicit;
     }
+    SQL_BACKENDS
+        .iter()
+        .filter(|&&(_, enabled)| enabled)
+        .map(|&(backend, _)| backend)
+        .collect()
+}
 
-    /// Returns function name.
-    fn name(&self) -> &Ident {
-        &self.item.ident
-    }
+/// Returns the description and the function of
+/// `#[custom_slice(json_schema(description = "...", with = "..."))]`.
+fn get_json_schema_conf(attrs: &CustomSliceAttrs) -> (Option<String>, Option<syn::Expr>) {
+    let with = attrs
+        .get_json_schema_with()
+        .unwrap_or_else(|e| panic!("Failed to parse `json_schema(with = ..)`: {}", e));
+    (attrs.get_json_schema_description(), with)
 }
 
+/// Returns the error type and the error value expression.
+///
+/// `default_error` is used if `error(type = "...")` is not specified.
 fn get_error_ty_and_val(
     attrs: &CustomSliceAttrs,
+    default_error: Option<&Ident>,
     error_var: impl ToTokens,
     arg_name: impl ToTokens,
 ) -> (syn::Type, TokenStream) {
+    if let Some((_, name)) = attrs
+        .get_generated_error()
+        .unwrap_or_else(|e| panic!("Failed to parse generated error type name: {}", e))
+    {
+        let value = quote!(#name { error: #error_var, inner: #arg_name });
+        return (syn::parse_quote!(#name), value);
+    }
+
     let ty_error = attrs
         .get_error_type()
         .unwrap_or_else(|e| panic!("Failed to parse error type: {}", e))
+        .or_else(|| default_error.map(|ident| syn::parse_quote!(#ident)))
         .expect("`#[custom_slice(error(type = \"...\"))]` should be specified");
     let mapped_error = attrs
         .get_mapped_error(error_var, arg_name)
@@ -1,19 +1,29 @@
 //! `Definitions` builder.
 
-use std::{convert::TryFrom, error, fmt, mem::replace};
+use std::{convert::TryFrom, error, fmt, mem};
 
-use syn::Item;
+use proc_macro2::{Span, TokenStream};
+use syn::{ext::IdentExt, Attribute, Ident, Item, Meta, NestedMeta};
 
 use crate::{
-    attrs::{CustomSliceAttrs, SpecialItemType},
-    defs::{CustomType, Definitions, Validator},
+    attrs::{suggest, CustomSliceAttrs, DidYouMean, SpecialItemType},
+    codegen::{props::Safety, types::BytesBuf},
+    defs::{
+        input::{FnStub, Input, InputItem, StubImpl},
+        normalizer::Normalizer,
+        validator::{
+            CombinedValidator, GeneratedValidator, OwnedValidator, TraitValidator, Validator,
+            ValidatorFn,
+        },
+        CustomType, Definitions, ARBITRARY_BACKENDS, ARBITRARY_FUZZ_ENABLED, BYTEMUCK_ENABLED,
+        BYTES_ENABLED, CLAP_ENABLED, EQUIVALENT_BACKENDS, SCHEMARS_ENABLED, SERIALIZATION_DERIVES,
+        SQL_BACKENDS,
+    },
 };
 
 /// Definition load error.
 #[derive(Debug)]
 pub(crate) enum LoadError {
-    /// Extra items found.
-    ExtraItems,
     /// No slice definitions found.
     NoSliceDefinitions,
     /// No owned type definitions found.
@@ -28,6 +38,20 @@ pub(crate) enum LoadError {
     ReprAttributeNotFound,
     /// There are unexpected number of fields.
     UnexpectedFields(usize),
+    /// Invalid `#[custom_slice(..)]` attributes.
+    InvalidAttributes(Vec<syn::Error>),
+}
+
+impl LoadError {
+    /// Creates compile errors.
+    pub(crate) fn to_compile_error(&self) -> TokenStream {
+        match self {
+            LoadError::InvalidAttributes(errors) => {
+                errors.iter().map(syn::Error::to_compile_error).collect()
+            }
+            e => syn::Error::new(Span::call_site(), e).to_compile_error(),
+        }
+    }
 }
 
 impl error::Error for LoadError {}
@@ -35,7 +59,6 @@ impl error::Error for LoadError {}
 impl fmt::Display for LoadError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            LoadError::ExtraItems => write!(f, "Extra items found"),
             LoadError::NoSliceDefinitions => write!(f, "No slice definitions found"),
             LoadError::NoOwnedDefinitions => write!(f, "No owned type definitions found"),
             LoadError::MultipleSliceDefinitions => write!(f, "Multiple slice definitions found"),
@@ -52,6 +75,16 @@ impl fmt::Display for LoadError {
                 "UnexpectedFields number of fields: expect just one, but got {}",
                 num
             ),
+            LoadError::InvalidAttributes(errors) => {
+                write!(f, "Invalid attributes: ")?;
+                for (i, e) in errors.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -62,52 +95,976 @@ pub(crate) struct Builder {
     slice: Option<CustomType>,
     /// Owned type definition.
     owned: Option<CustomType>,
-    /// Validator function definition.
-    validator: Option<Validator>,
+    /// Validator function definitions for the slice type.
+    validator_fns: Vec<ValidatorFn>,
+    /// Validator function definition for the owned type.
+    owned_validator: Option<OwnedValidator>,
+    /// Normalizer function definition.
+    normalizer: Option<Normalizer>,
+    /// Method declarations for the types.
+    fn_stubs: Vec<(Ident, FnStub)>,
+    /// Other items, passed through unchanged.
+    items: Vec<Item>,
+    /// Attribute errors.
+    errors: Vec<syn::Error>,
 }
 
 impl Builder {
     /// Builds a `Definitions`.
-    pub(crate) fn build(self) -> Result<Definitions, LoadError> {
-        let slice = self.slice.ok_or(LoadError::NoSliceDefinitions)?;
-        let owned = self.owned.ok_or(LoadError::NoOwnedDefinitions)?;
+    pub(crate) fn build(mut self) -> Result<Definitions, LoadError> {
+        let mut slice = self.slice.take().ok_or(LoadError::NoSliceDefinitions)?;
+        let mut owned = self.owned.take().ok_or(LoadError::NoOwnedDefinitions)?;
+
+        for (self_ty, stub) in mem::replace(&mut self.fn_stubs, Vec::new()) {
+            let target = if self_ty == *owned.outer_type() {
+                (&mut owned, SpecialItemType::OwnedType)
+            } else if self_ty == *slice.outer_type() {
+                (&mut slice, SpecialItemType::SliceType)
+            } else {
+                self.errors.push(syn::Error::new_spanned(
+                    &self_ty,
+                    format!(
+                        "method declarations are available only for `{}` and `{}`",
+                        owned.outer_type(),
+                        slice.outer_type()
+                    ),
+                ));
+                continue;
+            };
+            if let Err(e) = add_fn_stub(target.0, target.1, stub) {
+                self.errors.push(e);
+            }
+        }
+
+        let has_validator = !self.validator_fns.is_empty()
+            || slice.attrs.validator_spec().is_some()
+            || slice.attrs.find_key("validator").is_some();
+        // Generated validators always have generated error types.
+        let has_error_type = slice
+            .attrs
+            .validator_spec()
+            .map_or(false, |spec| !TraitValidator::is_specified_by(spec));
+        let validator = self.load_validator(&slice);
+
+        // The owned type uses the owned validator if available, and the slice
+        // validator otherwise.
+        let (has_owned_validator, has_owned_error_type, ty_owned_validator_error) =
+            match &self.owned_validator {
+                Some(v) => (true, false, v.error_type()),
+                None => (
+                    has_validator,
+                    has_error_type,
+                    validator.as_ref().and_then(Validator::error_type),
+                ),
+            };
+        if let Some(v) = &self.owned_validator {
+            if v.takes_value() {
+                check_owned_validator_by_value(&owned.attrs, &mut self.errors);
+            }
+        }
+        check_generated_error(
+            &owned.attrs,
+            has_owned_validator,
+            ty_owned_validator_error.is_some(),
+            &mut self.errors,
+        );
+        check_consistency(
+            &owned.attrs,
+            has_owned_validator,
+            has_owned_error_type,
+            &mut self.errors,
+        );
+        check_consistency(
+            &slice.attrs,
+            has_validator,
+            has_error_type,
+            &mut self.errors,
+        );
+        check_safe_unchecked(
+            &owned,
+            has_owned_validator,
+            &["AsMutSliceInner", "FromInner"],
+            &mut self.errors,
+        );
+        check_safe_unchecked(
+            &slice,
+            has_validator,
+            &[
+                "AsMutSliceInner",
+                "DerefMut",
+                "FromInner",
+                "FromInnerMut",
+                "TransparentWrapper",
+            ],
+            &mut self.errors,
+        );
+        for ty in &[&owned, &slice] {
+            check_normalizer(ty, self.normalizer.is_some(), &mut self.errors);
+        }
+        check_prefix_scanners(&owned, &slice, has_validator, &mut self.errors);
+        check_incremental_validator(&owned, has_owned_validator, &mut self.errors);
+        check_arbitrary(&owned, &mut self.errors);
+        for ty in &[&owned, &slice] {
+            check_arbitrary_fuzz(ty, &mut self.errors);
+        }
+        check_bytemuck(&slice, &mut self.errors);
+        check_bytes(&owned, &mut self.errors);
+        check_clap(&owned, &mut self.errors);
+        check_equivalent(&owned, &mut self.errors);
+        for ty in &[&owned, &slice] {
+            check_json_schema(ty, &mut self.errors);
+            check_serialization(ty, &mut self.errors);
+            check_sql(ty, &mut self.errors);
+        }
+        // The archived value is checked by the slice validator.
+        let has_slice_error_type = has_error_type
+            || slice
+                .attrs
+                .get_error_type()
+                .ok()
+                .and_then(|ty| ty)
+                .is_some();
+        check_rkyv(
+            &owned,
+            self.owned_validator.is_some(),
+            has_validator && !has_slice_error_type,
+            &mut self.errors,
+        );
+        if !self.errors.is_empty() {
+            return Err(LoadError::InvalidAttributes(self.errors));
+        }
 
         Ok(Definitions {
             slice,
             owned,
-            validator: self.validator,
+            validator,
+            owned_validator: self.owned_validator,
+            normalizer: self.normalizer,
+            items: self.items,
         })
     }
+
+    /// Adds method declarations.
+    fn add_stub_impl(&mut self, item_impl: StubImpl) {
+        if let Some(attr) = item_impl.attrs.first() {
+            self.errors.push(syn::Error::new_spanned(
+                attr,
+                "attributes for impl blocks are not supported",
+            ));
+        }
+        let self_ty = item_impl.self_ty;
+        self.fn_stubs.extend(
+            item_impl
+                .methods
+                .into_iter()
+                .map(|stub| (self_ty.clone(), stub)),
+        );
+    }
+
+    /// Loads the validator of the slice type.
+    fn load_validator(&mut self, slice: &CustomType) -> Option<Validator> {
+        let fns = mem::replace(&mut self.validator_fns, Vec::new());
+        match slice.attrs.get_validator_path() {
+            Ok(Some((key, path))) => {
+                if let Some(combined) = slice.attrs.validators_spec() {
+                    self.errors.push(syn::Error::new_spanned(
+                        &combined.ident,
+                        "`validators(..)` cannot be used with `validator = \"...\"`",
+                    ));
+                } else if !fns.is_empty() {
+                    self.errors.push(syn::Error::new_spanned(
+                        key,
+                        "`validator = \"...\"` cannot be used with a validator function",
+                    ));
+                }
+                return Some(Validator::Path(path));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.errors.push(e);
+                return None;
+            }
+        }
+        if let Some(spec) = slice.attrs.validator_spec() {
+            if let Some(combined) = slice.attrs.validators_spec() {
+                self.errors.push(syn::Error::new_spanned(
+                    &combined.ident,
+                    "`validators(..)` cannot be used with `validator(..)`",
+                ));
+            } else if !fns.is_empty() {
+                self.errors.push(syn::Error::new_spanned(
+                    &spec.ident,
+                    "`validator(..)` cannot be used with a validator function",
+                ));
+            } else if TraitValidator::is_specified_by(spec) {
+                match TraitValidator::new(spec, slice) {
+                    Ok(v) => return Some(Validator::Trait(v)),
+                    Err(e) => self.errors.push(e),
+                }
+            } else if self.errors.is_empty() {
+                // Malformed specs are already reported by the schema check.
+                match GeneratedValidator::new(spec, slice) {
+                    Ok(v) => return Some(Validator::Generated(v)),
+                    Err(e) => self.errors.push(e),
+                }
+            }
+            return None;
+        }
+        if let Some(spec) = slice.attrs.validators_spec() {
+            if fns.is_empty() {
+                self.errors.push(syn::Error::new_spanned(
+                    &spec.ident,
+                    "`validators(..)` requires validator functions \
+                     (`#[custom_slice(validator)]`)",
+                ));
+            } else if self.errors.is_empty() {
+                match CombinedValidator::new(fns, spec, slice) {
+                    Ok(v) => return Some(Validator::Combined(v)),
+                    Err(e) => self.errors.push(e),
+                }
+            }
+            return None;
+        }
+
+        for func in &fns {
+            if let Ok(Some((key, _))) = func.attrs.get_validator_map() {
+                self.errors.push(syn::Error::new_spanned(
+                    key,
+                    "`validator(map = \"...\")` requires \
+                     `#[custom_slice(validators(error = \"...\"))]` for the slice type",
+                ));
+            }
+        }
+        for func in fns.iter().skip(1) {
+            self.errors.push(syn::Error::new_spanned(
+                &func.item.ident,
+                "multiple validators require \
+                 `#[custom_slice(validators(error = \"...\"))]` for the slice type",
+            ));
+        }
+        fns.into_iter().next().map(Validator::Fn)
+    }
+
+    /// Adds the owned validator.
+    fn add_owned_validator(&mut self, validator: ValidatorFn) {
+        let ident = validator.item.ident.clone();
+        if let Ok(Some((key, _))) = validator.attrs.get_validator_map() {
+            self.errors.push(syn::Error::new_spanned(
+                key,
+                "`map` cannot be used with owned validators",
+            ));
+        }
+        match OwnedValidator::new(validator) {
+            Ok(v) => {
+                if self.owned_validator.replace(v).is_some() {
+                    self.errors.push(syn::Error::new_spanned(
+                        ident,
+                        "owned validator is specified more than once",
+                    ));
+                }
+            }
+            Err(e) => self.errors.push(e),
+        }
+    }
+
+    /// Checks the attributes of the item with the given kind.
+    fn check_attrs(&mut self, ty: SpecialItemType, attrs: &CustomSliceAttrs) {
+        self.errors.extend(ty.schema().check(attrs));
+    }
 }
 
-impl TryFrom<syn::File> for Builder {
+/// Adds the method declaration to the type.
+fn add_fn_stub(
+    target: &mut CustomType,
+    kind: SpecialItemType,
+    mut stub: FnStub,
+) -> Result<(), syn::Error> {
+    let attrs = CustomSliceAttrs::from(mem::replace(&mut stub.attrs, Vec::new()));
+    let (key, shape) = kind.schema().check_fn_stub(&attrs, stub.ident())?;
+    stub.check_shape(shape)?;
+    let key_name = key.unraw().to_string();
+    if target.attrs.find_key(&key_name).is_some()
+        || target.fn_stubs.iter().any(|(k, _)| *k == key_name)
+    {
+        return Err(syn::Error::new_spanned(
+            key,
+            format!("`{}` is specified more than once", key_name),
+        ));
+    }
+    stub.attrs = attrs.raw;
+    target.fn_stubs.push((key_name, stub));
+    Ok(())
+}
+
+/// Checks `error(generate = "...")`.
+///
+/// `has_validator_error_type` tells whether the validator returns
+/// `Result<_, Error>`.
+fn check_generated_error(
+    attrs: &CustomSliceAttrs,
+    has_validator: bool,
+    has_validator_error_type: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    let key = match attrs.get_generated_error() {
+        Ok(Some((key, _))) => key,
+        Ok(None) => return,
+        Err(e) => {
+            errors.push(e);
+            return;
+        }
+    };
+    if attrs.has_error_type_or_map() {
+        errors.push(syn::Error::new_spanned(
+            key,
+            "`generate` cannot be used with `type` or `map`",
+        ));
+    }
+    if !has_validator {
+        errors.push(syn::Error::new_spanned(
+            key,
+            "`error(generate = \"...\")` requires a validator (`#[custom_slice(validator)]`)",
+        ));
+    } else if !has_validator_error_type {
+        errors.push(syn::Error::new_spanned(
+            key,
+            "`error(generate = \"...\")` requires the validator to return \
+             `Result<(), Error>`",
+        ));
+    }
+}
+
+/// Checks keys which require the normalizer.
+fn check_normalizer(ty: &CustomType, has_normalizer: bool, errors: &mut Vec<syn::Error>) {
+    if has_normalizer {
+        return;
+    }
+    if let Some(ident) = find_fn_key(ty, "from_inner_normalized") {
+        errors.push(syn::Error::new_spanned(
+            ident,
+            "`from_inner_normalized` requires a normalizer (`#[custom_slice(normalizer)]`)",
+        ));
+    }
+}
+
+/// Checks keys for prefix scanning constructors.
+fn check_prefix_scanners(
+    owned: &CustomType,
+    slice: &CustomType,
+    has_validator: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    let valid_up_to = match slice.attrs.get_valid_up_to() {
+        Ok(v) => v,
+        Err(e) => {
+            errors.push(e);
+            return;
+        }
+    };
+    if let Some((ident, _)) = valid_up_to {
+        if !has_validator {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                "`valid_up_to` requires a validator (`#[custom_slice(validator)]`)",
+            ));
+        }
+        // The generated `into_valid_prefix` calls the inherent `truncate`
+        // method of the owned inner type.
+        if let Some(ident) = find_fn_key(owned, "into_valid_prefix") {
+            let has_truncate = owned.inner_bytes_buf().is_some()
+                || owned
+                    .inner_type_name()
+                    .map_or(false, |name| name == "String" || name == "Vec");
+            if !has_truncate {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`into_valid_prefix` requires `String`, `Vec<T>`, `bytes::Bytes`, or \
+                     `bytes::BytesMut` as the owned inner type",
+                ));
+            }
+        }
+        return;
+    }
+    let fns = [
+        (slice, "from_prefix"),
+        (slice, "split_valid_prefix"),
+        (owned, "into_valid_prefix"),
+    ];
+    for (ty, key) in &fns {
+        if let Some(ident) = find_fn_key(ty, key) {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`{}` requires `#[custom_slice(valid_up_to = \"...\")]` for the slice type",
+                    key
+                ),
+            ));
+        }
+    }
+}
+
+/// Checks keys and derive targets for the incremental validator.
+fn check_incremental_validator(
+    owned: &CustomType,
+    has_validator: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    let incremental_validator = match owned.attrs.get_incremental_validator() {
+        Ok(v) => v,
+        Err(e) => {
+            errors.push(e);
+            return;
+        }
+    };
+    if let Some((ident, _)) = incremental_validator {
+        if !has_validator {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                "`incremental_validator` requires a validator (`#[custom_slice(validator)]`)",
+            ));
+        }
+        return;
+    }
+    let push_checked = find_fn_key(owned, "push_checked").map(|ident| ("push_checked", ident));
+    let derives = owned.attrs.derives().filter_map(|derive| {
+        ["FmtWrite", "IoWrite"]
+            .iter()
+            .find(|name| derive == *name)
+            .map(|name| (*name, derive))
+    });
+    for (name, ident) in push_checked.into_iter().chain(derives) {
+        errors.push(syn::Error::new_spanned(
+            ident,
+            format!(
+                "`{}` requires `#[custom_slice(incremental_validator = \"...\")]`",
+                name
+            ),
+        ));
+    }
+}
+
+/// Checks `arbitrary(..)` and `Arbitrary` derive target.
+fn check_arbitrary(owned: &CustomType, errors: &mut Vec<syn::Error>) {
+    let derive = owned.attrs.derives().find(|derive| *derive == "Arbitrary");
+    for &(backend, enabled) in ARBITRARY_BACKENDS {
+        match owned.attrs.get_arbitrary_backend(backend) {
+            Ok(Some((ident, _))) if !enabled => errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`arbitrary({})` requires `{}` feature of `custom-slice-macros`",
+                    backend, backend
+                ),
+            )),
+            Ok(_) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+    if let Some((ident, 0)) = owned.attrs.get_arbitrary_max_retries() {
+        errors.push(syn::Error::new_spanned(
+            ident,
+            "`max_retries` should be greater than 0",
+        ));
+    }
+    match derive {
+        Some(derive) => {
+            if !ARBITRARY_BACKENDS.iter().any(|&(_, enabled)| enabled) {
+                errors.push(syn::Error::new_spanned(
+                    derive,
+                    "`Arbitrary` requires `proptest` or `quickcheck` feature of `custom-slice-macros`",
+                ));
+            }
+        }
+        None => {
+            if let Some(ident) = owned.attrs.find_key("arbitrary") {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`arbitrary(..)` requires `Arbitrary` derive target",
+                ));
+            }
+        }
+    }
+}
+
+/// Checks `fuzz_repair` and `ArbitraryFuzz` derive target.
+fn check_arbitrary_fuzz(ty: &CustomType, errors: &mut Vec<syn::Error>) {
+    let derive = ty.attrs.derives().find(|derive| *derive == "ArbitraryFuzz");
+    match derive {
+        Some(derive) if !ARBITRARY_FUZZ_ENABLED => errors.push(syn::Error::new_spanned(
+            derive,
+            "`ArbitraryFuzz` requires `arbitrary` feature of `custom-slice-macros`",
+        )),
+        Some(_) => {}
+        None => {
+            if let Some(ident) = ty.attrs.find_key("fuzz_repair") {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`fuzz_repair` requires `ArbitraryFuzz` derive target",
+                ));
+            }
+        }
+    }
+    if let Err(e) = ty.attrs.get_fuzz_repair() {
+        errors.push(e);
+    }
+}
+
+/// Checks `AsBytes`, `FromBytes`, and `TransparentWrapper` derive targets.
+fn check_bytemuck(slice: &CustomType, errors: &mut Vec<syn::Error>) {
+    for derive in slice.attrs.derives() {
+        let name = derive.to_string();
+        let message = match name.as_str() {
+            "AsBytes" | "FromBytes" | "TransparentWrapper" if !BYTEMUCK_ENABLED => format!(
+                "`{}` requires `bytemuck` feature of `custom-slice-macros`",
+                name
+            ),
+            "AsBytes" | "FromBytes" if slice.inner_slice_elem().is_none() => {
+                format!("`{}` requires `[T]` as the slice inner type", name)
+            }
+            "TransparentWrapper" if !slice.attrs.is_repr_transparent() => {
+                "`TransparentWrapper` requires `#[repr(transparent)]`".to_owned()
+            }
+            _ => continue,
+        };
+        errors.push(syn::Error::new_spanned(derive, message));
+    }
+}
+
+/// Checks `slice_ref`, `slice_range`, and `subslice_closed`.
+fn check_bytes(owned: &CustomType, errors: &mut Vec<syn::Error>) {
+    let keys = ["slice_ref", "slice_range"]
+        .iter()
+        .filter_map(|key| find_fn_key(owned, key).map(|ident| (*key, ident)))
+        .collect::<Vec<_>>();
+    for &(key, ident) in &keys {
+        let message = if !BYTES_ENABLED {
+            format!(
+                "`{}` requires `bytes` feature of `custom-slice-macros`",
+                key
+            )
+        } else if owned.inner_bytes_buf() != Some(BytesBuf::Bytes) {
+            format!("`{}` requires `bytes::Bytes` as the owned inner type", key)
+        } else {
+            continue;
+        };
+        errors.push(syn::Error::new_spanned(ident, message));
+    }
+    if keys.is_empty() {
+        if let Some(ident) = owned.attrs.find_key("subslice_closed") {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                "`subslice_closed` requires `slice_ref` or `slice_range`",
+            ));
+        }
+    }
+}
+
+/// Checks `ClapValueParser` derive target.
+fn check_clap(owned: &CustomType, errors: &mut Vec<syn::Error>) {
+    let derive = match owned
+        .attrs
+        .derives()
+        .find(|derive| *derive == "ClapValueParser")
+    {
+        Some(v) => v,
+        None => return,
+    };
+    if !CLAP_ENABLED {
+        errors.push(syn::Error::new_spanned(
+            derive,
+            "`ClapValueParser` requires `clap` feature of `custom-slice-macros`",
+        ));
+    }
+    let is_supported = owned
+        .inner_type_name()
+        .map_or(false, |name| name == "String" || name == "OsString");
+    if !is_supported {
+        errors.push(syn::Error::new_spanned(
+            derive,
+            "`ClapValueParser` requires `String` or `OsString` as the owned inner type",
+        ));
+    }
+}
+
+/// Checks `equivalent(..)` and `Equivalent` derive target.
+fn check_equivalent(owned: &CustomType, errors: &mut Vec<syn::Error>) {
+    for &(backend, enabled) in EQUIVALENT_BACKENDS {
+        if let Some(ident) = owned.attrs.get_equivalent_backend(backend) {
+            if !enabled {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "`equivalent({})` requires `{}` feature of `custom-slice-macros`",
+                        backend, backend
+                    ),
+                ));
+            }
+        }
+    }
+    match owned.attrs.derives().find(|derive| *derive == "Equivalent") {
+        Some(derive) => {
+            if !EQUIVALENT_BACKENDS.iter().any(|&(_, enabled)| enabled) {
+                errors.push(syn::Error::new_spanned(
+                    derive,
+                    "`Equivalent` requires `hashbrown` or `indexmap` feature of `custom-slice-macros`",
+                ));
+            }
+            // The hash of the owned type is known to be the hash of the inner
+            // value only if it is derived.
+            if !owned.attrs.has_std_derive("Hash") {
+                errors.push(syn::Error::new_spanned(
+                    derive,
+                    "`Equivalent` requires `#[derive(Hash)]` on the owned type",
+                ));
+            }
+        }
+        None => {
+            if let Some(ident) = owned.attrs.find_key("equivalent") {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`equivalent(..)` requires `Equivalent` derive target",
+                ));
+            }
+        }
+    }
+}
+
+/// Checks `json_schema(..)` and `JsonSchema` derive target.
+fn check_json_schema(ty: &CustomType, errors: &mut Vec<syn::Error>) {
+    match ty.attrs.derives().find(|derive| *derive == "JsonSchema") {
+        Some(derive) if !SCHEMARS_ENABLED => errors.push(syn::Error::new_spanned(
+            derive,
+            "`JsonSchema` requires `schemars` feature of `custom-slice-macros`",
+        )),
+        Some(_) => {}
+        None => {
+            if let Some(ident) = ty.attrs.find_key("json_schema") {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`json_schema(..)` requires `JsonSchema` derive target",
+                ));
+            }
+        }
+    }
+    if let Err(e) = ty.attrs.get_json_schema_with() {
+        errors.push(e);
+    }
+}
+
+/// Checks derive targets for binary serialization.
+fn check_serialization(ty: &CustomType, errors: &mut Vec<syn::Error>) {
+    for derive in ty.attrs.derives() {
+        let found = SERIALIZATION_DERIVES
+            .iter()
+            .find(|&&(name, _, _)| *derive == name);
+        if let Some(&(name, feature, false)) = found {
+            errors.push(syn::Error::new_spanned(
+                derive,
+                format!(
+                    "`{}` requires `{}` feature of `custom-slice-macros`",
+                    name, feature
+                ),
+            ));
+        }
+    }
+}
+
+/// Checks `sql(..)`, and `ToSql` and `FromSql` derive targets.
+fn check_sql(ty: &CustomType, errors: &mut Vec<syn::Error>) {
+    for &(backend, enabled) in SQL_BACKENDS {
+        if let Some(ident) = ty.attrs.get_sql_backend(backend) {
+            if !enabled {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "`sql({})` requires `{}` feature of `custom-slice-macros`",
+                        backend, backend
+                    ),
+                ));
+            }
+        }
+    }
+    let mut derives = ty
+        .attrs
+        .derives()
+        .filter(|derive| *derive == "ToSql" || *derive == "FromSql")
+        .peekable();
+    if derives.peek().is_none() {
+        if let Some(ident) = ty.attrs.find_key("sql") {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                "`sql(..)` requires `ToSql` or `FromSql` derive target",
+            ));
+        }
+        return;
+    }
+    if SQL_BACKENDS.iter().any(|&(_, enabled)| enabled) {
+        return;
+    }
+    for derive in derives {
+        errors.push(syn::Error::new_spanned(
+            derive,
+            format!(
+                "`{}` requires `postgres` or `rusqlite` feature of `custom-slice-macros`",
+                derive
+            ),
+        ));
+    }
+}
+
+/// Checks `Archive` derive target.
+///
+/// `lacks_slice_error_type` tells whether the slice validator is specified but
+/// the error type of the slice type is unknown.
+fn check_rkyv(
+    owned: &CustomType,
+    has_owned_validator: bool,
+    lacks_slice_error_type: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    let derive = match owned.attrs.derives().find(|derive| *derive == "Archive") {
+        Some(v) => v,
+        None => return,
+    };
+    // `CheckBytes` impl can only see the archived inner value, which is not
+    // `OwnedInner`.
+    if has_owned_validator {
+        errors.push(syn::Error::new_spanned(
+            derive,
+            "`Archive` cannot be used with `#[custom_slice(validator(owned))]`",
+        ));
+    }
+    if lacks_slice_error_type {
+        errors.push(syn::Error::new_spanned(
+            derive,
+            "`Archive` requires `#[custom_slice(error(type = \"...\"))]` on the slice type",
+        ));
+    }
+}
+
+/// Returns the key or the declared function name of the generated function.
+fn find_fn_key<'a>(ty: &'a CustomType, key: &'a str) -> Option<&'a Ident> {
+    ty.attrs.find_key(key).or_else(|| {
+        ty.fn_stubs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, stub)| stub.ident())
+    })
+}
+
+/// Checks that unchecked conversions are not safe when a validator is
+/// specified.
+///
+/// `unsafe_derives` are derive targets which expose the inner value without
+/// validation.
+fn check_safe_unchecked(
+    ty: &CustomType,
+    has_validator: bool,
+    unsafe_derives: &[&str],
+    errors: &mut Vec<syn::Error>,
+) {
+    if !has_validator || ty.attrs.find_key("allow_safe_unchecked").is_some() {
+        return;
+    }
+    for derive in ty.attrs.derives() {
+        let name = derive.to_string();
+        if unsafe_derives.contains(&name.as_str()) {
+            errors.push(syn::Error::new_spanned(
+                derive,
+                format!(
+                    "`{}` cannot be derived because a validator is specified \
+                     (use `#[custom_slice(allow_safe_unchecked)]` to allow it)",
+                    name
+                ),
+            ));
+        }
+    }
+    for key in &["new_unchecked", "new_unchecked_mut", "get_mut"] {
+        let ident = match ty.fn_stubs.iter().find(|(k, _)| k == key) {
+            Some((_, stub)) => Some(stub.ident()).filter(|_| stub.safety().is_safe()),
+            None => ty.attrs.find_key(key).filter(|_| {
+                ty.attrs.get_fn_prefix(key).map_or(false, |prefix| {
+                    prefix.safety().map(Safety::is_safe).unwrap_or(false)
+                })
+            }),
+        };
+        let ident = match ident {
+            Some(v) => v,
+            None => continue,
+        };
+        errors.push(syn::Error::new_spanned(
+            ident,
+            format!(
+                "`{}` should be `unsafe` because a validator is specified \
+                 (use `#[custom_slice(allow_safe_unchecked)]` to allow it)",
+                key
+            ),
+        ));
+    }
+}
+
+/// Checks the error of the owned type for validators taking `OwnedInner` by
+/// value.
+fn check_owned_validator_by_value(attrs: &CustomSliceAttrs, errors: &mut Vec<syn::Error>) {
+    for key in &["map", "generate"] {
+        if let Some(ident) = attrs.find_error_key(key) {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`{}` cannot be used with the owned validator taking `OwnedInner` by value",
+                    key
+                ),
+            ));
+        }
+    }
+}
+
+/// Checks combinations of keys which depend on the presence of the validator.
+///
+/// `has_error_type` tells whether the default error type is generated for the
+/// validator.
+fn check_consistency(
+    attrs: &CustomSliceAttrs,
+    has_validator: bool,
+    has_error_type: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    let mut requires_error_type = None;
+    for derive in attrs.derives() {
+        let name = derive.to_string();
+        match name.as_str() {
+            "TryFromInner" | "TryFromInnerMut" => {
+                if !has_validator {
+                    errors.push(syn::Error::new_spanned(
+                        derive,
+                        format!(
+                            "`{}` requires a validator (`#[custom_slice(validator)]`)",
+                            name
+                        ),
+                    ));
+                }
+                requires_error_type = requires_error_type.or(Some(derive));
+            }
+            // Fallible only when the validator is specified.
+            "BorrowDecode" | "BorshDeserialize" | "ClapValueParser" | "Decode" | "FromBytes"
+            | "FromSql" | "FromStr" => requires_error_type = requires_error_type.or(Some(derive)),
+            _ => {}
+        }
+    }
+    for key in &["new_checked", "new_checked_mut"] {
+        if let Some(ident) = attrs.find_key(key) {
+            if !has_validator {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "`{}` requires a validator (`#[custom_slice(validator)]`)",
+                        key
+                    ),
+                ));
+            }
+            requires_error_type = requires_error_type.or(Some(ident));
+        }
+    }
+    // Fallible unless `subslice_closed` is specified.
+    if attrs.find_key("subslice_closed").is_none() {
+        if let Some(ident) = attrs.find_key("slice_range") {
+            requires_error_type = requires_error_type.or(Some(ident));
+        }
+    }
+    for key in &["allow_safe_unchecked", "debug_validate", "fuzz_repair"] {
+        if let Some(ident) = attrs.find_key(key) {
+            if !has_validator {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "`{}` requires a validator (`#[custom_slice(validator)]`)",
+                        key
+                    ),
+                ));
+            }
+        }
+    }
+    if let Some(ident) = requires_error_type {
+        if has_validator
+            && !has_error_type
+            && attrs.get_error_type().ok().and_then(|ty| ty).is_none()
+            && attrs.get_generated_error().ok().and_then(|v| v).is_none()
+        {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`{}` requires `#[custom_slice(error(type = \"...\"))]`",
+                    ident
+                ),
+            ));
+        }
+    }
+}
+
+impl TryFrom<Input> for Builder {
     type Error = LoadError;
 
-    fn try_from(file: syn::File) -> Result<Self, Self::Error> {
+    fn try_from(input: Input) -> Result<Self, Self::Error> {
         let mut builder = Self::default();
 
-        for item in file.items {
+        for item in input.items {
+            let item = match item {
+                InputItem::Item(item) => item,
+                InputItem::Impl(item_impl) => {
+                    builder.add_stub_impl(item_impl);
+                    continue;
+                }
+            };
             match item {
                 Item::Fn(mut item_fn) => {
-                    let attrs = CustomSliceAttrs::from(replace(&mut item_fn.attrs, Vec::new()));
+                    let attrs =
+                        CustomSliceAttrs::from(mem::replace(&mut item_fn.attrs, Vec::new()));
+                    if attrs.is_empty() {
+                        item_fn.attrs = attrs.raw;
+                        builder.items.push(Item::Fn(item_fn));
+                        continue;
+                    }
                     match attrs.special_item_type() {
                         Some(SpecialItemType::Validator) => {
-                            let validator = Validator {
+                            builder.check_attrs(SpecialItemType::Validator, &attrs);
+                            let validator = ValidatorFn {
                                 item: item_fn,
                                 attrs,
                             };
-                            if builder.validator.replace(validator).is_some() {
-                                return Err(LoadError::MultipleSliceDefinitions);
+                            if validator.attrs.is_owned_validator() {
+                                builder.add_owned_validator(validator);
+                            } else {
+                                builder.validator_fns.push(validator);
+                            }
+                        }
+                        Some(SpecialItemType::Normalizer) => {
+                            builder.check_attrs(SpecialItemType::Normalizer, &attrs);
+                            let normalizer = Normalizer {
+                                item: item_fn,
+                                attrs,
+                            };
+                            let ident = normalizer.item.ident.clone();
+                            if builder.normalizer.replace(normalizer).is_some() {
+                                builder.errors.push(syn::Error::new_spanned(
+                                    ident,
+                                    "normalizer is specified more than once",
+                                ));
                             }
                         }
                         Some(ty) => return Err(LoadError::InvalidSpecialItem(ty)),
-                        None => return Err(LoadError::ExtraItems),
+                        None => return Err(missing_marker_error(&attrs)),
                     }
                 }
                 Item::Struct(mut item_struct) => {
-                    let attrs = CustomSliceAttrs::from(replace(&mut item_struct.attrs, Vec::new()));
+                    let attrs =
+                        CustomSliceAttrs::from(mem::replace(&mut item_struct.attrs, Vec::new()));
+                    if attrs.is_empty() {
+                        item_struct.attrs = attrs.raw;
+                        builder.items.push(Item::Struct(item_struct));
+                        continue;
+                    }
                     match attrs.special_item_type() {
                         Some(SpecialItemType::SliceType) => {
+                            builder.check_attrs(SpecialItemType::SliceType, &attrs);
                             let def = CustomType::new(item_struct, attrs)?;
                             if !def.attrs.is_repr_transparent_or_c() {
                                 return Err(LoadError::ReprAttributeNotFound);
@@ -117,19 +1074,95 @@ impl TryFrom<syn::File> for Builder {
                             }
                         }
                         Some(SpecialItemType::OwnedType) => {
+                            builder.check_attrs(SpecialItemType::OwnedType, &attrs);
                             let def = CustomType::new(item_struct, attrs)?;
                             if builder.owned.replace(def).is_some() {
                                 return Err(LoadError::MultipleOwnedDefinitions);
                             }
                         }
                         Some(ty) => return Err(LoadError::InvalidSpecialItem(ty)),
-                        None => return Err(LoadError::ExtraItems),
+                        None => return Err(missing_marker_error(&attrs)),
+                    }
+                }
+                item => {
+                    if let Some(attr) = item_attrs(&item)
+                        .iter()
+                        .find(|attr| attr.path.is_ident("custom_slice"))
+                    {
+                        builder.errors.push(syn::Error::new_spanned(
+                            attr,
+                            "`#[custom_slice(..)]` is available only for structs and functions",
+                        ));
                     }
+                    builder.items.push(item);
                 }
-                _ => return Err(LoadError::ExtraItems),
             }
         }
 
         Ok(builder)
     }
 }
+
+/// Returns an error for an item without item kind marker.
+fn missing_marker_error(attrs: &CustomSliceAttrs) -> LoadError {
+    let word = attrs
+        .custom_meta
+        .iter()
+        .filter_map(|nested_meta| match nested_meta {
+            NestedMeta::Meta(Meta::Word(ident)) => Some(ident),
+            _ => None,
+        })
+        .next();
+    let word = match word {
+        Some(v) => v,
+        None => {
+            let errors = match attrs.custom_meta.first() {
+                Some(meta) => vec![syn::Error::new_spanned(
+                    meta,
+                    "expected item kind `owned`, `slice`, `validator`, or `normalizer`",
+                )],
+                None => attrs.malformed().to_vec(),
+            };
+            return LoadError::InvalidAttributes(errors);
+        }
+    };
+    let markers = [
+        SpecialItemType::OwnedType,
+        SpecialItemType::SliceType,
+        SpecialItemType::Validator,
+        SpecialItemType::Normalizer,
+    ];
+    let suggestion = suggest(&word.to_string(), markers.iter().map(|ty| ty.marker()));
+    LoadError::InvalidAttributes(vec![syn::Error::new_spanned(
+        word,
+        format!(
+            "unknown item kind `{}`, expected `owned`, `slice`, `validator`, or `normalizer`{}",
+            word,
+            DidYouMean(suggestion)
+        ),
+    )])
+}
+
+/// Returns the attributes of the item.
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::ExternCrate(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Const(item) => &item.attrs,
+        Item::Fn(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::ForeignMod(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Existential(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::TraitAlias(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Macro2(item) => &item.attrs,
+        Item::Verbatim(_) => &[],
+    }
+}
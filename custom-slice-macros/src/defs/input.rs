@@ -0,0 +1,316 @@
+//! Macro input.
+
+use quote::{quote, ToTokens};
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream},
+    Abi, Attribute, Block, FnArg, FnDecl, Generics, Ident, ImplItem, Item, ItemFn, Pat, ReturnType,
+    Token, Type, Visibility,
+};
+
+use crate::{attrs::FnShape, codegen::props::Safety};
+
+/// Macro input.
+pub(crate) struct Input {
+    /// Items.
+    pub(crate) items: Vec<InputItem>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            let ahead = input.fork();
+            ahead.call(Attribute::parse_outer)?;
+            if ahead.peek(Token![impl]) && !is_plain_impl(&ahead) {
+                items.push(InputItem::Impl(input.parse()?));
+            } else {
+                items.push(InputItem::Item(input.parse()?));
+            }
+        }
+
+        Ok(Self { items })
+    }
+}
+
+/// Checks whether the next item is an impl block without method declarations.
+///
+/// Such impl blocks are passed through as usual items.
+fn is_plain_impl(input: ParseStream<'_>) -> bool {
+    match input.fork().parse::<Item>() {
+        Ok(Item::Impl(item_impl)) => !item_impl.items.iter().any(|item| match item {
+            ImplItem::Method(method) => method
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("custom_slice")),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+/// Item in the macro input.
+pub(crate) enum InputItem {
+    /// Usual item.
+    Item(Item),
+    /// Impl block with method declarations.
+    Impl(StubImpl),
+}
+
+/// Impl block with method declarations.
+///
+/// `impl Type { /* methods */ }`.
+pub(crate) struct StubImpl {
+    /// Attributes.
+    pub(crate) attrs: Vec<Attribute>,
+    /// Self type.
+    pub(crate) self_ty: Ident,
+    /// Methods.
+    pub(crate) methods: Vec<FnStub>,
+}
+
+impl Parse for StubImpl {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        input.parse::<Token![impl]>()?;
+        let self_ty: Ident = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let mut methods = Vec::new();
+        while !content.is_empty() {
+            methods.push(content.parse()?);
+        }
+
+        Ok(Self {
+            attrs,
+            self_ty,
+            methods,
+        })
+    }
+}
+
+/// Method declaration.
+///
+/// Method body is generated by the macro, and should be omitted (i.e.
+/// `fn name(args...) -> Type;`).
+#[derive(Clone)]
+pub(crate) struct FnStub {
+    /// Attributes.
+    pub(crate) attrs: Vec<Attribute>,
+    /// Visibility.
+    vis: Visibility,
+    /// `const`.
+    constness: Option<Token![const]>,
+    /// `unsafe`.
+    unsafety: Option<Token![unsafe]>,
+    /// ABI.
+    abi: Option<Abi>,
+    /// Function name.
+    ident: Ident,
+    /// Function signature.
+    decl: FnDecl,
+    /// Function body, which should be absent.
+    pub(crate) body: Option<Block>,
+}
+
+impl Parse for FnStub {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let constness: Option<Token![const]> = input.parse()?;
+        let unsafety: Option<Token![unsafe]> = input.parse()?;
+        let abi: Option<Abi> = input.parse()?;
+        let fn_token: Token![fn] = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let generics: Generics = input.parse()?;
+
+        let content;
+        let paren_token = parenthesized!(content in input);
+        let inputs = content.parse_terminated(FnArg::parse)?;
+
+        let output: ReturnType = input.parse()?;
+        let where_clause = input.parse()?;
+
+        let body = if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+            None
+        } else {
+            Some(input.parse()?)
+        };
+
+        Ok(Self {
+            attrs,
+            vis,
+            constness,
+            unsafety,
+            abi,
+            ident,
+            decl: FnDecl {
+                fn_token,
+                generics: Generics {
+                    where_clause,
+                    ..generics
+                },
+                paren_token,
+                inputs,
+                variadic: None,
+                output,
+            },
+            body,
+        })
+    }
+}
+
+impl FnStub {
+    /// Returns the function name.
+    pub(crate) fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    /// Returns the safety of the function.
+    pub(crate) fn safety(&self) -> Safety {
+        Safety::from(&self.unsafety)
+    }
+
+    /// Checks whether the signature has the expected shape.
+    pub(crate) fn check_shape(&self, shape: FnShape) -> Result<(), syn::Error> {
+        if let Some(body) = &self.body {
+            return Err(syn::Error::new_spanned(
+                body,
+                "function body is generated by the macro and should be omitted",
+            ));
+        }
+        if let ReturnType::Default = self.decl.output {
+            return Err(syn::Error::new_spanned(
+                &self.ident,
+                "return type should be specified",
+            ));
+        }
+        let args = &self.decl.inputs;
+        let expected = match shape {
+            FnShape::Arg => "a single argument `name: Type`",
+            FnShape::RefSelf => "`&self`",
+            FnShape::RefMutSelf => "`&mut self`",
+            FnShape::SelfValue => "`self`",
+            FnShape::RefSelfArg => "`&self` and a single argument `name: Type`",
+            FnShape::RefMutSelfArg => "`&mut self` and a single argument `name: Type`",
+        };
+        let valid = match shape {
+            FnShape::RefSelfArg => {
+                args.len() == 2
+                    && is_shape_of(FnShape::RefSelf, &args[0])
+                    && is_shape_of(FnShape::Arg, &args[1])
+            }
+            FnShape::RefMutSelfArg => {
+                args.len() == 2
+                    && is_shape_of(FnShape::RefMutSelf, &args[0])
+                    && is_shape_of(FnShape::Arg, &args[1])
+            }
+            shape => args.len() == 1 && is_shape_of(shape, &args[0]),
+        };
+        if !valid {
+            return Err(syn::Error::new_spanned(
+                &self.decl.inputs,
+                format!("`{}` should take {}", self.ident, expected),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Creates a function item with the given argument and body.
+    ///
+    /// The declared argument is bound to `arg_name` with type `ty_arg`.
+    pub(crate) fn build_item_with_named_arg(
+        &self,
+        arg_name: impl ToTokens,
+        ty_arg: impl ToTokens,
+        body_expr: impl ToTokens,
+    ) -> Result<ItemFn, syn::Error> {
+        self.check_shape(FnShape::Arg)?;
+        let declared = match &self.decl.inputs[0] {
+            FnArg::Captured(arg) => match arg.pat {
+                Pat::Ident(ref pat) => &pat.ident,
+                _ => unreachable!("Should never happen: shape is already checked"),
+            },
+            _ => unreachable!("Should never happen: shape is already checked"),
+        };
+        self.build_item(quote! {
+            let #arg_name: #ty_arg = #declared;
+            #body_expr
+        })
+    }
+
+    /// Creates a function item with the given receiver, argument, and body.
+    ///
+    /// The declared argument is bound to `arg_name` with type `ty_arg`.
+    /// If `ty_arg` is `impl Trait`, the declared type is used as is.
+    pub(crate) fn build_item_with_receiver_and_arg(
+        &self,
+        shape: FnShape,
+        arg_name: impl ToTokens,
+        ty_arg: impl ToTokens,
+        body_expr: impl ToTokens,
+    ) -> Result<ItemFn, syn::Error> {
+        self.check_shape(shape)?;
+        let declared = match &self.decl.inputs[1] {
+            FnArg::Captured(arg) => match arg.pat {
+                Pat::Ident(ref pat) => &pat.ident,
+                _ => unreachable!("Should never happen: shape is already checked"),
+            },
+            _ => unreachable!("Should never happen: shape is already checked"),
+        };
+        // `impl Trait` is not allowed as the type of `let` bindings.
+        let binding = match syn::parse2::<Type>(quote!(#ty_arg)) {
+            Ok(Type::ImplTrait(_)) => quote!(let #arg_name = #declared;),
+            _ => quote!(let #arg_name: #ty_arg = #declared;),
+        };
+        self.build_item(quote! {
+            #binding
+            #body_expr
+        })
+    }
+
+    /// Creates a function item with the given body.
+    pub(crate) fn build_item_with_receiver(
+        &self,
+        shape: FnShape,
+        body_expr: impl ToTokens,
+    ) -> Result<ItemFn, syn::Error> {
+        self.check_shape(shape)?;
+        self.build_item(body_expr)
+    }
+
+    /// Creates a function item with the given body.
+    fn build_item(&self, body: impl ToTokens) -> Result<ItemFn, syn::Error> {
+        let block = syn::parse2::<Block>(quote!({ #body }))?;
+        Ok(ItemFn {
+            attrs: self.attrs.clone(),
+            vis: self.vis.clone(),
+            constness: self.constness,
+            asyncness: None,
+            unsafety: self.unsafety,
+            abi: self.abi.clone(),
+            ident: self.ident.clone(),
+            decl: Box::new(self.decl.clone()),
+            block: Box::new(block),
+        })
+    }
+}
+
+/// Checks whether the function argument has the given shape.
+///
+/// `shape` should take a single argument.
+fn is_shape_of(shape: FnShape, arg: &FnArg) -> bool {
+    match (shape, arg) {
+        (FnShape::Arg, FnArg::Captured(arg)) => match arg.pat {
+            Pat::Ident(ref pat) => pat.by_ref.is_none() && pat.subpat.is_none(),
+            _ => false,
+        },
+        (FnShape::RefSelf, FnArg::SelfRef(arg)) => arg.mutability.is_none(),
+        (FnShape::RefMutSelf, FnArg::SelfRef(arg)) => arg.mutability.is_some(),
+        (FnShape::SelfValue, FnArg::SelfValue(_)) => true,
+        _ => false,
+    }
+}
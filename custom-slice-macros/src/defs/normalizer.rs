@@ -0,0 +1,30 @@
+//! Normalizer definitions.
+
+use syn::{Ident, ItemFn};
+
+use crate::attrs::CustomSliceAttrs;
+
+/// Normalizer function defined in the macro input.
+///
+/// The function has the type `fn(&SliceInner) -> Cow<'_, SliceInner>`.
+pub(crate) struct Normalizer {
+    /// Item.
+    pub(crate) item: ItemFn,
+    /// Attributes.
+    pub(crate) attrs: CustomSliceAttrs,
+}
+
+impl Normalizer {
+    /// Creates an item.
+    pub(crate) fn create_item(&self) -> ItemFn {
+        ItemFn {
+            attrs: self.attrs.raw.clone(),
+            ..self.item.clone()
+        }
+    }
+
+    /// Returns function name.
+    pub(crate) fn name(&self) -> &Ident {
+        &self.item.ident
+    }
+}
@@ -0,0 +1,532 @@
+//! Validator definitions.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    Expr, FnArg, GenericArgument, Ident, ItemFn, Lit, Meta, MetaList, NestedMeta, Path,
+    PathArguments, ReturnType, Type, Visibility,
+};
+
+use crate::{
+    attrs::{key_is, CustomSliceAttrs},
+    defs::CustomType,
+};
+
+use self::{regex::RegexRule, rules::Rules};
+
+mod regex;
+mod rules;
+
+/// Validator specification.
+pub(crate) enum Validator {
+    /// Validator function defined in the macro input.
+    Fn(ValidatorFn),
+    /// Validator generated from `#[custom_slice(validator(..))]`.
+    Generated(GeneratedValidator),
+    /// Validator functions combined by `#[custom_slice(validators(..))]`.
+    Combined(CombinedValidator),
+    /// Validator function defined outside the macro input, specified by
+    /// `#[custom_slice(validator = "path")]`.
+    Path(Path),
+    /// Validator type specified by `#[custom_slice(validator(type = ".."))]`.
+    Trait(TraitValidator),
+}
+
+impl Validator {
+    /// Creates items.
+    pub(crate) fn create_items(&self) -> TokenStream {
+        match self {
+            Validator::Fn(v) => v.create_item().into_token_stream(),
+            Validator::Generated(v) => v.create_items(),
+            Validator::Combined(v) => v.create_items(),
+            Validator::Path(_) | Validator::Trait(_) => TokenStream::new(),
+        }
+    }
+
+    /// Returns the path to the validator function.
+    pub(crate) fn path(&self) -> TokenStream {
+        match self {
+            Validator::Fn(v) => v.item.ident.clone().into_token_stream(),
+            Validator::Generated(v) => v.fn_name.clone().into_token_stream(),
+            Validator::Combined(v) => v.fn_name.clone().into_token_stream(),
+            Validator::Path(path) => path.into_token_stream(),
+            Validator::Trait(v) => {
+                let validator_trait = v.validator_trait();
+                quote!(#validator_trait::validate)
+            }
+        }
+    }
+
+    /// Returns the error type of the validator, if available.
+    ///
+    /// For validator functions, the error type is taken from the return type
+    /// `Result<(), Error>`.
+    /// The error type of validators specified by paths is not available.
+    pub(crate) fn error_type(&self) -> Option<Type> {
+        match self {
+            Validator::Fn(v) => v.error_type(),
+            Validator::Generated(v) => {
+                let error_ty = &v.error_ty;
+                Some(syn::parse_quote!(#error_ty))
+            }
+            Validator::Combined(v) => Some(v.result_error_type()),
+            Validator::Path(_) => None,
+            Validator::Trait(v) => {
+                let validator_trait = v.validator_trait();
+                Some(syn::parse_quote!(#validator_trait::Error))
+            }
+        }
+    }
+
+    /// Returns JSON Schema keywords and values for the constraints checked by
+    /// the validator.
+    ///
+    /// Only generated validators have known constraints.
+    pub(crate) fn schema_keywords(&self) -> Vec<(&'static str, SchemaValue)> {
+        match self {
+            Validator::Generated(v) => match &v.rule {
+                Rule::Regex(rule) => rule.schema_keywords(v.target),
+                Rule::All(rules) => rules.schema_keywords(v.target),
+            },
+            Validator::Fn(_)
+            | Validator::Combined(_)
+            | Validator::Path(_)
+            | Validator::Trait(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the error type generated for the validator, if available.
+    pub(crate) fn generated_error_type(&self) -> Option<&Ident> {
+        match self {
+            Validator::Fn(_)
+            | Validator::Combined(_)
+            | Validator::Path(_)
+            | Validator::Trait(_) => None,
+            Validator::Generated(v) => Some(&v.error_ty),
+        }
+    }
+}
+
+/// Value of a JSON Schema keyword.
+pub(crate) enum SchemaValue {
+    /// Integer.
+    Int(usize),
+    /// String.
+    Str(String),
+    /// Array of schemas with the patterns.
+    Patterns(Vec<String>),
+}
+
+/// Validator function defined in the macro input.
+pub(crate) struct ValidatorFn {
+    /// Item.
+    pub(crate) item: ItemFn,
+    /// Attributes.
+    pub(crate) attrs: CustomSliceAttrs,
+}
+
+impl ValidatorFn {
+    /// Returns the error type `Error` of the return type `Result<_, Error>`.
+    fn error_type(&self) -> Option<Type> {
+        let ty = match &self.item.decl.output {
+            ReturnType::Type(_, ty) => ty,
+            ReturnType::Default => return None,
+        };
+        let path = match &**ty {
+            Type::Path(ty) if ty.qself.is_none() => &ty.path,
+            _ => return None,
+        };
+        let segment = path.segments.last()?.into_value();
+        if segment.ident != "Result" {
+            return None;
+        }
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                })
+                .nth(1),
+            _ => None,
+        }
+    }
+
+    /// Creates an item.
+    fn create_item(&self) -> ItemFn {
+        ItemFn {
+            attrs: self.attrs.raw.clone(),
+            ..self.item.clone()
+        }
+    }
+}
+
+/// Validator function for owned types (`#[custom_slice(validator(owned))]`).
+pub(crate) struct OwnedValidator {
+    /// Validator function.
+    func: ValidatorFn,
+    /// Whether the function takes `OwnedInner` by value.
+    by_value: bool,
+}
+
+impl OwnedValidator {
+    /// Creates a new owned validator.
+    pub(crate) fn new(func: ValidatorFn) -> Result<Self, syn::Error> {
+        let inputs = &func.item.decl.inputs;
+        let by_value = match inputs.iter().collect::<Vec<_>>().as_slice() {
+            [FnArg::Captured(arg)] => match arg.ty {
+                Type::Reference(_) => false,
+                _ => true,
+            },
+            _ => {
+                let msg = "owned validator should take `&OwnedInner` or `OwnedInner`";
+                return Err(if inputs.is_empty() {
+                    syn::Error::new_spanned(&func.item.ident, msg)
+                } else {
+                    syn::Error::new_spanned(inputs, msg)
+                });
+            }
+        };
+        Ok(Self { func, by_value })
+    }
+
+    /// Creates an item.
+    pub(crate) fn create_item(&self) -> ItemFn {
+        self.func.create_item()
+    }
+
+    /// Returns function name.
+    pub(crate) fn name(&self) -> &Ident {
+        &self.func.item.ident
+    }
+
+    /// Returns whether the function takes `OwnedInner` by value.
+    ///
+    /// Validators taking the value return `Result<OwnedInner, Error>`.
+    pub(crate) fn takes_value(&self) -> bool {
+        self.by_value
+    }
+
+    /// Returns the error type of the validator, if available.
+    pub(crate) fn error_type(&self) -> Option<Type> {
+        self.func.error_type()
+    }
+}
+
+/// Validator functions combined by `#[custom_slice(validators(..))]`.
+pub(crate) struct CombinedValidator {
+    /// Validator functions and their error mappings, in declaration order.
+    fns: Vec<(ValidatorFn, Option<Expr>)>,
+    /// Slice inner type.
+    slice_inner: Type,
+    /// Common error type.
+    error_ty: Type,
+    /// Whether all validators are run and all errors are collected.
+    collect_all: bool,
+    /// Combined validator function name.
+    fn_name: Ident,
+}
+
+impl CombinedValidator {
+    /// Creates a validator from `validators(..)` of the slice type.
+    pub(crate) fn new(
+        fns: Vec<ValidatorFn>,
+        spec: &MetaList,
+        slice: &CustomType,
+    ) -> Result<Self, syn::Error> {
+        let mut error_ty = None;
+        let mut collect_all = false;
+        for nested_meta in &spec.nested {
+            // Invalid values are already reported by the schema check.
+            match nested_meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if key_is(&nv.ident, "error") => {
+                    if let Lit::Str(lit) = &nv.lit {
+                        error_ty = Some(lit.parse::<Type>()?);
+                    }
+                }
+                NestedMeta::Meta(Meta::Word(ident)) if key_is(ident, "collect_all") => {
+                    collect_all = true;
+                }
+                _ => {}
+            }
+        }
+        let error_ty = error_ty.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &spec.ident,
+                "`validators(..)` requires the common error type `error = \"...\"`",
+            )
+        })?;
+        let fns = fns
+            .into_iter()
+            .map(|func| {
+                let map = func.attrs.get_validator_map()?.map(|(_, expr)| expr);
+                Ok((func, map))
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+        Ok(Self {
+            fns,
+            slice_inner: slice.inner_type().clone(),
+            error_ty,
+            collect_all,
+            fn_name: Ident::new(
+                &format!("__custom_slice_validate_{}", slice.outer_type()),
+                Span::call_site(),
+            ),
+        })
+    }
+
+    /// Returns the error type of the combined validator.
+    ///
+    /// This is `Vec<Error>` if all errors are collected.
+    fn result_error_type(&self) -> Type {
+        let error_ty = &self.error_ty;
+        if self.collect_all {
+            syn::parse_quote!(::std::vec::Vec<#error_ty>)
+        } else {
+            error_ty.clone()
+        }
+    }
+
+    /// Creates the validator functions and the combined validator function.
+    fn create_items(&self) -> TokenStream {
+        let items = self.fns.iter().map(|(func, _)| func.create_item());
+        let checks = self.fns.iter().map(|(func, map)| {
+            let name = &func.item.ident;
+            let error = match map {
+                Some(map) => quote!((#map)(e)),
+                None => quote!(::core::convert::From::from(e)),
+            };
+            if self.collect_all {
+                quote! {
+                    if let ::core::result::Result::Err(e) = #name(s) {
+                        errors.push(#error);
+                    }
+                }
+            } else {
+                quote! {
+                    if let ::core::result::Result::Err(e) = #name(s) {
+                        return ::core::result::Result::Err(#error);
+                    }
+                }
+            }
+        });
+        let fn_name = &self.fn_name;
+        let slice_inner = &self.slice_inner;
+        let error_ty = &self.error_ty;
+        let result_error_ty = self.result_error_type();
+        let body = if self.collect_all {
+            quote! {
+                let mut errors: ::std::vec::Vec<#error_ty> = ::std::vec::Vec::new();
+                #(#checks)*
+                if errors.is_empty() {
+                    ::core::result::Result::Ok(())
+                } else {
+                    ::core::result::Result::Err(errors)
+                }
+            }
+        } else {
+            quote! {
+                #(#checks)*
+                ::core::result::Result::Ok(())
+            }
+        };
+
+        quote! {
+            #(#items)*
+
+            #[allow(non_snake_case)]
+            fn #fn_name(s: &#slice_inner) -> ::core::result::Result<(), #result_error_ty> {
+                #body
+            }
+        }
+    }
+}
+
+/// Validator type implementing `custom_slice::Validator`.
+pub(crate) struct TraitValidator {
+    /// Validator type.
+    ty: Type,
+    /// Slice inner type.
+    slice_inner: Type,
+}
+
+impl TraitValidator {
+    /// Checks whether `validator(..)` specifies the validator type.
+    pub(crate) fn is_specified_by(spec: &MetaList) -> bool {
+        spec.nested.iter().any(|nested_meta| match nested_meta {
+            NestedMeta::Meta(meta) => key_is(&meta.name(), "type"),
+            NestedMeta::Literal(_) => false,
+        })
+    }
+
+    /// Creates a validator from `validator(type = "..")` of the slice type.
+    pub(crate) fn new(spec: &MetaList, slice: &CustomType) -> Result<Self, syn::Error> {
+        let mut ty = None;
+        for nested_meta in &spec.nested {
+            // Invalid values are already reported by the schema check.
+            let meta = match nested_meta {
+                NestedMeta::Meta(meta) => meta,
+                NestedMeta::Literal(_) => continue,
+            };
+            match meta {
+                Meta::NameValue(nv) if key_is(&nv.ident, "type") => {
+                    if let Lit::Str(lit) = &nv.lit {
+                        ty = Some(lit.parse::<Type>()?);
+                    }
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta.name(),
+                        format!("`{}` cannot be used with `type`", meta.name()),
+                    ))
+                }
+            }
+        }
+        let ty = ty.ok_or_else(|| {
+            syn::Error::new_spanned(&spec.ident, "expected `validator(type = \"...\")`")
+        })?;
+
+        Ok(Self {
+            ty,
+            slice_inner: slice.inner_type().clone(),
+        })
+    }
+
+    /// Returns `<Type as ::custom_slice::Validator<SliceInner>>`.
+    fn validator_trait(&self) -> TokenStream {
+        let Self { ty, slice_inner } = self;
+        quote!(<#ty as ::custom_slice::Validator<#slice_inner>>)
+    }
+}
+
+/// Slice inner type supported by generated validators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    /// `str`.
+    Str,
+    /// `[u8]`.
+    Bytes,
+}
+
+impl Target {
+    /// Detects the target from the slice inner type.
+    fn from_type(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("str") => Some(Target::Str),
+            Type::Slice(ty) => match &*ty.elem {
+                Type::Path(elem) if elem.qself.is_none() && elem.path.is_ident("u8") => {
+                    Some(Target::Bytes)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the type of the validator argument.
+    fn ref_type(self) -> TokenStream {
+        match self {
+            Target::Str => quote!(&str),
+            Target::Bytes => quote!(&[u8]),
+        }
+    }
+}
+
+/// Validation rule of the generated validator.
+enum Rule {
+    /// `regex = ".."`.
+    Regex(RegexRule),
+    /// `all(..)`.
+    All(Rules),
+}
+
+/// Validator generated from `#[custom_slice(validator(..))]`.
+pub(crate) struct GeneratedValidator {
+    /// Validation rule.
+    rule: Rule,
+    /// Slice inner type.
+    target: Target,
+    /// Visibility of the generated error type.
+    vis: Visibility,
+    /// Validator function name.
+    fn_name: Ident,
+    /// Error type name.
+    error_ty: Ident,
+}
+
+impl GeneratedValidator {
+    /// Creates a validator from `validator(..)` of the slice type.
+    pub(crate) fn new(spec: &MetaList, slice: &CustomType) -> Result<Self, syn::Error> {
+        let target = Target::from_type(slice.inner_type()).ok_or_else(|| {
+            syn::Error::new_spanned(
+                slice.inner_type(),
+                "generated validators are available only for `str` and `[u8]` slice types",
+            )
+        })?;
+
+        let mut rule = None;
+        let mut error_ty = None;
+        for nested_meta in &spec.nested {
+            let meta = match nested_meta {
+                NestedMeta::Meta(meta) => meta,
+                // Already reported by the schema check.
+                NestedMeta::Literal(_) => continue,
+            };
+            let new_rule = match meta {
+                Meta::NameValue(nv) if key_is(&nv.ident, "regex") => match &nv.lit {
+                    Lit::Str(lit) => Rule::Regex(RegexRule::new(lit, target)?),
+                    _ => continue,
+                },
+                Meta::List(list) if key_is(&list.ident, "all") => {
+                    Rule::All(Rules::new(list, target)?)
+                }
+                Meta::NameValue(nv) if key_is(&nv.ident, "error") => {
+                    if let Lit::Str(lit) = &nv.lit {
+                        error_ty = Some(lit.parse::<Ident>()?);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            if rule.replace(new_rule).is_some() {
+                return Err(syn::Error::new_spanned(
+                    meta.name(),
+                    "only one of `regex` and `all` can be specified",
+                ));
+            }
+        }
+        let rule = rule.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &spec.ident,
+                "expected `validator(regex = \"...\")`, `validator(all(..))`, or \
+                 `validator(type = \"...\")`",
+            )
+        })?;
+
+        let slice_name = slice.outer_type();
+        let error_ty = error_ty
+            .unwrap_or_else(|| Ident::new(&format!("{}Error", slice_name), Span::call_site()));
+        let fn_name = Ident::new(
+            &format!("__custom_slice_validate_{}", slice_name),
+            Span::call_site(),
+        );
+
+        Ok(Self {
+            rule,
+            target,
+            vis: slice.item.vis.clone(),
+            fn_name,
+            error_ty,
+        })
+    }
+
+    /// Creates the validator function and the error type.
+    fn create_items(&self) -> TokenStream {
+        match &self.rule {
+            Rule::Regex(rule) => rule.create_items(self),
+            Rule::All(rules) => rules.create_items(self),
+        }
+    }
+}
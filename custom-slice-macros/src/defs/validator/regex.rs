@@ -0,0 +1,143 @@
+//! Validator generated from a regular expression.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+use crate::defs::validator::{GeneratedValidator, SchemaValue, Target};
+
+/// `regex = ".."`.
+pub(super) struct RegexRule {
+    /// Pattern.
+    pattern: LitStr,
+}
+
+impl RegexRule {
+    /// Creates a new rule.
+    pub(super) fn new(pattern: &LitStr, target: Target) -> Result<Self, syn::Error> {
+        check_pattern(pattern, target)?;
+        Ok(Self {
+            pattern: pattern.clone(),
+        })
+    }
+
+    /// Returns JSON Schema keywords and values for the rule.
+    ///
+    /// The pattern is used only for `str` slice types, because `[u8]` slices
+    /// are arrays in JSON.
+    pub(super) fn schema_keywords(&self, target: Target) -> Vec<(&'static str, SchemaValue)> {
+        match target {
+            Target::Str => vec![("pattern", SchemaValue::Str(self.pattern.value()))],
+            Target::Bytes => Vec::new(),
+        }
+    }
+
+    /// Creates the validator function and the error type.
+    pub(super) fn create_items(&self, validator: &GeneratedValidator) -> TokenStream {
+        let pattern = &self.pattern;
+        let GeneratedValidator {
+            target,
+            vis,
+            fn_name,
+            error_ty,
+            ..
+        } = validator;
+        let ty_input_ref = target.ref_type();
+        let (ty_regex, ty_input_owned) = match target {
+            Target::Str => (quote!(::regex::Regex), quote!(::std::string::String)),
+            Target::Bytes => (quote!(::regex::bytes::Regex), quote!(::std::vec::Vec<u8>)),
+        };
+        let error_doc = format!(
+            "Error for a value not matching the pattern `{}`.",
+            pattern.value()
+        );
+        let display = match target {
+            Target::Str => quote! {
+                ::core::write!(
+                    f,
+                    "{:?} does not match the pattern {:?}",
+                    self.input,
+                    #pattern
+                )
+            },
+            Target::Bytes => quote! {
+                f.write_str("b\"")?;
+                for &b in self.input.iter() {
+                    for c in ::std::ascii::escape_default(b) {
+                        ::core::fmt::Write::write_char(f, ::core::convert::From::from(c))?;
+                    }
+                }
+                ::core::write!(f, "\" does not match the pattern {:?}", #pattern)
+            },
+        };
+
+        // `OnceLock` is not used, to keep the minimum supported rustc version
+        // of the user crate.
+        let get_regex = quote! {
+            static REGEX: ::custom_slice::OnceCell<#ty_regex> = ::custom_slice::OnceCell::new();
+            let regex = REGEX.get_or_init(|| {
+                <#ty_regex>::new(#pattern).expect("The pattern is checked by the macro")
+            });
+        };
+
+        quote! {
+            #[doc = #error_doc]
+            #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+            #vis struct #error_ty {
+                /// Rejected input.
+                input: #ty_input_owned,
+            }
+
+            impl #error_ty {
+                /// Returns the rejected input.
+                #vis fn input(&self) -> #ty_input_ref {
+                    &self.input
+                }
+
+                /// Returns the pattern the input is required to match.
+                #vis fn pattern(&self) -> &'static str {
+                    #pattern
+                }
+            }
+
+            impl ::core::fmt::Display for #error_ty {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #display
+                }
+            }
+
+            impl ::std::error::Error for #error_ty {}
+
+            #[allow(non_snake_case)]
+            fn #fn_name(s: #ty_input_ref) -> ::core::result::Result<(), #error_ty> {
+                #get_regex
+                if regex.is_match(s) {
+                    ::core::result::Result::Ok(())
+                } else {
+                    ::core::result::Result::Err(#error_ty {
+                        input: ::std::borrow::ToOwned::to_owned(s),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Checks whether the pattern is a valid regular expression.
+#[cfg(feature = "regex")]
+fn check_pattern(pattern: &LitStr, target: Target) -> Result<(), syn::Error> {
+    let result = match target {
+        Target::Str => ::regex::Regex::new(&pattern.value()).map(drop),
+        Target::Bytes => ::regex::bytes::Regex::new(&pattern.value()).map(drop),
+    };
+    result.map_err(|e| syn::Error::new_spanned(pattern, format!("invalid regex: {}", e)))
+}
+
+/// Checks whether the pattern is a valid regular expression.
+#[cfg(not(feature = "regex"))]
+fn check_pattern(pattern: &LitStr, _: Target) -> Result<(), syn::Error> {
+    Err(syn::Error::new_spanned(
+        pattern,
+        "`validator(regex = \"...\")` requires `regex` feature of `custom-slice-macros`",
+    ))
+}
@@ -0,0 +1,465 @@
+//! Validator generated from built-in rules.
+
+use std::convert::TryFrom;
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{ext::IdentExt, Lit, LitStr, Meta, MetaList, NestedMeta};
+
+use crate::defs::validator::{GeneratedValidator, SchemaValue, Target};
+
+/// Rules checked before scanning the value.
+enum LenRule {
+    /// `non_empty`.
+    NonEmpty,
+    /// `max_len = N`.
+    MaxLen(usize),
+}
+
+/// Rules checked for each character (or byte).
+enum CharRule {
+    /// `no_leading = ".."`.
+    NoLeading(Charset),
+    /// `ascii`.
+    Ascii,
+    /// `ascii_lowercase`.
+    AsciiLowercase,
+    /// `charset = ".."`.
+    Charset(Charset),
+}
+
+impl CharRule {
+    /// Returns the regular expression for JSON Schema `pattern`.
+    fn schema_pattern(&self) -> String {
+        match self {
+            CharRule::NoLeading(charset) => format!("^(?:[^{}]|$)", charset.schema_class()),
+            CharRule::Ascii => "^[\\u0000-\\u007F]*$".to_owned(),
+            CharRule::AsciiLowercase => "^[a-z]*$".to_owned(),
+            CharRule::Charset(charset) => format!("^[{}]*$", charset.schema_class()),
+        }
+    }
+
+    /// Checks whether the rule restricts the value to ASCII characters.
+    fn is_ascii_only(&self) -> bool {
+        match self {
+            CharRule::NoLeading(_) => false,
+            CharRule::Ascii | CharRule::AsciiLowercase => true,
+            CharRule::Charset(charset) => charset.ranges.iter().all(|&(_, end)| end.is_ascii()),
+        }
+    }
+}
+
+/// `all(..)`.
+pub(super) struct Rules {
+    /// Length rules.
+    len_rules: Vec<LenRule>,
+    /// Whether the value should be valid UTF-8.
+    utf8: bool,
+    /// Character rules.
+    char_rules: Vec<CharRule>,
+    /// Whether the value is scanned as bytes (not as characters).
+    scan_bytes: bool,
+}
+
+impl Rules {
+    /// Creates rules from `all(..)`.
+    pub(super) fn new(list: &MetaList, target: Target) -> Result<Self, syn::Error> {
+        let utf8 = list.nested.iter().any(|nested_meta| match nested_meta {
+            NestedMeta::Meta(Meta::Word(ident)) => ident.unraw() == "utf8",
+            _ => false,
+        });
+        let scan_bytes = target == Target::Bytes && !utf8;
+
+        let mut len_rules = Vec::new();
+        let mut char_rules = Vec::new();
+        for nested_meta in &list.nested {
+            // Invalid values are already reported by the schema check.
+            match nested_meta {
+                NestedMeta::Meta(Meta::Word(ident)) => match ident.unraw().to_string().as_str() {
+                    "non_empty" => len_rules.push(LenRule::NonEmpty),
+                    "ascii" => char_rules.push(CharRule::Ascii),
+                    "ascii_lowercase" => char_rules.push(CharRule::AsciiLowercase),
+                    "utf8" if target == Target::Str => {
+                        return Err(syn::Error::new_spanned(
+                            ident,
+                            "`utf8` is available only for `[u8]` slice types",
+                        ));
+                    }
+                    _ => {}
+                },
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    match (nv.ident.unraw().to_string().as_str(), &nv.lit) {
+                        ("max_len", Lit::Int(lit)) => {
+                            let max = usize::try_from(lit.value()).map_err(|_| {
+                                syn::Error::new_spanned(lit, "`max_len` is too large for `usize`")
+                            })?;
+                            len_rules.push(LenRule::MaxLen(max))
+                        }
+                        ("charset", Lit::Str(lit)) => {
+                            char_rules.push(CharRule::Charset(Charset::new(lit, scan_bytes)?))
+                        }
+                        ("no_leading", Lit::Str(lit)) => {
+                            char_rules.push(CharRule::NoLeading(Charset::new(lit, scan_bytes)?))
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if len_rules.is_empty() && char_rules.is_empty() && !utf8 {
+            return Err(syn::Error::new_spanned(
+                &list.ident,
+                "`all(..)` requires at least one rule",
+            ));
+        }
+
+        Ok(Self {
+            len_rules,
+            utf8,
+            char_rules,
+            scan_bytes,
+        })
+    }
+
+    /// Returns JSON Schema keywords and values for the rules.
+    ///
+    /// Character rules are converted into patterns only for `str` slice types,
+    /// because `[u8]` slices are arrays in JSON.
+    pub(super) fn schema_keywords(&self, target: Target) -> Vec<(&'static str, SchemaValue)> {
+        let (min_key, max_key) = match target {
+            Target::Str => ("minLength", "maxLength"),
+            Target::Bytes => ("minItems", "maxItems"),
+        };
+        // `maxLength` counts characters while `max_len` counts bytes, so it
+        // is emitted only if they are the same.
+        let is_len_in_chars =
+            target == Target::Bytes || self.char_rules.iter().any(CharRule::is_ascii_only);
+        let mut keywords = Vec::new();
+        for rule in &self.len_rules {
+            match rule {
+                LenRule::NonEmpty => keywords.push((min_key, SchemaValue::Int(1))),
+                LenRule::MaxLen(max) if is_len_in_chars => {
+                    keywords.push((max_key, SchemaValue::Int(*max)))
+                }
+                LenRule::MaxLen(_) => {}
+            }
+        }
+        if target == Target::Str {
+            let mut patterns = self
+                .char_rules
+                .iter()
+                .map(CharRule::schema_pattern)
+                .collect::<Vec<_>>();
+            match patterns.len() {
+                0 => {}
+                1 => keywords.push(("pattern", SchemaValue::Str(patterns.remove(0)))),
+                _ => keywords.push(("allOf", SchemaValue::Patterns(patterns))),
+            }
+        }
+        keywords
+    }
+
+    /// Creates the validator function and the error type.
+    pub(super) fn create_items(&self, validator: &GeneratedValidator) -> TokenStream {
+        let GeneratedValidator {
+            target,
+            vis,
+            fn_name,
+            error_ty,
+            ..
+        } = validator;
+        let ty_input_ref = target.ref_type();
+
+        let mut variants = Vec::new();
+        let mut offsets = Vec::new();
+        let mut messages = Vec::new();
+        let mut len_checks = Vec::new();
+        for rule in &self.len_rules {
+            match rule {
+                LenRule::NonEmpty => {
+                    variants.push(quote! {
+                        /// The value is empty.
+                        Empty
+                    });
+                    offsets.push(quote!(#error_ty::Empty => 0));
+                    messages.push(quote! {
+                        #error_ty::Empty => ::core::write!(f, "the value is empty")
+                    });
+                    len_checks.push(quote! {
+                        if s.is_empty() {
+                            return ::core::result::Result::Err(#error_ty::Empty);
+                        }
+                    });
+                }
+                LenRule::MaxLen(max) => {
+                    let max = Literal::usize_unsuffixed(*max);
+                    variants.push(quote! {
+                        /// The value is longer than the maximum length.
+                        TooLong {
+                            /// Length of the value in bytes.
+                            len: usize,
+                            /// Maximum length in bytes.
+                            max: usize,
+                        }
+                    });
+                    offsets.push(quote!(#error_ty::TooLong { max, .. } => *max));
+                    messages.push(quote! {
+                        #error_ty::TooLong { len, max } => ::core::write!(
+                            f,
+                            "the value is {} bytes long, but the maximum length is {} bytes",
+                            len,
+                            max
+                        )
+                    });
+                    len_checks.push(quote! {
+                        if s.len() > #max {
+                            return ::core::result::Result::Err(#error_ty::TooLong {
+                                len: s.len(),
+                                max: #max,
+                            });
+                        }
+                    });
+                }
+            }
+        }
+
+        let utf8_check = if self.utf8 {
+            variants.push(quote! {
+                /// The value is not valid UTF-8.
+                InvalidUtf8 {
+                    /// Byte offset of the invalid sequence.
+                    offset: usize,
+                }
+            });
+            offsets.push(quote!(#error_ty::InvalidUtf8 { offset } => *offset));
+            messages.push(quote! {
+                #error_ty::InvalidUtf8 { offset } => ::core::write!(
+                    f,
+                    "invalid UTF-8 sequence at byte offset {}",
+                    offset
+                )
+            });
+            quote! {
+                let s = match ::core::str::from_utf8(s) {
+                    ::core::result::Result::Ok(v) => v,
+                    ::core::result::Result::Err(e) => {
+                        return ::core::result::Result::Err(#error_ty::InvalidUtf8 {
+                            offset: e.valid_up_to(),
+                        });
+                    }
+                };
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        let mut char_checks = Vec::new();
+        for rule in &self.char_rules {
+            let (variant, doc, message, cond) = match rule {
+                CharRule::NoLeading(charset) => {
+                    let pat = charset.pattern();
+                    (
+                        quote!(InvalidLeading),
+                        format!("The value starts with a character in `{}`.", charset.source),
+                        format!("leading character in `{}`", charset.source),
+                        quote!(offset == 0 && match c { #pat => true, _ => false }),
+                    )
+                }
+                CharRule::Ascii => (
+                    quote!(NonAscii),
+                    "The value contains a non-ASCII character.".to_owned(),
+                    "non-ASCII character".to_owned(),
+                    quote!(!c.is_ascii()),
+                ),
+                CharRule::AsciiLowercase => (
+                    quote!(NotAsciiLowercase),
+                    "The value contains a character other than ASCII lowercase letters.".to_owned(),
+                    "non-ASCII-lowercase character".to_owned(),
+                    quote!(!c.is_ascii_lowercase()),
+                ),
+                CharRule::Charset(charset) => {
+                    let pat = charset.pattern();
+                    (
+                        quote!(InvalidChar),
+                        format!(
+                            "The value contains a character not in `{}`.",
+                            charset.source
+                        ),
+                        format!("character not in `{}`", charset.source),
+                        quote!(match c { #pat => false, _ => true }),
+                    )
+                }
+            };
+            // `message` is used as a format string.
+            let message = format!(
+                "{} at byte offset {{}}",
+                message.replace('{', "{{").replace('}', "}}")
+            );
+            variants.push(quote! {
+                #[doc = #doc]
+                #variant {
+                    /// Byte offset of the character.
+                    offset: usize,
+                }
+            });
+            offsets.push(quote!(#error_ty::#variant { offset } => *offset));
+            messages.push(quote! {
+                #error_ty::#variant { offset } => ::core::write!(f, #message, offset)
+            });
+            char_checks.push(quote! {
+                if #cond {
+                    return ::core::result::Result::Err(#error_ty::#variant { offset });
+                }
+            });
+        }
+        let scan = if char_checks.is_empty() {
+            TokenStream::new()
+        } else if self.scan_bytes {
+            quote! {
+                for (offset, &c) in ::core::iter::Iterator::enumerate(s.iter()) {
+                    #(#char_checks)*
+                }
+            }
+        } else {
+            quote! {
+                for (offset, c) in s.char_indices() {
+                    #(#char_checks)*
+                }
+            }
+        };
+
+        quote! {
+            /// Error for a value violating the validation rules.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #error_ty {
+                #(#variants,)*
+            }
+
+            impl #error_ty {
+                /// Returns the byte offset where the validation failed.
+                #vis fn offset(&self) -> usize {
+                    match self {
+                        #(#offsets,)*
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for #error_ty {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#messages,)*
+                    }
+                }
+            }
+
+            impl ::std::error::Error for #error_ty {}
+
+            #[allow(non_snake_case)]
+            fn #fn_name(s: #ty_input_ref) -> ::core::result::Result<(), #error_ty> {
+                #(#len_checks)*
+                #utf8_check
+                #scan
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// Character set such as `a-z0-9_`.
+struct Charset {
+    /// Source string.
+    source: String,
+    /// Inclusive ranges.
+    ranges: Vec<(char, char)>,
+    /// Whether the characters are matched as bytes.
+    bytes: bool,
+}
+
+impl Charset {
+    /// Parses the character set.
+    ///
+    /// `-` between two characters denotes a range, and `-` at the beginning or
+    /// the end denotes `-` itself.
+    fn new(lit: &LitStr, bytes: bool) -> Result<Self, syn::Error> {
+        let source = lit.value();
+        let chars = source.chars().collect::<Vec<_>>();
+        if chars.is_empty() {
+            return Err(syn::Error::new_spanned(lit, "empty character set"));
+        }
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            if i + 2 < chars.len() && chars[i + 1] == '-' {
+                let (start, end) = (chars[i], chars[i + 2]);
+                if start > end {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        format!("invalid range `{}-{}` in the character set", start, end),
+                    ));
+                }
+                ranges.push((start, end));
+                i += 3;
+            } else {
+                ranges.push((chars[i], chars[i]));
+                i += 1;
+            }
+        }
+        if bytes && !source.is_ascii() {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "character set for `[u8]` slice types should contain only ASCII characters \
+                 (or specify `utf8` rule)",
+            ));
+        }
+
+        Ok(Self {
+            source,
+            ranges,
+            bytes,
+        })
+    }
+
+    /// Returns the content of the regular expression character class
+    /// matching the characters in the set.
+    fn schema_class(&self) -> String {
+        let escape = |c: char, class: &mut String| match c {
+            '\\' | ']' | '[' | '^' | '-' => {
+                class.push('\\');
+                class.push(c);
+            }
+            c if c.is_control() => class.push_str(&format!("\\u{:04X}", c as u32)),
+            c => class.push(c),
+        };
+        let mut class = String::new();
+        for &(start, end) in &self.ranges {
+            escape(start, &mut class);
+            if start != end {
+                class.push('-');
+                escape(end, &mut class);
+            }
+        }
+        class
+    }
+
+    /// Returns the pattern matching the characters in the set.
+    fn pattern(&self) -> TokenStream {
+        let literal = |c: char| {
+            if self.bytes {
+                Literal::u8_suffixed(c as u8)
+            } else {
+                Literal::character(c)
+            }
+        };
+        let pats = self.ranges.iter().map(|&(start, end)| {
+            let start_lit = literal(start);
+            if start == end {
+                quote!(#start_lit)
+            } else {
+                let end_lit = literal(end);
+                quote!(#start_lit..=#end_lit)
+            }
+        });
+        quote!(#(#pats)|*)
+    }
+}
@@ -4,7 +4,7 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
-use crate::defs::Definitions;
+use crate::defs::{Definitions, Input};
 
 pub(crate) mod attrs;
 pub(crate) mod codegen;
@@ -12,10 +12,14 @@ pub(crate) mod defs;
 
 #[proc_macro]
 pub fn define_slice_types_pair(input: TokenStream) -> TokenStream {
-    let file: syn::File =
-        syn::parse(input).unwrap_or_else(|e| panic!("Failed to parse input token stream: {}", e));
-    let defs = Definitions::from_file(file)
-        .unwrap_or_else(|e| panic!("Failed to load definitions: {}", e));
+    let input: Input = match syn::parse(input) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let defs = match Definitions::from_input(input) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let output = defs.generate();
     output.into()
 }
@@ -0,0 +1,60 @@
+//! Safe unchecked conversions with `#[custom_slice(allow_safe_unchecked)]`.
+
+/// Validation error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+custom_slice_macros::define_slice_types_pair! {
+    /// Non-empty string.
+    ///
+    /// Empty strings break no memory safety, so safe unchecked conversions
+    /// are allowed.
+    #[derive(Debug, PartialEq)]
+    #[custom_slice(owned)]
+    #[custom_slice(allow_safe_unchecked)]
+    #[custom_slice(new_unchecked = "pub fn new_unchecked")]
+    #[custom_slice(derive(FromInner))]
+    pub struct NonEmptyString(String);
+
+    /// Non-empty string slice.
+    #[derive(Debug, PartialEq)]
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(allow_safe_unchecked)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(error(type = "Error"))]
+    #[custom_slice(new_unchecked = "pub fn new_unchecked")]
+    #[custom_slice(new_unchecked_mut = "pub fn new_unchecked_mut")]
+    #[custom_slice(get_mut = "pub fn as_mut_str")]
+    #[custom_slice(derive(Deref, DerefMut, FromInner))]
+    pub struct NonEmptyStr(str);
+
+    #[custom_slice(validator)]
+    fn validate(s: &str) -> Result<(), Error> {
+        if s.is_empty() {
+            Err(Error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn owned() {
+    let s = NonEmptyString::from("foo".to_owned());
+    assert_eq!(s, NonEmptyString::new_unchecked("foo".to_owned()));
+}
+
+#[test]
+fn slice() {
+    let mut buf = String::from("foo");
+    let s = NonEmptyStr::new_unchecked(&buf);
+    assert_eq!(s, <&NonEmptyStr>::from("foo"));
+    assert_eq!(NonEmptyStr::new(""), Err(Error));
+
+    let s = NonEmptyStr::new_unchecked_mut(&mut buf);
+    s.as_mut_str().make_ascii_uppercase();
+    assert_eq!(&**s, "FOO");
+    s.make_ascii_lowercase();
+    assert_eq!(&**s, "foo");
+}
@@ -0,0 +1,191 @@
+//! Validators generated from built-in rules.
+
+mod label {
+    custom_slice_macros::define_slice_types_pair! {
+        /// A DNS label like string.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Label(String);
+
+        /// A DNS label like string slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(
+            non_empty,
+            max_len = 8,
+            no_leading = "-",
+            charset = "a-z0-9-"
+        )))]
+        pub struct LabelStr(str);
+    }
+
+    #[test]
+    fn valid() {
+        assert!(LabelStr::new("a").is_ok());
+        assert!(LabelStr::new("foo-bar2").is_ok());
+        assert!(Label::new("x-1".to_owned()).is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(LabelStr::new("").unwrap_err(), LabelStrError::Empty);
+        assert_eq!(
+            LabelStr::new("foo-bar-baz").unwrap_err(),
+            LabelStrError::TooLong { len: 11, max: 8 }
+        );
+        assert_eq!(
+            LabelStr::new("-foo").unwrap_err(),
+            LabelStrError::InvalidLeading { offset: 0 }
+        );
+        assert_eq!(
+            LabelStr::new("foo_bar").unwrap_err(),
+            LabelStrError::InvalidChar { offset: 3 }
+        );
+        assert_eq!(
+            Label::new("fooé".to_owned()).unwrap_err(),
+            LabelStrError::InvalidChar { offset: 3 }
+        );
+    }
+
+    #[test]
+    fn error() {
+        let e = LabelStr::new("foo_bar").unwrap_err();
+        assert_eq!(e.offset(), 3);
+        assert_eq!(e.to_string(), "character not in `a-z0-9-` at byte offset 3");
+        assert_eq!(
+            LabelStr::new("foo-bar-baz").unwrap_err().to_string(),
+            "the value is 11 bytes long, but the maximum length is 8 bytes"
+        );
+    }
+}
+
+mod lower_ascii {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct LowerAsciiString(String);
+
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(ascii, ascii_lowercase), error = "Error"))]
+        pub struct LowerAsciiStr(str);
+    }
+
+    #[test]
+    fn validate() {
+        assert!(LowerAsciiStr::new("").is_ok());
+        assert!(LowerAsciiStr::new("hello").is_ok());
+        assert_eq!(
+            LowerAsciiStr::new("abcdéf").unwrap_err(),
+            Error::NonAscii { offset: 4 }
+        );
+        assert_eq!(
+            LowerAsciiStr::new("Hello").unwrap_err(),
+            Error::NotAsciiLowercase { offset: 0 }
+        );
+        assert_eq!(
+            LowerAsciiStr::new("hello world").unwrap_err(),
+            Error::NotAsciiLowercase { offset: 5 }
+        );
+    }
+}
+
+mod bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Token(Vec<u8>);
+
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, charset = "A-Za-z0-9_{}")))]
+        pub struct TokenBytes([u8]);
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Text(Vec<u8>);
+
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(utf8, no_leading = "é")))]
+        pub struct TextBytes([u8]);
+    }
+
+    #[test]
+    fn bytes() {
+        assert!(TokenBytes::new(b"Foo_1").is_ok());
+        assert_eq!(TokenBytes::new(b"").unwrap_err(), TokenBytesError::Empty);
+        assert_eq!(
+            TokenBytes::new(b"foo\xffbar").unwrap_err(),
+            TokenBytesError::InvalidChar { offset: 3 }
+        );
+        assert_eq!(
+            TokenBytes::new(b"{x}-").unwrap_err().to_string(),
+            "character not in `A-Za-z0-9_{}` at byte offset 3"
+        );
+    }
+
+    #[test]
+    fn utf8() {
+        assert!(TextBytes::new("aé".as_bytes()).is_ok());
+        assert_eq!(
+            TextBytes::new(b"ab\xff").unwrap_err(),
+            TextBytesError::InvalidUtf8 { offset: 2 }
+        );
+        assert_eq!(
+            TextBytes::new("éa".as_bytes()).unwrap_err(),
+            TextBytesError::InvalidLeading { offset: 0 }
+        );
+    }
+}
+
+#[no_implicit_prelude]
+mod without_prelude {
+    #[allow(dead_code)]
+    mod std {}
+    #[allow(dead_code)]
+    type Result = ();
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Name(::std::string::String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, max_len = 4, no_leading = "0-9", charset = "a-z0-9")))]
+        pub struct NameStr(str);
+    }
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Bytes(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(ascii, charset = "a-z")))]
+        pub struct BytesSlice([u8]);
+    }
+
+    #[test]
+    fn validate() {
+        ::std::assert!(NameStr::new("a1").is_ok());
+        ::std::assert!(NameStr::new("1a").is_err());
+        ::std::assert!(BytesSlice::new(b"foo").is_ok());
+        ::std::assert!(BytesSlice::new(b"Foo").is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Combined validators.
+
+/// Validation error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The value is empty.
+    Empty,
+    /// Non-ASCII character found.
+    NonAscii,
+    /// Too long.
+    TooLong(usize),
+}
+
+/// Error of `validate_ascii`.
+#[derive(Debug)]
+pub struct NonAsciiError;
+
+impl From<NonAsciiError> for Error {
+    fn from(_: NonAsciiError) -> Self {
+        Error::NonAscii
+    }
+}
+
+/// Reusable validator.
+fn validate_non_empty(s: &str) -> Result<(), Error> {
+    if s.is_empty() {
+        Err(Error::Empty)
+    } else {
+        Ok(())
+    }
+}
+
+mod first_failure {
+    use super::{Error, NonAsciiError};
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// An ASCII string with at most 4 bytes.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error", map = "{|e, _v| e}"))]
+        pub struct ShortAscii(String);
+
+        /// An ASCII string slice with at most 4 bytes.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error"))]
+        #[custom_slice(validators(error = "Error"))]
+        pub struct ShortAsciiStr(str);
+
+        #[custom_slice(validator)]
+        fn validate_non_empty(s: &str) -> Result<(), Error> {
+            super::validate_non_empty(s)
+        }
+
+        /// Checks whether the string is ASCII.
+        #[custom_slice(validator)]
+        fn validate_ascii(s: &str) -> Result<(), NonAsciiError> {
+            if s.is_ascii() {
+                Ok(())
+            } else {
+                Err(NonAsciiError)
+            }
+        }
+
+        #[custom_slice(validator(map = "Error::TooLong"))]
+        fn validate_len(s: &str) -> Result<(), usize> {
+            if s.len() > 4 {
+                Err(s.len())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert!(ShortAscii::new("abcd".to_owned()).is_ok());
+        assert!(ShortAsciiStr::new("a").is_ok());
+    }
+
+    #[test]
+    fn first_failure() {
+        assert_eq!(ShortAsciiStr::new("").unwrap_err(), Error::Empty);
+        assert_eq!(ShortAsciiStr::new("ééé").unwrap_err(), Error::NonAscii);
+        assert_eq!(
+            ShortAscii::new("abcde".to_owned()).unwrap_err(),
+            Error::TooLong(5)
+        );
+    }
+}
+
+mod collect_all {
+    use super::{Error, NonAsciiError};
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// An ASCII string with at most 4 bytes.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "(Vec<Error>, String)", map = "{|e, v| (e, v)}"))]
+        pub struct ShortAscii(String);
+
+        /// An ASCII string slice with at most 4 bytes.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Vec<Error>"))]
+        #[custom_slice(validators(error = "Error", collect_all))]
+        pub struct ShortAsciiStr(str);
+
+        #[custom_slice(validator)]
+        fn validate_ascii(s: &str) -> Result<(), NonAsciiError> {
+            if s.is_ascii() {
+                Ok(())
+            } else {
+                Err(NonAsciiError)
+            }
+        }
+
+        #[custom_slice(validator(map = "Error::TooLong"))]
+        fn validate_len(s: &str) -> Result<(), usize> {
+            if s.len() > 4 {
+                Err(s.len())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert!(ShortAscii::new("abcd".to_owned()).is_ok());
+        assert!(ShortAsciiStr::new("").is_ok());
+    }
+
+    #[test]
+    fn collect_all() {
+        assert_eq!(
+            ShortAsciiStr::new("ééé").unwrap_err(),
+            vec![Error::NonAscii, Error::TooLong(6)]
+        );
+        assert_eq!(
+            ShortAscii::new("abcde".to_owned()).unwrap_err(),
+            (vec![Error::TooLong(5)], "abcde".to_owned())
+        );
+    }
+}
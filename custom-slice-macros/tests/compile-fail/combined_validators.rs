@@ -0,0 +1,108 @@
+#![allow(unused_imports)]
+
+pub struct Error;
+
+mod multiple_without_validators {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+
+        #[custom_slice(validator)]
+        fn validate2(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod map_without_validators {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(map = "{|e| e}"))]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod without_error {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validators(collect_all))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod without_validator_fns {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validators(error = "super::Error"))]
+        pub struct Slice(str);
+    }
+}
+
+mod with_generated {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(ascii)))]
+        #[custom_slice(validators(error = "super::Error"))]
+        pub struct Slice(str);
+    }
+}
+
+mod map_for_owned_validator {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(owned, map = "{|e| e}"))]
+        fn validate(_: &String) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(debug_validate)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(debug_validate)]
+        pub struct Slice(str);
+    }
+}
+
+mod with_value {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(debug_validate = "true")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
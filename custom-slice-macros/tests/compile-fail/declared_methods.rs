@@ -0,0 +1,39 @@
+custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(new_unchecked = "pub fn new")]
+    pub struct Owned(String);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    pub struct Slice(str);
+
+    impl Owned {
+        #[custom_slice(new_unchecked)]
+        pub fn new2(s: String) -> Self;
+
+        #[custom_slice(get_ref)]
+        pub fn get(self) -> &String;
+
+        #[custom_slice(into_inner)]
+        pub fn into_inner(self) -> String {
+            self.0
+        }
+
+        #[custom_slice(new_checkd)]
+        pub fn new_checked(s: String) -> Self;
+    }
+
+    impl Slice {
+        #[custom_slice(into_inner)]
+        pub fn into_inner(&self) -> &str;
+
+        pub fn undecorated(&self) -> &str;
+    }
+
+    impl Unknown {
+        #[custom_slice(get_ref)]
+        pub fn get(&self) -> &str;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,91 @@
+#![allow(unused_imports)]
+
+pub struct Error;
+
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(error(generate = "OwnedError"))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod with_type {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(error(generate = "OwnedError", type = "Error"))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(error(type = "Error"))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod unknown_validator_error {
+    use super::Error;
+
+    type ValidationResult = Result<(), Error>;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(error(generate = "OwnedError"))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(error(type = "Error"))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> ValidationResult {
+            Ok(())
+        }
+    }
+}
+
+mod for_slice {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(error(generate = "SliceError"))]
+        pub struct Slice(str);
+    }
+}
+
+mod from_str_without_error_type {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(FromStr))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(error(type = "Error"))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
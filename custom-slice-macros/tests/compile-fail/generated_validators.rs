@@ -0,0 +1,73 @@
+mod utf8_for_str {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(utf8, non_empty)))]
+        pub struct Slice(str);
+    }
+}
+
+mod non_ascii_charset_for_bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(charset = "a-zé")))]
+        pub struct Slice([u8]);
+    }
+}
+
+mod invalid_range {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(charset = "z-a")))]
+        pub struct Slice(str);
+    }
+}
+
+mod invalid_rules {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty = 1, max_len = "64", ascii_lower)))]
+        pub struct Slice(str);
+    }
+}
+
+mod empty_rules {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all()))]
+        pub struct Slice(str);
+    }
+}
+
+mod unsupported_inner {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(Vec<u32>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        pub struct Slice([u32]);
+    }
+}
+
+fn main() {}
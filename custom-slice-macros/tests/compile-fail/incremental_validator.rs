@@ -0,0 +1,53 @@
+mod without_incremental_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(push_checked = "pub fn push_str")]
+        #[custom_slice(derive(FmtWrite))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(incremental_validator = "State")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod invalid_shape {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(incremental_validator = "State")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        impl Owned {
+            #[custom_slice(push_checked)]
+            pub fn push_str(s: &str) -> Result<(), ()>;
+        }
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
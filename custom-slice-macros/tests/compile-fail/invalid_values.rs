@@ -0,0 +1,14 @@
+custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(new_unchecked = 42)]
+    #[custom_slice(get_ref = "pub fn get", get_ref = "pub fn get2")]
+    #[custom_slice(derive(Deref, Deref))]
+    pub struct Owned(String);
+
+    #[repr(transparent)]
+    #[custom_slice(slice, owned)]
+    #[custom_slice(error(type = "()", type = "()"))]
+    pub struct Slice(str);
+}
+
+fn main() {}
@@ -0,0 +1,61 @@
+#![allow(unused_imports)]
+
+mod without_normalizer {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(from_inner_normalized = "pub fn from_inner_normalized")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        impl Slice {
+            #[custom_slice(from_inner_normalized)]
+            pub fn from_inner_normalized(s: &str) -> std::borrow::Cow<'_, Slice>;
+        }
+    }
+}
+
+mod duplicate {
+    use std::borrow::Cow;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(normalizer)]
+        fn normalize(s: &str) -> Cow<'_, str> {
+            Cow::Borrowed(s)
+        }
+
+        #[custom_slice(normalizer)]
+        fn normalize2(s: &str) -> Cow<'_, str> {
+            Cow::Borrowed(s)
+        }
+    }
+}
+
+mod unknown_key {
+    use std::borrow::Cow;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(normalizer, owned)]
+        fn normalize(s: &str) -> Cow<'_, str> {
+            Cow::Borrowed(s)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,86 @@
+#![allow(unused_imports)]
+
+pub struct Error;
+
+mod by_value_with_map {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error", map = "{|e, _v| e}"))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(owned))]
+        fn validate(v: String) -> Result<String, Error> {
+            Ok(v)
+        }
+    }
+}
+
+mod by_value_with_generate {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(generate = "OwnedError"))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(owned))]
+        fn validate(v: String) -> Result<String, Error> {
+            Ok(v)
+        }
+    }
+}
+
+mod duplicate {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(owned))]
+        fn validate(_: &String) -> Result<(), Error> {
+            Ok(())
+        }
+
+        #[custom_slice(validator(owned))]
+        fn validate2(_: &String) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod no_args {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(owned))]
+        fn validate() -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,71 @@
+mod without_valid_up_to {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(into_valid_prefix = "pub fn into_valid_prefix")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(from_prefix = "pub fn from_prefix")]
+        pub struct Slice(str);
+
+        impl Slice {
+            #[custom_slice(split_valid_prefix)]
+            pub fn split_valid_prefix(s: &str) -> (&Slice, &str);
+        }
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), usize> {
+            Ok(())
+        }
+    }
+}
+
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "|e: &usize| *e")]
+        pub struct Slice(str);
+    }
+}
+
+mod invalid_expr {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "|e: &usize|")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), usize> {
+            Ok(())
+        }
+    }
+}
+
+mod without_truncate {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(into_valid_prefix = "pub fn into_valid_prefix")]
+        pub struct Owned(Box<str>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "|e: &usize| *e")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), usize> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
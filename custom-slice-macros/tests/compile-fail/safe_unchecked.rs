@@ -0,0 +1,76 @@
+mod safe_fns {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new_unchecked")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked_mut = "pub fn new_unchecked_mut")]
+        pub struct Slice(str);
+
+        impl Slice {
+            #[custom_slice(get_mut)]
+            pub fn get_mut(&mut self) -> &mut str;
+        }
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod safe_derives {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(AsMutSliceInner, DerefMut))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(DerefMut, FromInnerMut))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod allowed {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(allow_safe_unchecked)]
+        #[custom_slice(new_unchecked = "pub fn new_unchecked")]
+        #[custom_slice(derive(FromInner))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(allow_safe_unchecked)]
+        #[custom_slice(get_mut = "pub fn get_mut")]
+        #[custom_slice(derive(Deref, DerefMut))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(allow_safe_unchecked)]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
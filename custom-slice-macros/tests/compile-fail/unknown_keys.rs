@@ -0,0 +1,15 @@
+custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(new_cheked = "pub fn new")]
+    #[custom_slice(derive(Dref))]
+    pub struct Owned(String);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(into_inner = "pub fn into_inner")]
+    #[custom_slice(derive(IntoInner))]
+    #[custom_slice(error(tpye = "()"))]
+    pub struct Slice(str);
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+mod with_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(FromInner))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(TryFromInner))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,85 @@
+#![allow(unused_imports)]
+
+pub struct Error;
+
+pub fn validate(_: &str) -> Result<(), Error> {
+    Ok(())
+}
+
+mod with_validator_fn {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator = "super::validate")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod with_validators {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator = "super::validate")]
+        #[custom_slice(validators(error = "super::Error"))]
+        pub struct Slice(str);
+    }
+}
+
+mod not_string {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator = 42)]
+        pub struct Slice(str);
+    }
+}
+
+mod without_marker {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator = "validate")]
+        fn validate(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
+
+mod attrs_for_other_items {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        const LEN: usize = 0;
+    }
+}
+
+fn main() {}
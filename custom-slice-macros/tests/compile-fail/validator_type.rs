@@ -0,0 +1,40 @@
+pub enum Validator {}
+
+mod with_regex {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(type = "super::Validator", regex = "^a$"))]
+        pub struct Slice(str);
+    }
+}
+
+mod with_error {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(type = "super::Validator", error = "Error"))]
+        pub struct Slice(str);
+    }
+}
+
+mod without_error_type {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(type = "super::Validator"))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
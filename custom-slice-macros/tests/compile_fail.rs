@@ -0,0 +1,7 @@
+//! Tests for diagnostics of invalid definitions.
+
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile-fail/*.rs");
+}
@@ -0,0 +1,199 @@
+//! Debug assertions by `#[custom_slice(debug_validate)]`.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Validation error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+custom_slice_macros::define_slice_types_pair! {
+    /// ASCII bytes.
+    #[derive(Debug, Default, PartialEq)]
+    #[custom_slice(owned)]
+    #[custom_slice(debug_validate)]
+    #[custom_slice(new_unchecked = "pub unsafe fn new_unchecked")]
+    #[custom_slice(get_mut = "pub unsafe fn as_vec_mut")]
+    #[custom_slice(derive(Deref))]
+    pub struct AsciiBuf(Vec<u8>);
+
+    /// ASCII bytes slice.
+    #[derive(Debug, PartialEq)]
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(debug_validate)]
+    #[custom_slice(new_unchecked = "pub unsafe fn new_unchecked")]
+    #[custom_slice(new_unchecked_mut = "pub unsafe fn new_unchecked_mut")]
+    #[custom_slice(get_ref = "pub fn as_bytes")]
+    #[custom_slice(get_mut = "pub unsafe fn as_bytes_mut")]
+    #[custom_slice(derive(DefaultBox, DefaultRef, IntoArc, IntoBox, IntoRc))]
+    pub struct AsciiBytes([u8]);
+
+    #[custom_slice(validator)]
+    fn validate(s: &[u8]) -> Result<(), Error> {
+        if s.is_ascii() {
+            Ok(())
+        } else {
+            Err(Error)
+        }
+    }
+}
+
+/// Returns a slice whose content is broken through the mutable accessor.
+fn broken(buf: &mut [u8]) -> &mut AsciiBytes {
+    let s = unsafe { AsciiBytes::new_unchecked_mut(buf) };
+    unsafe { s.as_bytes_mut()[0] = 0xff };
+    s
+}
+
+#[test]
+fn valid() {
+    let s = unsafe { AsciiBytes::new_unchecked(b"foo") };
+    assert_eq!(s.to_owned(), unsafe {
+        AsciiBuf::new_unchecked(b"foo".to_vec())
+    });
+    assert_eq!(Box::<AsciiBytes>::from(s).as_bytes(), b"foo");
+    assert_eq!(Arc::<AsciiBytes>::from(s).as_bytes(), b"foo");
+    assert_eq!(Rc::<AsciiBytes>::from(s).as_bytes(), b"foo");
+
+    let mut buf = *b"bar";
+    let s = unsafe { AsciiBytes::new_unchecked_mut(&mut buf) };
+    unsafe { s.as_bytes_mut()[0] = b'c' };
+    assert_eq!(s.as_bytes(), b"car");
+}
+
+#[test]
+fn default() {
+    assert_eq!(<&AsciiBytes>::default().as_bytes(), b"");
+    assert_eq!(Box::<AsciiBytes>::default().as_bytes(), b"");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn slice_new_unchecked() {
+    let _ = unsafe { AsciiBytes::new_unchecked(b"\xff") };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn slice_new_unchecked_mut() {
+    let _ = unsafe { AsciiBytes::new_unchecked_mut(&mut [0xff]) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn owned_new_unchecked() {
+    let _ = unsafe { AsciiBuf::new_unchecked(vec![0xff]) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn slice_get_mut() {
+    let mut buf = *b"foo";
+    let _ = unsafe { broken(&mut buf).as_bytes_mut() };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn owned_get_mut() {
+    let mut s = unsafe { AsciiBuf::new_unchecked(b"foo".to_vec()) };
+    unsafe { s.as_vec_mut().push(0xff) };
+    let _ = unsafe { s.as_vec_mut() };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn to_owned() {
+    let mut buf = *b"foo";
+    let _ = broken(&mut buf).to_owned();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn into_box() {
+    let mut buf = *b"foo";
+    let _ = Box::<AsciiBytes>::from(&*broken(&mut buf));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn into_arc() {
+    let mut buf = *b"foo";
+    let _ = Arc::<AsciiBytes>::from(&*broken(&mut buf));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the value should pass the validator")]
+fn into_rc() {
+    let mut buf = *b"foo";
+    let _ = Rc::<AsciiBytes>::from(&*broken(&mut buf));
+}
+
+mod from_inner {
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// ASCII bytes.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(allow_safe_unchecked)]
+        #[custom_slice(derive(FromInner))]
+        pub struct AsciiBuf(Vec<u8>);
+
+        /// ASCII bytes slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(allow_safe_unchecked)]
+        #[custom_slice(derive(FromInner, FromInnerMut))]
+        pub struct AsciiBytes([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(s: &[u8]) -> Result<(), Error> {
+            if s.is_ascii() {
+                Ok(())
+            } else {
+                Err(Error)
+            }
+        }
+    }
+
+    #[test]
+    fn valid() {
+        let _ = AsciiBuf::from(b"foo".to_vec());
+        let _ = <&AsciiBytes>::from(&b"foo"[..]);
+        let _ = <&mut AsciiBytes>::from(&mut b"foo".to_vec()[..]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the value should pass the validator")]
+    fn owned() {
+        let _ = AsciiBuf::from(vec![0xff]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the value should pass the validator")]
+    fn slice() {
+        let _ = <&AsciiBytes>::from(&b"\xff"[..]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the value should pass the validator")]
+    fn slice_mut() {
+        let _ = <&mut AsciiBytes>::from(&mut [0xff][..]);
+    }
+}
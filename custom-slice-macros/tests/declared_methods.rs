@@ -0,0 +1,133 @@
+//! Methods declared as items.
+
+use std::{error, fmt};
+
+/// Error for lower ascii string creation.
+#[derive(Debug, Clone, Copy)]
+pub struct Error(char);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Non-ascii-lowercase character: {:?}", self.0)
+    }
+}
+
+impl error::Error for Error {}
+
+custom_slice_macros::define_slice_types_pair! {
+    /// A string which contains only lower ascii characters.
+    #[custom_slice(owned)]
+    #[custom_slice(error(type = "Error"))]
+    #[custom_slice(get_mut = "unsafe fn get_mut")]
+    pub struct LowerAsciiString(String);
+
+    /// A string which contains only lower ascii characters.
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(error(type = "Error"))]
+    pub struct LowerAsciiStr(str);
+
+    impl LowerAsciiString {
+        /// Creates a new string without validation.
+        #[custom_slice(new_unchecked)]
+        pub unsafe fn new_unchecked(s: String) -> Self;
+
+        /// Creates a new string.
+        #[custom_slice(new_checked)]
+        pub fn new(s: String) -> Result<Self, Error>;
+
+        /// Returns the inner string.
+        #[custom_slice(get_ref)]
+        pub fn as_string(&self) -> &String;
+
+        /// Returns the inner string.
+        #[custom_slice(into_inner)]
+        pub fn into_string(self) -> String;
+    }
+
+    impl LowerAsciiStr {
+        /// Creates a new string slice without validation.
+        #[custom_slice(new_unchecked)]
+        pub unsafe fn new_unchecked<'a>(s: &'a str) -> &'a Self;
+
+        /// Creates a new mutable string slice without validation.
+        #[custom_slice(new_unchecked_mut)]
+        pub unsafe fn new_unchecked_mut(s: &mut str) -> &mut Self;
+
+        /// Creates a new string slice.
+        #[custom_slice(new_checked)]
+        pub fn new(s: &str) -> Result<&Self, Error>;
+
+        /// Creates a new mutable string slice.
+        #[custom_slice(new_checked_mut)]
+        pub fn new_mut(s: &mut str) -> Result<&mut Self, Error>;
+
+        /// Returns the inner string slice.
+        #[custom_slice(get_ref)]
+        pub fn as_str(&self) -> &str;
+
+        /// Returns the inner mutable string slice.
+        #[custom_slice(get_mut)]
+        pub unsafe fn as_mut_str(&mut self) -> &mut str;
+    }
+
+    /// Validates that the given string as `LowerAsciiStr`.
+    #[custom_slice(validator)]
+    fn validate(s: &str) -> Result<(), Error> {
+        match s.chars().find(|c| !c.is_ascii_lowercase()) {
+            Some(c) => Err(Error(c)),
+            None => Ok(()),
+        }
+    }
+}
+
+mod owned {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let s: LowerAsciiString = LowerAsciiString::new("hello".to_owned()).unwrap();
+        assert_eq!(s.as_string(), "hello");
+        assert_eq!(s.into_string(), "hello");
+    }
+
+    #[test]
+    fn new_should_fail() {
+        assert!(LowerAsciiString::new("Hello".to_owned()).is_err());
+    }
+
+    #[test]
+    fn new_unchecked() {
+        let mut s = unsafe { LowerAsciiString::new_unchecked("hello".to_owned()) };
+        let _: &mut String = unsafe { s.get_mut() };
+    }
+}
+
+mod slice {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let s: &LowerAsciiStr = LowerAsciiStr::new("hello").unwrap();
+        assert_eq!(s.as_str(), "hello");
+    }
+
+    #[test]
+    fn new_should_fail() {
+        assert!(LowerAsciiStr::new("Hello").is_err());
+    }
+
+    #[test]
+    fn new_mut() {
+        let mut hello = "hello".to_owned();
+        let s: &mut LowerAsciiStr = LowerAsciiStr::new_mut(&mut hello).unwrap();
+        let _: &mut str = unsafe { s.as_mut_str() };
+    }
+
+    #[test]
+    fn new_unchecked() {
+        let _: &LowerAsciiStr = unsafe { LowerAsciiStr::new_unchecked("hello") };
+        let mut hello = "hello".to_owned();
+        let _: &mut LowerAsciiStr = unsafe { LowerAsciiStr::new_unchecked_mut(&mut hello) };
+    }
+}
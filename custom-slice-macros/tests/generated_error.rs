@@ -0,0 +1,128 @@
+//! Generated error types for owned types.
+
+mod utf8 {
+    use std::{convert::TryFrom, error::Error as _, fmt, str::FromStr};
+
+    /// Error for UTF-8 string slice creation.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Utf8Error {
+        valid_up_to: usize,
+    }
+
+    impl std::error::Error for Utf8Error {}
+
+    impl fmt::Display for Utf8Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Invalid UTF-8 sequence from {}", self.valid_up_to)
+        }
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn from_utf8")]
+        #[custom_slice(derive(FromStr, TryFromInner))]
+        #[custom_slice(error(generate = "FromUtf8Error"))]
+        pub struct Utf8String(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn from_utf8")]
+        #[custom_slice(error(type = "Utf8Error"))]
+        pub struct Utf8Str([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(bytes: &[u8]) -> std::result::Result<(), Utf8Error> {
+            match std::str::from_utf8(bytes) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(Utf8Error {
+                    valid_up_to: e.valid_up_to(),
+                }),
+            }
+        }
+    }
+
+    #[test]
+    fn new_checked() {
+        assert!(Utf8String::from_utf8(b"hello".to_vec()).is_ok());
+
+        let e: FromUtf8Error = Utf8String::from_utf8(b"ab\xffcd".to_vec())
+            .expect_err("Should fail: invalid UTF-8 sequence");
+        assert_eq!(*e.error(), Utf8Error { valid_up_to: 2 });
+        assert_eq!(e.to_string(), "Invalid UTF-8 sequence from 2");
+        assert_eq!(
+            e.source().map(ToString::to_string),
+            Some("Invalid UTF-8 sequence from 2".to_owned())
+        );
+        assert_eq!(e.into_inner(), b"ab\xffcd");
+    }
+
+    #[test]
+    fn try_from_inner() {
+        let e = Utf8String::try_from(b"\xff".to_vec()).expect_err("Should fail: invalid UTF-8");
+        assert_eq!(e.into_inner(), b"\xff");
+    }
+
+    #[test]
+    fn from_str() {
+        let s = Utf8String::from_str("hello").expect("Should never fail: valid UTF-8");
+        assert_eq!(
+            s,
+            Utf8String::from_utf8(b"hello".to_vec()).expect("Should never fail: valid UTF-8")
+        );
+    }
+
+    impl PartialEq for Utf8String {
+        fn eq(&self, other: &Self) -> bool {
+            use std::borrow::Borrow;
+            let lhs: &Utf8Str = self.borrow();
+            let rhs: &Utf8Str = other.borrow();
+            lhs.0 == rhs.0
+        }
+    }
+}
+
+mod with_generated_validator {
+    use std::str::FromStr;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(FromStr))]
+        #[custom_slice(error(generate = "FromStringError"))]
+        pub struct Label(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty, charset = "a-z")))]
+        pub struct LabelStr(str);
+    }
+
+    #[test]
+    fn from_str() {
+        assert!(Label::from_str("foo").is_ok());
+        let e = Label::from_str("foo1").expect_err("Should fail: invalid character");
+        assert_eq!(*e.error(), LabelStrError::InvalidChar { offset: 3 });
+        assert_eq!(e.into_inner(), "foo1");
+    }
+}
+
+mod without_validator {
+    use std::str::FromStr;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(FromStr))]
+        pub struct Name(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct NameStr(str);
+    }
+
+    #[test]
+    fn from_str() {
+        let name: Result<Name, std::convert::Infallible> = Name::from_str("foo");
+        assert_eq!(name.map(|v| v.0), Ok("foo".to_owned()));
+    }
+}
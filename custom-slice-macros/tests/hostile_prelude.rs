@@ -0,0 +1,421 @@
+//! Generated code should not depend on the prelude or on relative paths.
+
+#![no_implicit_prelude]
+#![allow(dead_code, unused_imports)]
+
+/// A module which shadows relative `std::..` paths.
+mod std {}
+
+/// Types which shadow the prelude.
+mod hostile {
+    pub enum Result {
+        Ok,
+        Err,
+    }
+
+    pub struct Option;
+    pub struct Some;
+    pub struct None;
+    pub struct Box;
+    pub struct String;
+    pub struct Vec;
+
+    pub trait Borrow {}
+    pub trait Default {}
+    pub trait From {}
+    pub trait Into {}
+    pub trait AsRef {}
+    pub trait ToOwned {}
+    pub trait PartialEq {}
+    pub trait PartialOrd {}
+}
+
+use self::hostile::Result::{Err, Ok};
+use self::hostile::*;
+
+// `DefaultArc` and `DefaultRc` are not tested here, since `Arc` and `Rc` are
+// not fundamental types and the impls are always rejected by the orphan rule.
+mod without_validator {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn get")]
+        #[custom_slice(get_mut = "pub fn get_mut")]
+        #[custom_slice(into_inner = "pub fn into_inner")]
+        #[custom_slice(derive(
+            AsMutSlice,
+            AsMutSliceInner,
+            AsRefSlice,
+            AsRefSliceInner,
+            BorrowMut,
+            Deref,
+            DerefMut,
+            FromInner,
+            FromStr,
+            IntoInner,
+            PartialEq,
+            PartialEqBulk,
+            PartialEqInnerBulk,
+            PartialOrd,
+            PartialOrdBulk,
+            PartialOrdInnerBulk
+        ))]
+        pub struct Owned(::std::vec::Vec<u8>);
+
+        #[derive(PartialEq, PartialOrd)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(new_unchecked_mut = "pub fn new_mut")]
+        #[custom_slice(get_ref = "pub fn get")]
+        #[custom_slice(get_mut = "pub fn get_mut")]
+        #[custom_slice(derive(
+            AsMutSlice,
+            AsMutSliceInner,
+            AsRefSlice,
+            AsRefSliceInner,
+            DefaultBox,
+            DefaultRef,
+            DefaultRefMut,
+            Deref,
+            DerefMut,
+            FromInner,
+            FromInnerMut,
+            IntoArc,
+            IntoBox,
+            IntoRc,
+            PartialEqBulk,
+            PartialEqInnerBulk,
+            PartialOrdBulk,
+            PartialOrdInnerBulk
+        ))]
+        pub struct Slice([u8]);
+    }
+}
+
+mod with_validator {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    /// Validation error.
+    pub struct Error;
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error"))]
+        #[custom_slice(derive(FromStr, TryFromInner))]
+        pub struct Owned(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(new_checked_mut = "pub fn new_mut")]
+        #[custom_slice(error(type = "Error"))]
+        #[custom_slice(derive(TryFromInner, TryFromInnerMut))]
+        pub struct Slice([u8]);
+
+        impl Owned {
+            #[custom_slice(new_unchecked)]
+            pub unsafe fn new_unchecked(v: ::std::vec::Vec<u8>) -> Self;
+        }
+
+        #[custom_slice(validator)]
+        fn validate(v: &[u8]) -> ::core::result::Result<(), Error> {
+            if v.is_ascii() {
+                ::core::result::Result::Ok(())
+            } else {
+                ::core::result::Result::Err(Error)
+            }
+        }
+    }
+}
+
+mod with_generated_error {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    /// Validation error.
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl ::core::fmt::Display for Error {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.write_str("non-ASCII")
+        }
+    }
+
+    impl ::std::error::Error for Error {}
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(generate = "OwnedError"))]
+        #[custom_slice(derive(FromStr, TryFromInner))]
+        pub struct Owned(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(error(type = "Error"))]
+        pub struct Slice([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(v: &[u8]) -> ::core::result::Result<(), Error> {
+            if v.is_ascii() {
+                ::core::result::Result::Ok(())
+            } else {
+                ::core::result::Result::Err(Error)
+            }
+        }
+    }
+}
+
+mod with_combined_validators {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    /// Validation error.
+    pub enum Error {
+        Empty,
+        NonAscii,
+    }
+
+    /// Non-ASCII error.
+    pub struct NonAscii;
+
+    impl ::core::convert::From<NonAscii> for Error {
+        fn from(_: NonAscii) -> Self {
+            Error::NonAscii
+        }
+    }
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "::std::vec::Vec<Error>"))]
+        pub struct Owned(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validators(error = "Error", collect_all))]
+        pub struct Slice([u8]);
+
+        #[custom_slice(validator(map = "{|()| Error::Empty}"))]
+        fn validate_non_empty(v: &[u8]) -> ::core::result::Result<(), ()> {
+            if v.is_empty() {
+                ::core::result::Result::Err(())
+            } else {
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        #[custom_slice(validator)]
+        fn validate_ascii(v: &[u8]) -> ::core::result::Result<(), NonAscii> {
+            if v.is_ascii() {
+                ::core::result::Result::Ok(())
+            } else {
+                ::core::result::Result::Err(NonAscii)
+            }
+        }
+    }
+}
+
+mod with_normalizer {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    /// Validation error.
+    pub struct Error;
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(from_inner_normalized = "pub fn from_inner_normalized")]
+        pub struct Owned(::std::string::String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(from_inner_normalized = "pub fn from_inner_normalized")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(v: &str) -> ::core::result::Result<(), Error> {
+            if v.is_ascii() {
+                ::core::result::Result::Ok(())
+            } else {
+                ::core::result::Result::Err(Error)
+            }
+        }
+
+        #[custom_slice(normalizer)]
+        fn normalize(v: &str) -> ::std::borrow::Cow<'_, str> {
+            if v.is_ascii() {
+                ::std::borrow::Cow::Borrowed(v)
+            } else {
+                let v = v.replace(|c: char| !c.is_ascii(), "?");
+                ::std::borrow::Cow::Owned(v)
+            }
+        }
+    }
+}
+
+mod with_debug_validate {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    /// Validation error.
+    pub struct Error;
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(new_unchecked = "pub unsafe fn new_unchecked")]
+        #[custom_slice(get_mut = "pub unsafe fn get_mut")]
+        pub struct Owned(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(new_unchecked = "pub unsafe fn new_unchecked")]
+        #[custom_slice(new_unchecked_mut = "pub unsafe fn new_unchecked_mut")]
+        #[custom_slice(get_mut = "pub unsafe fn get_mut")]
+        #[custom_slice(derive(DefaultBox, DefaultRef, IntoArc, IntoBox, IntoRc))]
+        pub struct Slice([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(v: &[u8]) -> ::core::result::Result<(), Error> {
+            if v.is_ascii() {
+                ::core::result::Result::Ok(())
+            } else {
+                ::core::result::Result::Err(Error)
+            }
+        }
+    }
+}
+
+mod with_prefix_scanning {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(into_valid_prefix = "pub fn into_valid_prefix")]
+        pub struct Owned(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "::core::str::Utf8Error::valid_up_to")]
+        #[custom_slice(from_prefix = "pub fn from_prefix")]
+        #[custom_slice(split_valid_prefix = "pub fn split_valid_prefix")]
+        pub struct Slice([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(v: &[u8]) -> ::core::result::Result<(), ::core::str::Utf8Error> {
+            ::core::result::Result::map(::core::str::from_utf8(v), |_| ())
+        }
+    }
+}
+
+mod with_via_derives {
+    use super::hostile::Result::{Err, Ok};
+    use super::hostile::*;
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(
+            AsRefVia(::std::path::Path, [u8]),
+            IntoVia(::std::boxed::Box<str>, ::std::path::PathBuf)
+        ))]
+        pub struct Owned(::std::string::String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(
+            AsRefVia(::std::path::Path, [u8]),
+            IntoVia(::std::string::String, ::std::boxed::Box<str>)
+        ))]
+        pub struct Slice(str);
+    }
+}
+
+#[test]
+fn without_validator() {
+    use self::without_validator::{Owned, Slice};
+
+    let owned = <Owned as ::core::convert::From<::std::vec::Vec<u8>>>::from(::std::vec![1, 2]);
+    let slice: &Slice = ::core::ops::Deref::deref(&owned);
+    ::core::assert!(::core::cmp::PartialEq::eq(slice, &owned));
+    ::core::assert_eq!(slice.get(), &[1, 2]);
+}
+
+#[test]
+fn with_validator() {
+    use self::with_validator::{Owned, Slice};
+
+    ::core::assert!(Owned::new(b"ascii".to_vec()).is_ok());
+    ::core::assert!(Slice::new(&b"\xff"[..]).is_err());
+    let _ = unsafe { Owned::new_unchecked(::std::vec::Vec::new()) };
+}
+
+#[test]
+fn with_generated_error() {
+    use self::with_generated_error::{Owned, OwnedError};
+
+    let e: OwnedError = match Owned::new(b"\xff".to_vec()) {
+        ::core::result::Result::Ok(_) => ::core::panic!("Should fail: non-ASCII"),
+        ::core::result::Result::Err(e) => e,
+    };
+    ::core::assert_eq!(e.into_inner(), b"\xff");
+}
+
+#[test]
+fn with_combined_validators() {
+    use self::with_combined_validators::Owned;
+
+    ::core::assert!(Owned::new(b"ascii".to_vec()).is_ok());
+    match Owned::new(b"\xff".to_vec()) {
+        ::core::result::Result::Ok(_) => ::core::panic!("Should fail: non-ASCII"),
+        ::core::result::Result::Err(e) => ::core::assert_eq!(e.len(), 1),
+    }
+}
+
+#[test]
+fn with_normalizer() {
+    use self::with_normalizer::{Owned, Slice};
+
+    let _ = Slice::from_inner_normalized("\u{3042}");
+    let _ = Owned::from_inner_normalized(::std::string::String::new());
+}
+
+#[test]
+fn with_debug_validate() {
+    use self::with_debug_validate::{Owned, Slice};
+
+    let slice = unsafe { Slice::new_unchecked(b"ascii") };
+    let _ = <Slice as ::std::borrow::ToOwned>::to_owned(slice);
+    let mut owned = unsafe { Owned::new_unchecked(::std::vec::Vec::new()) };
+    let _ = unsafe { owned.get_mut() };
+}
+
+#[test]
+fn with_prefix_scanning() {
+    use self::with_prefix_scanning::{Owned, Slice};
+
+    let _ = Slice::from_prefix(b"ascii\xff");
+    let _ = Slice::split_valid_prefix(b"ascii\xff");
+    let _ = Owned::into_valid_prefix(::std::vec::Vec::new());
+}
+
+#[test]
+fn with_via_derives() {
+    use self::with_via_derives::{Owned, Slice};
+
+    let owned = Owned::new(::std::string::String::new());
+    let _: &::std::path::Path = ::core::convert::AsRef::as_ref(&owned);
+    let _: ::std::boxed::Box<str> = ::core::convert::From::from(owned);
+    let _: ::std::string::String = ::core::convert::From::from(Slice::new("via"));
+}
@@ -0,0 +1,121 @@
+//! Normalizing constructors.
+
+use std::borrow::Cow;
+
+/// Validation error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error;
+
+mod lower_ascii {
+    use std::borrow::Cow;
+
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// ASCII lowercase string.
+        #[derive(Debug, PartialEq)]
+        #[custom_slice(owned)]
+        #[custom_slice(from_inner_normalized = "pub fn from_inner_normalized")]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        pub struct LowerAsciiString(String);
+
+        /// ASCII lowercase string slice.
+        #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        pub struct LowerAsciiStr(str);
+
+        impl LowerAsciiStr {
+            /// Creates a string, converting the characters into lowercase.
+            #[custom_slice(from_inner_normalized)]
+            pub fn from_inner_normalized(s: &str) -> Cow<'_, LowerAsciiStr>;
+        }
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), Error> {
+            if s.bytes().any(|b| b.is_ascii_uppercase()) {
+                return Err(Error);
+            }
+            Ok(())
+        }
+
+        #[custom_slice(normalizer)]
+        fn normalize(s: &str) -> Cow<'_, str> {
+            if s.bytes().any(|b| b.is_ascii_uppercase()) {
+                Cow::Owned(s.to_ascii_lowercase())
+            } else {
+                Cow::Borrowed(s)
+            }
+        }
+    }
+}
+
+mod broken {
+    use std::borrow::Cow;
+
+    use super::Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(from_inner_normalized = "pub fn from_inner_normalized")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(from_inner_normalized = "pub fn from_inner_normalized")]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), Error> {
+            if s.is_empty() {
+                Err(Error)
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Normalizer which does nothing.
+        #[custom_slice(normalizer)]
+        fn normalize(s: &str) -> Cow<'_, str> {
+            Cow::Borrowed(s)
+        }
+    }
+}
+
+#[test]
+fn slice() {
+    use self::lower_ascii::LowerAsciiStr;
+
+    let borrowed = LowerAsciiStr::from_inner_normalized("foo");
+    assert!(matches!(borrowed, Cow::Borrowed(_)));
+    assert_eq!(borrowed.as_str(), "foo");
+
+    let owned = LowerAsciiStr::from_inner_normalized("FoO");
+    assert!(matches!(owned, Cow::Owned(_)));
+    assert_eq!(owned.as_str(), "foo");
+}
+
+#[test]
+fn owned() {
+    use self::lower_ascii::LowerAsciiString;
+
+    let s = LowerAsciiString::from_inner_normalized("foo".to_owned());
+    assert_eq!(s.as_str(), "foo");
+    let s = LowerAsciiString::from_inner_normalized("FoO".to_owned());
+    assert_eq!(s.as_str(), "foo");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the normalizer output should pass the validator")]
+fn invalid_slice_output() {
+    let _ = broken::Slice::from_inner_normalized("");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the normalizer output should pass the validator")]
+fn invalid_owned_output() {
+    let _ = broken::Owned::from_inner_normalized(String::new());
+}
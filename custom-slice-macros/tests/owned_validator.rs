@@ -0,0 +1,124 @@
+//! Validators for owned types.
+
+mod by_ref {
+    use std::convert::TryFrom;
+
+    /// Validation error.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Error {
+        /// Non-ASCII character found.
+        NonAscii,
+        /// Too long for the owned string.
+        TooLong,
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// An ASCII string with at most 8 bytes.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(TryFromInner))]
+        #[custom_slice(error(type = "(Error, String)", map = "{|e, v| (e, v)}"))]
+        pub struct ShortAsciiString(String);
+
+        /// An ASCII string slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error"))]
+        pub struct AsciiStr(str);
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), Error> {
+            if s.is_ascii() {
+                Ok(())
+            } else {
+                Err(Error::NonAscii)
+            }
+        }
+
+        #[allow(clippy::ptr_arg)]
+        #[custom_slice(validator(owned))]
+        fn validate_owned(s: &String) -> Result<(), Error> {
+            validate(s)?;
+            if s.len() > 8 {
+                return Err(Error::TooLong);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn owned() {
+        assert!(ShortAsciiString::new("short".to_owned()).is_ok());
+        assert_eq!(
+            ShortAsciiString::new("too long string".to_owned()).unwrap_err(),
+            (Error::TooLong, "too long string".to_owned())
+        );
+        assert_eq!(
+            ShortAsciiString::try_from("non-ascii é".to_owned()).unwrap_err(),
+            (Error::NonAscii, "non-ascii é".to_owned())
+        );
+    }
+
+    #[test]
+    fn slice() {
+        assert!(AsciiStr::new("too long string").is_ok());
+        assert_eq!(AsciiStr::new("é").unwrap_err(), Error::NonAscii);
+    }
+}
+
+mod by_value {
+    use std::convert::TryFrom;
+
+    /// Error with the rejected value.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Error(Vec<u8>);
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// A NUL-terminated byte string.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_bytes_with_nul")]
+        #[custom_slice(derive(TryFromInner))]
+        #[custom_slice(error(type = "Error"))]
+        pub struct NulTerminated(Vec<u8>);
+
+        /// A byte string slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(FromInner))]
+        pub struct Bytes([u8]);
+
+        #[custom_slice(validator(owned))]
+        fn validate_owned(mut v: Vec<u8>) -> Result<Vec<u8>, Error> {
+            match v.iter().position(|&b| b == 0) {
+                None => {
+                    v.push(0);
+                    Ok(v)
+                }
+                Some(pos) if pos == v.len() - 1 => Ok(v),
+                Some(_) => Err(Error(v)),
+            }
+        }
+    }
+
+    #[test]
+    fn owned() {
+        let v = NulTerminated::new(b"foo".to_vec()).expect("Should never fail: no NUL");
+        assert_eq!(v.as_bytes_with_nul(), b"foo\0");
+        let v = NulTerminated::try_from(b"foo\0".to_vec()).expect("Should never fail: no NUL");
+        assert_eq!(v.as_bytes_with_nul(), b"foo\0");
+        assert_eq!(
+            NulTerminated::new(b"f\0o".to_vec()).unwrap_err(),
+            Error(b"f\0o".to_vec())
+        );
+    }
+
+    #[test]
+    fn slice() {
+        let _: &Bytes = <&Bytes>::from(&b"f\0o"[..]);
+    }
+}
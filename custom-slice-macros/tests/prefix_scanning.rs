@@ -0,0 +1,167 @@
+//! Prefix scanning constructors.
+
+mod utf8 {
+    use std::str::Utf8Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// UTF-8 string.
+        #[derive(Debug, PartialEq)]
+        #[custom_slice(owned)]
+        #[custom_slice(into_valid_prefix = "pub fn into_valid_prefix")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        pub struct Utf8Buf(Vec<u8>);
+
+        /// UTF-8 string slice.
+        #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "Utf8Error::valid_up_to")]
+        #[custom_slice(split_valid_prefix = "pub fn split_valid_prefix")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        pub struct Utf8Bytes([u8]);
+
+        impl Utf8Bytes {
+            /// Returns the longest valid prefix and the rest.
+            #[custom_slice(from_prefix)]
+            pub fn from_prefix(v: &[u8]) -> Option<(&Utf8Bytes, &[u8])>;
+        }
+
+        #[custom_slice(validator)]
+        fn validate(s: &[u8]) -> Result<(), Utf8Error> {
+            std::str::from_utf8(s).map(|_| ())
+        }
+    }
+}
+
+mod non_empty {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(into_valid_prefix = "pub fn into_valid_prefix")]
+        pub struct Owned(String);
+
+        #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "|e: &usize| *e")]
+        #[custom_slice(from_prefix = "pub fn from_prefix")]
+        #[custom_slice(split_valid_prefix = "pub fn split_valid_prefix")]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        pub struct Slice(str);
+
+        /// Accepts non-empty strings without whitespaces.
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), usize> {
+            match s.find(char::is_whitespace) {
+                _ if s.is_empty() => Err(0),
+                Some(pos) => Err(pos),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+mod short {
+    use std::str::Utf8Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(into_valid_prefix = "pub fn into_valid_prefix")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        pub struct Owned(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(valid_up_to = "Utf8Error::valid_up_to")]
+        pub struct Slice([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(s: &[u8]) -> Result<(), Utf8Error> {
+            std::str::from_utf8(s).map(|_| ())
+        }
+
+        /// Accepts UTF-8 strings up to 3 bytes.
+        #[custom_slice(validator(owned))]
+        #[allow(clippy::ptr_arg)]
+        fn validate_owned(s: &Vec<u8>) -> Result<(), ()> {
+            match std::str::from_utf8(s) {
+                Ok(_) if s.len() <= 3 => Ok(()),
+                _ => Err(()),
+            }
+        }
+    }
+}
+
+#[test]
+fn from_prefix() {
+    use self::utf8::Utf8Bytes;
+
+    let (prefix, rest) = Utf8Bytes::from_prefix(b"foo\xffbar").expect("Should succeed");
+    assert_eq!(prefix.as_bytes(), b"foo");
+    assert_eq!(rest, b"\xffbar");
+
+    let (prefix, rest) = Utf8Bytes::from_prefix(b"foo").expect("Should succeed");
+    assert_eq!(prefix.as_bytes(), b"foo");
+    assert!(rest.is_empty());
+
+    let (prefix, rest) = Utf8Bytes::from_prefix(b"\xff").expect("Should succeed");
+    assert!(prefix.as_bytes().is_empty());
+    assert_eq!(rest, b"\xff");
+}
+
+#[test]
+fn split_valid_prefix() {
+    use self::utf8::Utf8Bytes;
+
+    let input = b"\xe3\x81\x82\xe3\x81";
+    let (prefix, rest) = Utf8Bytes::split_valid_prefix(input);
+    assert_eq!(prefix.as_bytes(), b"\xe3\x81\x82");
+    assert_eq!(rest, b"\xe3\x81");
+    // The rest points to the end of the prefix.
+    assert_eq!(rest.as_ptr(), input[3..].as_ptr());
+}
+
+#[test]
+fn into_valid_prefix() {
+    use self::utf8::Utf8Buf;
+
+    let buf = Utf8Buf::into_valid_prefix(b"foo\xffbar".to_vec());
+    assert_eq!(buf.as_bytes(), b"foo");
+    let buf = Utf8Buf::into_valid_prefix(b"foo".to_vec());
+    assert_eq!(buf.as_bytes(), b"foo");
+}
+
+#[test]
+fn invalid_prefix() {
+    use self::non_empty::Slice;
+
+    let (prefix, rest) = Slice::split_valid_prefix("foo bar");
+    assert_eq!(prefix.as_str(), "foo");
+    assert_eq!(rest, " bar");
+
+    // The empty prefix does not pass the validator.
+    assert_eq!(Slice::from_prefix(" foo"), None);
+}
+
+#[test]
+#[should_panic(expected = "the valid prefix should pass the validator")]
+fn split_invalid_prefix() {
+    let _ = self::non_empty::Slice::split_valid_prefix(" foo");
+}
+
+#[test]
+#[should_panic(expected = "the valid prefix should pass the validator")]
+fn into_invalid_prefix() {
+    let _ = self::non_empty::Owned::into_valid_prefix(" foo".to_owned());
+}
+
+#[test]
+fn into_valid_prefix_owned_validator() {
+    let buf = self::short::Owned::into_valid_prefix(b"foo\xffbar".to_vec());
+    assert_eq!(buf.as_bytes(), b"foo");
+}
+
+#[test]
+#[should_panic(expected = "the valid prefix should pass the validator")]
+fn into_invalid_prefix_owned_validator() {
+    let _ = self::short::Owned::into_valid_prefix(b"foobar".to_vec());
+}
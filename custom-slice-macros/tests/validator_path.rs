@@ -0,0 +1,85 @@
+//! Validators defined outside the macro input, and items passed through.
+
+use std::fmt;
+
+/// Shared validators.
+mod ident {
+    /// Identifier error.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Error;
+
+    /// Checks whether the string is a lowercase identifier.
+    pub fn validate(s: &str) -> Result<(), Error> {
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_lowercase() => {}
+            _ => return Err(Error),
+        }
+        if chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+            Ok(())
+        } else {
+            Err(Error)
+        }
+    }
+}
+
+custom_slice_macros::define_slice_types_pair! {
+    /// Owned identifier.
+    #[derive(Debug)]
+    #[custom_slice(owned)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(derive(TryFromInner, Deref))]
+    #[custom_slice(error(type = "ident::Error", map = "{|e, _v| e}"))]
+    pub struct Ident(String);
+
+    /// Identifier slice.
+    #[derive(Debug)]
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(get_ref = "pub fn as_str")]
+    #[custom_slice(error(type = "ident::Error"))]
+    #[custom_slice(validator = "crate::ident::validate")]
+    pub struct IdentStr(str);
+
+    /// Maximum length of identifiers for display.
+    const MAX_DISPLAY_LEN: usize = 8;
+
+    impl IdentStr {
+        /// Returns the length.
+        pub fn len(&self) -> usize {
+            self.as_str().len()
+        }
+
+        /// Returns whether the identifier is empty (always false).
+        pub fn is_empty(&self) -> bool {
+            false
+        }
+    }
+
+    impl fmt::Display for IdentStr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(truncate(self.as_str()))
+        }
+    }
+
+    /// Truncates the string for display.
+    fn truncate(s: &str) -> &str {
+        &s[..s.len().min(MAX_DISPLAY_LEN)]
+    }
+}
+
+#[test]
+fn validator_path() {
+    assert!(IdentStr::new("foo_1").is_ok());
+    assert_eq!(IdentStr::new("1foo").unwrap_err(), ident::Error);
+    assert_eq!(Ident::new("Foo".to_owned()).unwrap_err(), ident::Error);
+    assert!(<Ident as std::convert::TryFrom<String>>::try_from("foo".to_owned()).is_ok());
+}
+
+#[test]
+fn passed_through_items() {
+    let ident = Ident::new("long_identifier".to_owned()).expect("Should never fail: valid");
+    assert_eq!(ident.len(), 15);
+    assert_eq!(ident.to_string(), "long_ide");
+}
@@ -0,0 +1,111 @@
+//! Pass-through `AsRefVia` and `IntoVia` derive targets.
+
+mod file_name {
+    use std::{
+        ffi::{OsStr, OsString},
+        path::{Path, PathBuf},
+    };
+
+    /// Validation error.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Error;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// A file name.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error"))]
+        #[custom_slice(derive(
+            AsRefVia(Path, OsStr, [u8]),
+            IntoVia(PathBuf, OsString, Box<str>, std::rc::Rc<str>)
+        ))]
+        pub struct FileName(String);
+
+        /// A file name slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "Error"))]
+        #[custom_slice(derive(AsRefVia(Path, [u8]), IntoVia(String, PathBuf, Box<str>)))]
+        pub struct FileNameStr(str);
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), Error> {
+            if s.is_empty() || s.contains('/') {
+                Err(Error)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn as_path(v: impl AsRef<Path>) -> PathBuf {
+        v.as_ref().to_owned()
+    }
+
+    #[test]
+    fn owned() {
+        let name = FileName::new("foo.txt".to_owned()).expect("Should succeed");
+        assert_eq!(as_path(&name), PathBuf::from("foo.txt"));
+        assert_eq!(AsRef::<OsStr>::as_ref(&name), "foo.txt");
+        assert_eq!(AsRef::<[u8]>::as_ref(&name), b"foo.txt");
+
+        let path: PathBuf = FileName::new("foo.txt".to_owned())
+            .expect("Should succeed")
+            .into();
+        assert_eq!(path, Path::new("foo.txt"));
+        let os_string: OsString = FileName::new("foo.txt".to_owned())
+            .expect("Should succeed")
+            .into();
+        assert_eq!(os_string, "foo.txt");
+        let boxed: Box<str> = name.into();
+        assert_eq!(&*boxed, "foo.txt");
+        let rc: std::rc::Rc<str> = FileName::new("foo.txt".to_owned())
+            .expect("Should succeed")
+            .into();
+        assert_eq!(&*rc, "foo.txt");
+    }
+
+    #[test]
+    fn slice() {
+        let name = FileNameStr::new("foo.txt").expect("Should succeed");
+        assert_eq!(as_path(name), PathBuf::from("foo.txt"));
+        assert_eq!(AsRef::<[u8]>::as_ref(name), b"foo.txt");
+
+        assert_eq!(String::from(name), "foo.txt");
+        assert_eq!(PathBuf::from(name), Path::new("foo.txt"));
+        assert_eq!(&*Box::<str>::from(name), "foo.txt");
+
+        FileNameStr::new("foo/bar").expect_err("Should fail");
+    }
+}
+
+mod bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Bytes.
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(IntoInner, IntoVia(Box<[u8]>, std::rc::Rc<[u8]>)))]
+        pub struct Bytes(Vec<u8>);
+
+        /// Bytes slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(AsRefSliceInner, IntoVia(Vec<u8>)))]
+        pub struct ByteSlice([u8]);
+    }
+
+    #[test]
+    fn bytes() {
+        let v: Vec<u8> = Bytes::new(vec![1, 2]).into();
+        assert_eq!(v, [1, 2]);
+        let v: Box<[u8]> = Bytes::new(vec![1, 2]).into();
+        assert_eq!(&*v, [1, 2]);
+        let v: std::rc::Rc<[u8]> = Bytes::new(vec![1, 2]).into();
+        assert_eq!(&*v, [1, 2]);
+        assert_eq!(Vec::from(ByteSlice::new(&[1, 2])), [1, 2]);
+    }
+}
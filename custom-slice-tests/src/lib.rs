@@ -0,0 +1,4 @@
+//! Tests for optional features of `custom-slice-macros`, and for
+//! `custom-slice`.
+//!
+//! Tests are in `tests/` directory.
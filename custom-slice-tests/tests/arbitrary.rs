@@ -0,0 +1,145 @@
+//! `Arbitrary` derive target for proptest and quickcheck.
+
+use proptest::{
+    arbitrary::any,
+    prop_assert, proptest,
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+mod lower {
+    use proptest::strategy::Strategy;
+    use quickcheck::{Arbitrary, Gen};
+
+    /// Strategy for lowercase strings.
+    fn lower_strategy() -> impl Strategy<Value = String> {
+        "[a-z]{1,8}"
+    }
+
+    /// Generates a lowercase string.
+    fn gen_lower(g: &mut Gen) -> String {
+        let len = usize::arbitrary(g) % 8 + 1;
+        (0..len)
+            .map(|_| char::from(*g.choose(b"abcdefghijklmnopqrstuvwxyz").unwrap()))
+            .collect()
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// Lowercase string.
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(derive(Arbitrary))]
+        #[custom_slice(arbitrary(proptest = "lower_strategy()", quickcheck = "gen_lower"))]
+        pub struct LowerString(String);
+
+        /// Lowercase string slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, max_len = 8, ascii_lowercase)))]
+        pub struct LowerStr(str);
+    }
+}
+
+mod non_empty {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Non-empty string.
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(derive(Arbitrary))]
+        pub struct NonEmptyString(String);
+
+        /// Non-empty string slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        pub struct NonEmptyStr(str);
+    }
+}
+
+mod never {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(Arbitrary))]
+        #[custom_slice(arbitrary(quickcheck, max_retries = 3))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        /// Rejects all values.
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), ()> {
+            Err(())
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn proptest_custom_strategy(s in any::<lower::LowerString>()) {
+        prop_assert!(lower::LowerStr::new(s.as_str()).is_ok());
+    }
+
+    #[test]
+    fn proptest_default_strategy(s in any::<non_empty::NonEmptyString>()) {
+        prop_assert!(!s.as_str().is_empty());
+    }
+}
+
+#[test]
+fn proptest_shrink() {
+    use self::non_empty::NonEmptyString;
+
+    let mut runner = TestRunner::deterministic();
+    let mut tree = any::<NonEmptyString>()
+        .new_tree(&mut runner)
+        .expect("Should succeed");
+    while tree.simplify() {
+        assert!(!tree.current().as_str().is_empty());
+    }
+    // Shrinks toward the shortest valid value.
+    assert_eq!(tree.current().as_str().chars().count(), 1);
+}
+
+#[test]
+fn quickcheck_custom_generator() {
+    use self::lower::{LowerStr, LowerString};
+
+    fn prop(s: LowerString) -> bool {
+        LowerStr::new(s.as_str()).is_ok()
+    }
+    QuickCheck::new().quickcheck(prop as fn(LowerString) -> bool);
+}
+
+#[test]
+fn quickcheck_default_generator() {
+    use self::non_empty::NonEmptyString;
+
+    fn prop(s: NonEmptyString) -> bool {
+        !s.as_str().is_empty()
+    }
+    QuickCheck::new().quickcheck(prop as fn(NonEmptyString) -> bool);
+}
+
+#[test]
+fn quickcheck_shrink() {
+    use self::lower::{LowerStr, LowerString};
+
+    let mut g = Gen::new(8);
+    let s = LowerString::arbitrary(&mut g);
+    for candidate in s.shrink() {
+        assert!(LowerStr::new(candidate.as_str()).is_ok());
+    }
+}
+
+#[test]
+#[should_panic(expected = "failed to generate a value passing the validator in 3 attempts")]
+fn quickcheck_max_retries() {
+    let _ = never::Owned::arbitrary(&mut Gen::new(8));
+}
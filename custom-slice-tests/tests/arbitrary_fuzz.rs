@@ -0,0 +1,166 @@
+//! `ArbitraryFuzz` derive target for the `arbitrary` crate.
+
+use arbitrary::{Arbitrary, Unstructured};
+
+mod lower {
+    use std::borrow::Cow;
+
+    /// Returns the longest lowercase prefix.
+    fn lower_prefix(s: &str) -> &str {
+        let end = s.find(|c: char| !c.is_ascii_lowercase()).unwrap_or(s.len());
+        &s[..end]
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// Lowercase string.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct LowerString(String);
+
+        /// Lowercase string slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(validator(all(ascii_lowercase)))]
+        #[custom_slice(fuzz_repair = "lower_prefix")]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct LowerStr(str);
+
+        /// Removes non-alphabetic characters and converts to lowercase.
+        #[custom_slice(normalizer)]
+        fn normalize(s: &str) -> Cow<'_, str> {
+            if s.chars().all(|c| c.is_ascii_lowercase()) {
+                Cow::Borrowed(s)
+            } else {
+                Cow::Owned(
+                    s.chars()
+                        .filter(char::is_ascii_alphabetic)
+                        .map(|c| c.to_ascii_lowercase())
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+mod non_empty {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct Owned(String);
+
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(validator(all(non_empty)))]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct Slice(str);
+    }
+}
+
+mod digits {
+    /// Removes non-digit characters, and pads to be non-empty.
+    fn repair(s: String) -> String {
+        let digits: String = s.chars().filter(char::is_ascii_digit).collect();
+        if digits.is_empty() {
+            "0".to_owned()
+        } else {
+            digits
+        }
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(fuzz_repair = "repair")]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct Digits(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty, charset = "0-9")))]
+        pub struct DigitsStr(str);
+    }
+}
+
+mod broken_repair {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(fuzz_repair = "std::convert::identity")]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        pub struct Slice(str);
+    }
+}
+
+#[test]
+fn owned_normalizer() {
+    use self::lower::LowerString;
+
+    let s =
+        LowerString::arbitrary_take_rest(Unstructured::new(b"Foo-Bar")).expect("Should succeed");
+    assert_eq!(s.as_str(), "foobar");
+}
+
+#[test]
+fn owned_repair() {
+    use self::digits::Digits;
+
+    let s = Digits::arbitrary_take_rest(Unstructured::new(b"a1b2")).expect("Should succeed");
+    assert_eq!(s.as_str(), "12");
+    let s = Digits::arbitrary_take_rest(Unstructured::new(b"")).expect("Should succeed");
+    assert_eq!(s.as_str(), "0");
+}
+
+#[test]
+fn slice_repair() {
+    use self::lower::LowerStr;
+
+    let s =
+        <&LowerStr>::arbitrary_take_rest(Unstructured::new(b"foo1bar")).expect("Should succeed");
+    assert_eq!(s.as_str(), "foo");
+}
+
+#[test]
+fn rejected() {
+    let e = non_empty::Owned::arbitrary_take_rest(Unstructured::new(b"")).expect_err("Should fail");
+    assert_eq!(e, arbitrary::Error::IncorrectFormat);
+    let e =
+        <&non_empty::Slice>::arbitrary_take_rest(Unstructured::new(b"")).expect_err("Should fail");
+    assert_eq!(e, arbitrary::Error::IncorrectFormat);
+
+    let s = <&non_empty::Slice>::arbitrary_take_rest(Unstructured::new(b"foo"))
+        .expect("Should succeed");
+    assert_eq!(s.as_str(), "foo");
+}
+
+#[test]
+fn from_raw_bytes() {
+    use self::lower::{LowerStr, LowerString};
+
+    let data = (0..=255).cycle().take(4096).collect::<Vec<u8>>();
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let s = LowerString::arbitrary(&mut u).expect("Should succeed");
+        assert!(s.as_str().bytes().all(|b| b.is_ascii_lowercase()));
+        let s = <&LowerStr>::arbitrary(&mut u).expect("Should succeed");
+        assert!(s.as_str().bytes().all(|b| b.is_ascii_lowercase()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "the repaired value should pass the validator")]
+fn broken_repair() {
+    let _ = broken_repair::Owned::arbitrary_take_rest(Unstructured::new(b""));
+}
@@ -0,0 +1,131 @@
+//! `Encode`, `Decode`, and `BorrowDecode` derive targets for `bincode`.
+
+use bincode::{config, error::DecodeError};
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(Encode, Decode, BorrowDecode))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, charset = "a-z_")))]
+        #[custom_slice(derive(Encode, BorrowDecode))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(Encode, Decode, BorrowDecode))]
+        pub struct Bytes(Vec<u8>);
+
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(Encode, BorrowDecode))]
+        pub struct BytesSlice([u8]);
+    }
+}
+
+/// A struct with custom slice fields.
+#[derive(Debug, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+struct Record {
+    name: ident::Identifier,
+    payload: bytes::Bytes,
+}
+
+#[test]
+fn roundtrip() {
+    use self::ident::{IdentStr, Identifier};
+
+    let ident = Identifier::new("foo_bar".to_owned()).expect("Should succeed");
+    let encoded = bincode::encode_to_vec(&ident, config::standard()).expect("Should succeed");
+    assert_eq!(
+        encoded,
+        bincode::encode_to_vec("foo_bar", config::standard()).expect("Should succeed")
+    );
+    let slice = IdentStr::new("foo_bar").expect("Should succeed");
+    assert_eq!(
+        bincode::encode_to_vec(slice, config::standard()).expect("Should succeed"),
+        encoded
+    );
+
+    let (decoded, _): (Identifier, _) =
+        bincode::decode_from_slice(&encoded, config::standard()).expect("Should succeed");
+    assert_eq!(decoded, ident);
+    let (decoded, _): (Identifier, _) =
+        bincode::borrow_decode_from_slice(&encoded, config::standard()).expect("Should succeed");
+    assert_eq!(decoded, ident);
+    let (decoded, _): (&IdentStr, _) =
+        bincode::borrow_decode_from_slice(&encoded, config::standard()).expect("Should succeed");
+    assert_eq!(decoded, slice);
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    use self::ident::{IdentStr, Identifier};
+
+    let encoded = bincode::encode_to_vec("foo-bar", config::standard()).expect("Should succeed");
+    let message = "character not in `a-z_` at byte offset 3";
+
+    let e = bincode::decode_from_slice::<Identifier, _>(&encoded, config::standard())
+        .expect_err("Should fail");
+    assert!(
+        matches!(&e, DecodeError::OtherString(s) if s == message),
+        "{:?}",
+        e
+    );
+    let e = bincode::borrow_decode_from_slice::<Identifier, _>(&encoded, config::standard())
+        .expect_err("Should fail");
+    assert!(
+        matches!(&e, DecodeError::OtherString(s) if s == message),
+        "{:?}",
+        e
+    );
+    let e = bincode::borrow_decode_from_slice::<&IdentStr, _>(&encoded, config::standard())
+        .expect_err("Should fail");
+    assert!(
+        matches!(&e, DecodeError::OtherString(s) if s == message),
+        "{:?}",
+        e
+    );
+}
+
+#[test]
+fn nested() {
+    use self::bytes::{Bytes, BytesSlice};
+    use self::ident::Identifier;
+
+    let record = Record {
+        name: Identifier::new("foo".to_owned()).expect("Should succeed"),
+        payload: Bytes::new(vec![0, 1, 255]),
+    };
+    let encoded = bincode::encode_to_vec(&record, config::standard()).expect("Should succeed");
+    let (decoded, _): (Record, _) =
+        bincode::decode_from_slice(&encoded, config::standard()).expect("Should succeed");
+    assert_eq!(decoded, record);
+
+    let encoded =
+        bincode::encode_to_vec(&[0_u8, 1, 255][..], config::standard()).expect("Should succeed");
+    let (decoded, _): (&BytesSlice, _) =
+        bincode::borrow_decode_from_slice(&encoded, config::standard()).expect("Should succeed");
+    assert_eq!(decoded, BytesSlice::new(&[0, 1, 255]));
+
+    let encoded =
+        bincode::encode_to_vec(("", vec![0_u8]), config::standard()).expect("Should succeed");
+    bincode::decode_from_slice::<Record, _>(&encoded, config::standard()).expect_err("Should fail");
+}
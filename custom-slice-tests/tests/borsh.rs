@@ -0,0 +1,82 @@
+//! `BorshSerialize` and `BorshDeserialize` derive targets for `borsh`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(BorshSerialize, BorshDeserialize))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, charset = "a-z_")))]
+        #[custom_slice(derive(BorshSerialize))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(BorshSerialize, BorshDeserialize))]
+        pub struct Bytes(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct BytesSlice([u8]);
+    }
+}
+
+#[test]
+fn roundtrip() {
+    use self::ident::{IdentStr, Identifier};
+
+    let ident = Identifier::new("foo_bar".to_owned()).expect("Should succeed");
+    let encoded = borsh::to_vec(&ident).expect("Should succeed");
+    assert_eq!(encoded, borsh::to_vec("foo_bar").expect("Should succeed"));
+    let slice = IdentStr::new("foo_bar").expect("Should succeed");
+    assert_eq!(borsh::to_vec(slice).expect("Should succeed"), encoded);
+
+    let decoded = Identifier::try_from_slice(&encoded).expect("Should succeed");
+    assert_eq!(decoded, ident);
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    use self::ident::{IdentStrError, Identifier};
+
+    let encoded = borsh::to_vec("foo-bar").expect("Should succeed");
+    let e = Identifier::try_from_slice(&encoded).expect_err("Should fail");
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    let inner = e
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<IdentStrError>())
+        .expect("Should be the validator error");
+    assert_eq!(inner.offset(), 3);
+
+    let encoded = borsh::to_vec("").expect("Should succeed");
+    Identifier::try_from_slice(&encoded).expect_err("Should fail");
+}
+
+#[test]
+fn without_validator() {
+    use self::bytes::Bytes;
+
+    let v = Bytes::new(vec![0, 1, 255]);
+    let mut encoded = Vec::new();
+    v.serialize(&mut encoded).expect("Should succeed");
+    assert_eq!(
+        Bytes::deserialize(&mut encoded.as_slice()).expect("Should succeed"),
+        v
+    );
+}
@@ -0,0 +1,155 @@
+//! `AsBytes`, `FromBytes`, and `TransparentWrapper` derive targets.
+
+use bytemuck::TransparentWrapper;
+use custom_slice::{AsBytes, CastError, FromBytes};
+
+mod bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct ByteVec(Vec<u8>);
+
+        /// Byte slice.
+        #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(AsBytes, FromBytes, TransparentWrapper))]
+        pub struct Bytes([u8]);
+    }
+}
+
+mod records {
+    use bytemuck::{Pod, Zeroable};
+
+    /// Record.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    #[repr(C)]
+    pub struct Record {
+        pub id: u32,
+        pub value: u32,
+    }
+
+    /// Error of unsorted records.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct UnsortedError {
+        /// Index of the first out-of-order record.
+        pub index: usize,
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct RecordVec(Vec<Record>);
+
+        /// Records sorted by the ID.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_records")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "UnsortedError"))]
+        #[custom_slice(derive(AsBytes, FromBytes))]
+        pub struct Records([Record]);
+
+        /// Validates the records.
+        #[custom_slice(validator)]
+        fn validate(s: &[Record]) -> Result<(), UnsortedError> {
+            match s.windows(2).position(|w| w[0].id > w[1].id) {
+                Some(pos) => Err(UnsortedError { index: pos + 1 }),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+mod wrapper {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(Vec<u16>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_slice")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "()"))]
+        #[custom_slice(allow_safe_unchecked)]
+        #[custom_slice(derive(TransparentWrapper))]
+        pub struct Slice([u16]);
+
+        /// Rejects empty slices.
+        #[custom_slice(validator)]
+        fn validate(s: &[u16]) -> Result<(), ()> {
+            if s.is_empty() {
+                Err(())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+#[test]
+fn transparent_wrapper() {
+    use self::bytes::Bytes;
+
+    let s = Bytes::wrap_ref(b"foo");
+    assert_eq!(s, Bytes::from_bytes(b"foo").expect("Should never fail"));
+    assert_eq!(Bytes::peel_ref(s), b"foo");
+
+    let mut buf = *b"foo";
+    Bytes::peel_mut(Bytes::wrap_mut(&mut buf[..]))[0] = b'b';
+    assert_eq!(&buf, b"boo");
+
+    // `allow_safe_unchecked` allows wrapping values without validation.
+    assert!(wrapper::Slice::new(&[]).is_err());
+    let s = wrapper::Slice::wrap_ref(&[][..]);
+    assert_eq!(s.as_slice(), &[]);
+}
+
+#[test]
+fn unvalidated_bytes() {
+    use self::bytes::Bytes;
+
+    let s = Bytes::from_bytes(b"").expect("Should never fail");
+    assert_eq!(s.as_bytes(), b"");
+    let s = Bytes::from_bytes(b"foo").expect("Should never fail");
+    assert_eq!(s.as_bytes(), b"foo");
+}
+
+#[test]
+fn records_roundtrip() {
+    use self::records::{Record, Records};
+
+    let raw = [
+        Record { id: 1, value: 10 },
+        Record { id: 2, value: 20 },
+        Record { id: 4, value: 40 },
+    ];
+    let records = Records::new(&raw).expect("Should succeed");
+    let bytes = records.as_bytes();
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(bytes, bytemuck::cast_slice::<Record, u8>(&raw));
+
+    let casted = Records::from_bytes(bytes).expect("Should succeed");
+    assert_eq!(casted.as_records(), &raw);
+}
+
+#[test]
+fn records_rejected() {
+    use self::records::{Record, Records, UnsortedError};
+
+    let raw = [Record { id: 2, value: 20 }, Record { id: 1, value: 10 }];
+    let e = Records::from_bytes(bytemuck::cast_slice(&raw)).expect_err("Should fail");
+    assert_eq!(e, CastError::Invalid(UnsortedError { index: 1 }));
+}
+
+#[test]
+fn records_cast_error() {
+    use self::records::{Record, Records};
+
+    let raw = [Record { id: 1, value: 10 }, Record { id: 2, value: 20 }];
+    let bytes: &[u8] = bytemuck::cast_slice(&raw);
+
+    let e = Records::from_bytes(&bytes[..12]).expect_err("Should fail");
+    assert_eq!(e, CastError::InvalidLength);
+    let e = Records::from_bytes(&bytes[1..9]).expect_err("Should fail");
+    assert_eq!(e, CastError::Misaligned);
+}
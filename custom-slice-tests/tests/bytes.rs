@@ -0,0 +1,175 @@
+//! `bytes::Bytes` and `bytes::BytesMut` as the owned inner type.
+
+use std::borrow::Borrow;
+
+use bytes::{Bytes, BytesMut};
+
+mod text {
+    custom_slice_macros::define_slice_types_pair! {
+        /// ASCII text.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_inner")]
+        #[custom_slice(slice_ref = "pub fn slice_ref")]
+        #[custom_slice(slice_range = "pub fn slice")]
+        #[custom_slice(subslice_closed)]
+        #[custom_slice(derive(Deref))]
+        pub struct Text(bytes::Bytes);
+
+        /// ASCII text slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        #[custom_slice(validator(all(ascii)))]
+        pub struct TextSlice([u8]);
+    }
+}
+
+mod token {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Token.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_inner")]
+        #[custom_slice(error(type = "TokenSliceError"))]
+        #[custom_slice(derive(Deref))]
+        pub struct Token(bytes::Bytes);
+
+        /// Token slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        #[custom_slice(validator(all(non_empty, ascii)))]
+        pub struct TokenSlice([u8]);
+
+        impl Token {
+            #[custom_slice(slice_ref)]
+            pub fn slice_ref(&self, subset: &TokenSlice) -> Self;
+
+            #[custom_slice(slice_range)]
+            pub fn slice(&self, range: std::ops::Range<usize>) -> Result<Self, TokenSliceError>;
+        }
+    }
+}
+
+mod word {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Non-empty word, wrongly declared as closed under subslicing.
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(slice_range = "pub fn slice")]
+        #[custom_slice(subslice_closed)]
+        pub struct Word(bytes::Bytes);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        pub struct WordSlice([u8]);
+    }
+}
+
+mod buf {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_inner")]
+        #[custom_slice(derive(Deref, DerefMut, BorrowMut))]
+        pub struct Buf(bytes::BytesMut);
+
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(get_mut = "pub fn as_bytes_mut")]
+        pub struct BufSlice([u8]);
+    }
+}
+
+#[test]
+fn to_owned_and_borrow() {
+    use self::text::{Text, TextSlice};
+
+    let text = Text::new(Bytes::from_static(b"hello")).expect("Should succeed");
+    let slice: &TextSlice = text.borrow();
+    assert_eq!(slice.as_bytes(), b"hello");
+    assert_eq!(&*text, slice);
+
+    let owned: Text = slice.to_owned();
+    assert_eq!(owned, text);
+    // `to_owned` copies the bytes.
+    assert_ne!(owned.as_inner().as_ptr(), text.as_inner().as_ptr());
+}
+
+#[test]
+fn closed_subslice() {
+    use self::text::{Text, TextSlice};
+
+    let text = Text::new(Bytes::from_static(b"hello, world")).expect("Should succeed");
+    let sub = TextSlice::new(&text.as_bytes()[7..]).expect("Should succeed");
+    let world: Text = text.slice_ref(sub);
+    assert_eq!(world.as_bytes(), b"world");
+    assert_eq!(world.as_inner().as_ptr(), sub.as_bytes().as_ptr());
+
+    let hello: Text = text.slice(..5);
+    assert_eq!(hello.as_bytes(), b"hello");
+    assert_eq!(hello.as_inner().as_ptr(), text.as_inner().as_ptr());
+    let empty: Text = text.slice(3..3);
+    assert!(empty.as_bytes().is_empty());
+}
+
+#[test]
+fn checked_subslice() {
+    use self::token::{Token, TokenSlice, TokenSliceError};
+
+    let token = Token::new(Bytes::from_static(b"foo bar")).expect("Should succeed");
+    let sub = TokenSlice::new(&token.as_inner()[4..]).expect("Should succeed");
+    let bar: Token = token.slice_ref(sub);
+    assert_eq!(&*bar, TokenSlice::new(b"bar").expect("Should succeed"));
+    assert_eq!(bar.as_inner().as_ptr(), sub.as_bytes().as_ptr());
+
+    let foo = token.slice(0..3).expect("Should succeed");
+    assert_eq!(&*foo, TokenSlice::new(b"foo").expect("Should succeed"));
+    assert_eq!(foo.as_inner().as_ptr(), token.as_inner().as_ptr());
+    let e = token.slice(3..3).expect_err("Should fail");
+    assert!(matches!(e, TokenSliceError::Empty), "{:?}", e);
+}
+
+#[test]
+#[should_panic]
+fn slice_ref_of_unrelated_slice() {
+    use self::token::{Token, TokenSlice};
+
+    let token = Token::new(Bytes::from_static(b"foo")).expect("Should succeed");
+    let other = TokenSlice::new(b"bar").expect("Should succeed");
+    let _ = token.slice_ref(other);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "`subslice_closed` is specified")]
+fn wrongly_closed_subslice() {
+    use self::word::Word;
+
+    let word = Word::new(Bytes::from_static(b"foo")).expect("Should succeed");
+    let _ = word.slice(0..0);
+}
+
+#[test]
+fn bytes_mut() {
+    use self::buf::{Buf, BufSlice};
+
+    let mut buf = Buf::new(BytesMut::from(&b"abc"[..]));
+    buf.as_bytes_mut()[0] = b'x';
+    assert_eq!(buf.as_inner(), &b"xbc"[..]);
+
+    let slice = BufSlice::new(b"def");
+    let owned: Buf = slice.to_owned();
+    assert_eq!(owned.as_inner(), &b"def"[..]);
+}
@@ -0,0 +1,199 @@
+//! `ClapValueParser` derive target for `clap`.
+
+use clap::{error::ErrorKind, value_parser, Arg, Command};
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(derive(ClapValueParser))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty, charset = "a-z_")))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod component {
+    use std::ffi::OsStr;
+    use std::fmt;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// Path component.
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_os_str")]
+        #[custom_slice(error(type = "ComponentError"))]
+        #[custom_slice(derive(ClapValueParser))]
+        pub struct Component(std::ffi::OsString);
+
+        /// Path component slice.
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(error(type = "ComponentError"))]
+        pub struct ComponentStr(OsStr);
+
+        /// Rejects empty strings and strings with slashes.
+        #[custom_slice(validator)]
+        fn validate(s: &OsStr) -> Result<(), ComponentError> {
+            if s.is_empty() || s.as_encoded_bytes().contains(&b'/') {
+                Err(ComponentError)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Path component error.
+    #[derive(Debug)]
+    pub struct ComponentError;
+
+    impl fmt::Display for ComponentError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a path component should be non-empty and have no slashes")
+        }
+    }
+}
+
+mod label {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(derive(ClapValueParser))]
+        pub struct Label(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct LabelStr(str);
+    }
+}
+
+fn command(arg: Arg) -> Command {
+    Command::new("test").arg(arg)
+}
+
+#[test]
+fn string_accepted() {
+    use self::ident::Identifier;
+
+    let cmd = command(
+        Arg::new("name")
+            .long("name")
+            .value_parser(value_parser!(Identifier)),
+    );
+    let matches = cmd
+        .try_get_matches_from(["test", "--name", "foo_bar"])
+        .expect("Should succeed");
+    let name = matches.get_one::<Identifier>("name").expect("Should exist");
+    assert_eq!(name.as_str(), "foo_bar");
+}
+
+#[test]
+fn string_rejected() {
+    use self::ident::Identifier;
+
+    let cmd = command(
+        Arg::new("name")
+            .long("name")
+            .value_parser(value_parser!(Identifier)),
+    );
+    let e = cmd
+        .try_get_matches_from(["test", "--name", "foo-bar"])
+        .expect_err("Should fail");
+    assert_eq!(e.kind(), ErrorKind::ValueValidation);
+    let message = e.to_string();
+    assert!(message.contains("'foo-bar'"), "{}", message);
+    assert!(message.contains("--name <name>"), "{}", message);
+    assert!(
+        message.contains("character not in `a-z_` at byte offset 3"),
+        "{}",
+        message
+    );
+
+    let cmd = command(Arg::new("name").value_parser(value_parser!(Identifier)));
+    let e = cmd
+        .try_get_matches_from(["test", ""])
+        .expect_err("Should fail");
+    assert_eq!(e.kind(), ErrorKind::ValueValidation);
+    assert!(e.to_string().contains("the value is empty"), "{}", e);
+}
+
+#[cfg(unix)]
+#[test]
+fn string_non_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    use self::ident::Identifier;
+
+    let cmd = command(Arg::new("name").value_parser(value_parser!(Identifier)));
+    let e = cmd
+        .try_get_matches_from([OsStr::new("test"), OsStr::from_bytes(b"\xff")])
+        .expect_err("Should fail");
+    assert_eq!(e.kind(), ErrorKind::InvalidUtf8);
+}
+
+#[test]
+fn os_string() {
+    use self::component::Component;
+
+    let cmd = command(
+        Arg::new("dir")
+            .long("dir")
+            .value_parser(value_parser!(Component)),
+    );
+    let matches = cmd
+        .clone()
+        .try_get_matches_from(["test", "--dir", "foo"])
+        .expect("Should succeed");
+    let dir = matches.get_one::<Component>("dir").expect("Should exist");
+    assert_eq!(dir.as_os_str(), "foo");
+
+    let e = cmd
+        .try_get_matches_from(["test", "--dir", "foo/bar"])
+        .expect_err("Should fail");
+    assert_eq!(e.kind(), ErrorKind::ValueValidation);
+    let message = e.to_string();
+    assert!(message.contains("--dir <dir>"), "{}", message);
+    assert!(
+        message.contains("a path component should be non-empty and have no slashes"),
+        "{}",
+        message
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn os_string_non_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    use self::component::Component;
+
+    let cmd = command(Arg::new("dir").value_parser(value_parser!(Component)));
+    let matches = cmd
+        .try_get_matches_from([OsStr::new("test"), OsStr::from_bytes(b"\xff")])
+        .expect("Should succeed");
+    let dir = matches.get_one::<Component>("dir").expect("Should exist");
+    assert_eq!(dir.as_os_str().as_bytes(), b"\xff");
+}
+
+#[test]
+fn without_validator() {
+    use self::label::Label;
+
+    let cmd = command(Arg::new("label").value_parser(value_parser!(Label)));
+    let matches = cmd
+        .try_get_matches_from(["test", ""])
+        .expect("Should succeed");
+    let label = matches.get_one::<Label>("label").expect("Should exist");
+    assert_eq!(label.as_str(), "");
+}
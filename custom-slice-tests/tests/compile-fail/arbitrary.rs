@@ -0,0 +1,54 @@
+mod without_derive {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(arbitrary(proptest))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod zero_retries {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(Arbitrary))]
+        #[custom_slice(arbitrary(quickcheck, max_retries = 0))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod invalid_values {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(Arbitrary))]
+        #[custom_slice(arbitrary(proptest = 42, quickchek))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod for_slice {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(Arbitrary))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+mod without_derive {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(fuzz_repair = "std::convert::identity")]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        pub struct Slice(str);
+    }
+}
+
+mod without_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(fuzz_repair = "std::convert::identity")]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct Slice(str);
+    }
+}
+
+mod invalid_repair {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(fuzz_repair = "fn repair")]
+        #[custom_slice(derive(ArbitraryFuzz))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+mod repr_c {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(Vec<u8>);
+
+        #[repr(C)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(TransparentWrapper))]
+        pub struct Slice([u8]);
+    }
+}
+
+mod with_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty)))]
+        #[custom_slice(derive(TransparentWrapper))]
+        pub struct Slice([u8]);
+    }
+}
+
+mod not_slice {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(AsBytes, FromBytes))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+mod slice_ref_without_bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(slice_ref = "pub fn slice_ref")]
+        pub struct Owned(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice([u8]);
+    }
+}
+
+mod slice_range_with_bytes_mut {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(slice_range = "pub fn slice")]
+        pub struct Owned(bytes::BytesMut);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice([u8]);
+    }
+}
+
+mod subslice_closed_without_methods {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(subslice_closed)]
+        pub struct Owned(bytes::Bytes);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice([u8]);
+    }
+}
+
+mod slice_range_without_error_type {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(slice_range = "pub fn slice")]
+        pub struct Owned(bytes::Bytes);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice([u8]);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &[u8]) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+mod unsupported_inner {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(ClapValueParser))]
+        pub struct Owned(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice([u8]);
+    }
+}
+
+mod without_error_type {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Clone)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(ClapValueParser))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
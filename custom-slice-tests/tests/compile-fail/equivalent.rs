@@ -0,0 +1,27 @@
+mod without_hash {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(Equivalent))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod without_derive {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(PartialEq, Eq, Hash)]
+        #[custom_slice(owned)]
+        #[custom_slice(equivalent(indexmap))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
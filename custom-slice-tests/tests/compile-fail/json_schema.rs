@@ -0,0 +1,26 @@
+mod without_derive {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(json_schema(description = "Owned."))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod invalid_with {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(json_schema(with = "fn schema"))]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,66 @@
+mod invalid_pattern {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(regex = "^[a-z$"))]
+        pub struct Slice(str);
+    }
+}
+
+mod unsupported_inner {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(Vec<u32>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(regex = "^[a-z]*$"))]
+        pub struct Slice([u32]);
+    }
+}
+
+mod with_validator_fn {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(regex = "^[a-z]*$"))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod for_owned {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(validator(regex = "^[a-z]*$"))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod with_rules {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(regex = "^[a-z]*$", all(non_empty)))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
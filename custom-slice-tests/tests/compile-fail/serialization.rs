@@ -0,0 +1,55 @@
+mod archive_with_owned_validator {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(error(type = "()"))]
+        #[custom_slice(derive(Archive))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator(owned))]
+        fn validate(_v: &String) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod archive_without_slice_error_type {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(error(type = "()"))]
+        #[custom_slice(derive(Archive))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod decode_without_error_type {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(BorshDeserialize, Decode))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(BorrowDecode))]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+mod from_sql_without_error_type {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(derive(ToSql, FromSql))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+
+        #[custom_slice(validator)]
+        fn validate(_s: &str) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+mod sql_without_derive {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(sql(rusqlite))]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Slice(str);
+    }
+}
+
+mod from_sql_for_slice {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Owned(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(derive(FromSql))]
+        pub struct Slice(str);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,104 @@
+//! `Equivalent` derive target for `hashbrown` and `indexmap`.
+
+use hashbrown::HashMap;
+use indexmap::IndexMap;
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "IdentError"))]
+        #[custom_slice(derive(Equivalent))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "IdentError"))]
+        pub struct IdentStr(str);
+
+        /// Rejects empty strings and strings with whitespaces.
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), IdentError> {
+            if s.is_empty() || s.contains(char::is_whitespace) {
+                Err(IdentError)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Identifier error.
+    #[derive(Debug)]
+    pub struct IdentError;
+}
+
+mod non_eq {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, PartialEq, Eq, ::core::hash::Hash)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(equivalent(hashbrown))]
+        #[custom_slice(derive(Equivalent))]
+        pub struct Owned(Vec<u8>);
+
+        /// Slice type without `Eq`.
+        #[derive(PartialEq, Hash)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        pub struct Slice([u8]);
+    }
+}
+
+#[test]
+fn lookup_by_slice_inner() {
+    use self::ident::Identifier;
+
+    let foo = Identifier::new("foo".to_owned()).expect("Should succeed");
+    let bar = Identifier::new("bar".to_owned()).expect("Should succeed");
+
+    let mut map = HashMap::new();
+    map.insert(foo.clone(), 1);
+    map.insert(bar.clone(), 2);
+    assert_eq!(map.get("foo"), Some(&1));
+    assert_eq!(map.get("bar"), Some(&2));
+    // Invalid values are never found.
+    assert_eq!(map.get("foo bar"), None);
+
+    let mut map = IndexMap::new();
+    map.insert(foo, 1);
+    map.insert(bar, 2);
+    assert_eq!(map.get("bar"), Some(&2));
+    assert_eq!(map.get_index_of("foo"), Some(0));
+    assert_eq!(map.get(""), None);
+}
+
+#[test]
+fn lookup_by_slice() {
+    use self::ident::{IdentStr, Identifier};
+
+    let mut map = HashMap::new();
+    map.insert(
+        Identifier::new("foo".to_owned()).expect("Should succeed"),
+        1,
+    );
+    // The blanket impl using `Borrow<IdentStr>` is used.
+    let key = IdentStr::new("foo").expect("Should succeed");
+    assert_eq!(map.get(key), Some(&1));
+}
+
+#[test]
+fn lookup_by_non_eq_slice() {
+    use self::non_eq::{Owned, Slice};
+
+    let mut map = HashMap::new();
+    map.insert(Owned::new(b"foo".to_vec()), 1);
+    assert_eq!(map.get(Slice::new(b"foo")), Some(&1));
+    assert_eq!(map.get(&b"foo"[..]), Some(&1));
+    assert_eq!(map.get(Slice::new(b"bar")), None);
+}
@@ -0,0 +1,339 @@
+//! Generated code for optional features should not depend on the prelude or
+//! on relative paths.
+
+#![no_implicit_prelude]
+#![allow(dead_code, unused_imports)]
+
+/// A module which shadows relative `std::..` paths.
+mod std {}
+
+/// Types which shadow the prelude.
+mod hostile {
+    pub enum Result {
+        Ok,
+        Err,
+    }
+
+    pub struct Option;
+    pub struct Some;
+    pub struct None;
+    pub struct Box;
+    pub struct Cow;
+    pub struct String;
+    pub struct Vec;
+
+    pub trait AsRef {}
+    pub trait Borrow {}
+    pub trait Clone {}
+    pub trait Default {}
+    pub trait From {}
+    pub trait Hash {}
+    pub trait Into {}
+    pub trait IntoIterator {}
+    pub trait Iterator {}
+    pub trait PartialEq {}
+    pub trait PartialOrd {}
+    pub trait Sized {}
+    pub trait ToOwned {}
+    pub trait ToString {}
+}
+
+use self::hostile::Result::{Err, Ok};
+use self::hostile::*;
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(error(generate = "ShortError"))]
+    pub struct Short(::std::string::String);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(error(type = "::custom_slice::TooLongError"))]
+    #[custom_slice(derive(TryFromInner))]
+    #[custom_slice(validator(type = "::custom_slice::MaxLen<4>"))]
+    pub struct ShortStr(str);
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(error(generate = "BufError"))]
+    #[custom_slice(incremental_validator = "::custom_slice::MaxLenState<8>")]
+    #[custom_slice(push_checked = "pub fn push")]
+    #[custom_slice(derive(IoWrite))]
+    pub struct Buf(::std::vec::Vec<u8>);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(error(type = "::custom_slice::TooLongError"))]
+    #[custom_slice(validator(type = "::custom_slice::MaxLen<8>"))]
+    #[custom_slice(derive(AsBytes, FromBytes))]
+    pub struct BufSlice([u8]);
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(error(generate = "NoteError"))]
+    #[custom_slice(incremental_validator = "::custom_slice::MaxLenState<8>")]
+    #[custom_slice(derive(FmtWrite))]
+    pub struct Note(::std::string::String);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(error(type = "::custom_slice::TooLongError"))]
+    #[custom_slice(validator(type = "::custom_slice::MaxLen<8>"))]
+    pub struct NoteStr(str);
+}
+
+/// Pads the value to be non-empty.
+fn repair(s: ::std::string::String) -> ::std::string::String {
+    let mut s = s;
+    ::std::string::String::push(&mut s, 'x');
+    s
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[derive(
+        ::core::fmt::Debug,
+        ::core::clone::Clone,
+        ::core::cmp::PartialEq,
+        ::core::cmp::Eq,
+        ::core::hash::Hash,
+    )]
+    #[custom_slice(owned)]
+    #[custom_slice(into_inner = "pub fn into_inner")]
+    #[custom_slice(fuzz_repair = "repair")]
+    #[custom_slice(json_schema(description = "Text."))]
+    #[custom_slice(sql(postgres, rusqlite))]
+    #[custom_slice(derive(
+        Arbitrary,
+        ArbitraryFuzz,
+        Equivalent,
+        JsonSchema,
+        ClapValueParser,
+        Encode,
+        Decode,
+        BorrowDecode,
+        BorshSerialize,
+        BorshDeserialize,
+        Archive,
+        ToSql,
+        FromSql
+    ))]
+    pub struct Text(::std::string::String);
+
+    #[derive(::core::fmt::Debug, ::core::cmp::PartialEq, ::core::hash::Hash)]
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(validator(all(non_empty)))]
+    #[custom_slice(sql(postgres, rusqlite))]
+    #[custom_slice(derive(
+        ArbitraryFuzz,
+        JsonSchema,
+        Encode,
+        BorrowDecode,
+        BorshSerialize,
+        ToSql
+    ))]
+    pub struct TextStr(str);
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    pub struct WrapperVec(::std::vec::Vec<u32>);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(derive(AsBytes, FromBytes, TransparentWrapper))]
+    pub struct Wrapper([u32]);
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[derive(::core::clone::Clone)]
+    #[custom_slice(owned)]
+    #[custom_slice(derive(ClapValueParser))]
+    pub struct OsText(::std::ffi::OsString);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    pub struct OsTextStr(::std::ffi::OsStr);
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    #[custom_slice(slice_ref = "pub fn slice_ref")]
+    #[custom_slice(slice_range = "pub fn slice")]
+    #[custom_slice(error(type = "SharedSliceError"))]
+    #[custom_slice(derive(Deref))]
+    pub struct Shared(::bytes::Bytes);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(new_checked = "pub fn new")]
+    #[custom_slice(validator(all(non_empty)))]
+    pub struct SharedSlice([u8]);
+}
+
+::custom_slice_macros::define_slice_types_pair! {
+    #[custom_slice(owned)]
+    pub struct SharedMut(::bytes::BytesMut);
+
+    #[repr(transparent)]
+    #[custom_slice(slice)]
+    #[custom_slice(new_unchecked = "pub fn new")]
+    pub struct SharedMutSlice([u8]);
+}
+
+#[test]
+fn validator_trait() {
+    let v = Short::new(::std::borrow::ToOwned::to_owned("abcd"));
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    match Short::new(::std::borrow::ToOwned::to_owned("abcde")) {
+        ::core::result::Result::Ok(_) => ::core::panic!("Should fail: too long"),
+        ::core::result::Result::Err(e) => ::core::assert_eq!(e.into_inner(), "abcde"),
+    }
+}
+
+#[test]
+fn incremental_validator() {
+    let mut v = ::core::result::Result::unwrap(Buf::new(::std::vec::Vec::new()));
+    ::core::assert!(::core::result::Result::is_ok(&v.push(b"foo")));
+    ::core::assert!(::core::result::Result::is_ok(&::std::io::Write::write_all(
+        &mut v, b"bar"
+    )));
+    let mut v = ::core::result::Result::unwrap(Note::new(::std::string::String::new()));
+    ::core::assert!(::core::result::Result::is_ok(
+        &::core::fmt::Write::write_str(&mut v, "foo")
+    ));
+}
+
+#[test]
+fn arbitrary() {
+    let v = <Text as ::quickcheck::Arbitrary>::arbitrary(&mut ::quickcheck::Gen::new(8));
+    ::core::assert!(!::std::string::String::is_empty(&v.into_inner()));
+    let mut runner = ::proptest::test_runner::TestRunner::deterministic();
+    let tree = ::proptest::strategy::Strategy::new_tree(
+        &::proptest::arbitrary::any::<Text>(),
+        &mut runner,
+    );
+    ::core::assert!(::core::result::Result::is_ok(&tree));
+}
+
+#[test]
+fn arbitrary_fuzz() {
+    let v = <Text as ::arbitrary::Arbitrary<'_>>::arbitrary_take_rest(
+        ::arbitrary::Unstructured::new(b""),
+    );
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    let v = <&TextStr as ::arbitrary::Arbitrary<'_>>::arbitrary_take_rest(
+        ::arbitrary::Unstructured::new(b"foo"),
+    );
+    ::core::assert!(::core::result::Result::is_ok(&v));
+}
+
+#[test]
+fn bytemuck() {
+    let raw: &[u32] = &[1, 2];
+    let bytes: &[u8] = ::bytemuck::cast_slice(raw);
+    let v = <BufSlice as ::custom_slice::FromBytes>::from_bytes(bytes);
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    let v = <Wrapper as ::custom_slice::FromBytes>::from_bytes(bytes);
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    let w = <Wrapper as ::bytemuck::TransparentWrapper<[u32]>>::wrap_ref(raw);
+    ::core::assert_eq!(<Wrapper as ::custom_slice::AsBytes>::as_bytes(w), bytes);
+}
+
+#[test]
+fn equivalent() {
+    let key = <TextStr as ::std::borrow::ToOwned>::to_owned(::core::result::Result::unwrap(
+        TextStr::new("foo"),
+    ));
+    ::core::assert!(<str as ::hashbrown::Equivalent<Text>>::equivalent(
+        "foo", &key
+    ));
+    ::core::assert!(<str as ::indexmap::Equivalent<Text>>::equivalent(
+        "foo", &key
+    ));
+    ::core::assert!(!<str as ::indexmap::Equivalent<Text>>::equivalent(
+        "bar", &key
+    ));
+}
+
+#[test]
+fn json_schema() {
+    let mut generator = <::schemars::SchemaGenerator as ::core::default::Default>::default();
+    let schema = <Text as ::schemars::JsonSchema>::json_schema(&mut generator);
+    ::core::assert!(::core::option::Option::is_some(&schema.get("description")));
+    let schema = <TextStr as ::schemars::JsonSchema>::json_schema(&mut generator);
+    ::core::assert!(::core::option::Option::is_some(&schema.get("minLength")));
+}
+
+#[test]
+fn clap() {
+    let cmd = ::clap::Command::arg(
+        ::clap::Command::new("test"),
+        ::clap::Arg::value_parser(
+            ::clap::Arg::new("value"),
+            <Text as ::clap::builder::ValueParserFactory>::value_parser(),
+        ),
+    );
+    let v = ::clap::Command::try_get_matches_from(cmd, ["test", "foo"]);
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    let _ = <OsText as ::clap::builder::ValueParserFactory>::value_parser();
+}
+
+#[test]
+fn bincode() {
+    let encoded = ::bincode::encode_to_vec("foo", ::bincode::config::standard());
+    let encoded = ::core::result::Result::unwrap(encoded);
+    let v = ::bincode::decode_from_slice::<Text, _>(&encoded, ::bincode::config::standard());
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    let v =
+        ::bincode::borrow_decode_from_slice::<&TextStr, _>(&encoded, ::bincode::config::standard());
+    ::core::assert!(::core::result::Result::is_ok(&v));
+}
+
+#[test]
+fn borsh() {
+    let encoded = ::borsh::to_vec("foo");
+    let encoded = ::core::result::Result::unwrap(encoded);
+    let v = <Text as ::borsh::BorshDeserialize>::try_from_slice(&encoded);
+    ::core::assert!(::core::result::Result::is_ok(&v));
+}
+
+#[test]
+fn rkyv() {
+    let value = <::std::string::String as ::core::convert::From<&str>>::from("foo");
+    let bytes = ::rkyv::to_bytes::<::rkyv::rancor::Error>(&value);
+    let bytes = ::core::result::Result::unwrap(bytes);
+    let v = ::rkyv::access::<ArchivedText, ::rkyv::rancor::Error>(&bytes);
+    ::core::assert!(::core::result::Result::is_ok(&v));
+}
+
+#[test]
+fn postgres() {
+    let v = <Text as ::postgres_types::FromSql>::from_sql(&::postgres_types::Type::TEXT, b"foo");
+    ::core::assert!(::core::result::Result::is_ok(&v));
+}
+
+#[test]
+fn rusqlite() {
+    let conn = ::rusqlite::Connection::open_in_memory();
+    let conn = ::core::result::Result::unwrap(conn);
+    let v = conn.query_row("SELECT 'foo'", [], |row| row.get::<_, Text>(0));
+    ::core::assert!(::core::result::Result::is_ok(&v));
+}
+
+#[test]
+fn bytes() {
+    let slice = ::core::result::Result::unwrap(SharedSlice::new(b"foo"));
+    let owned = <SharedSlice as ::std::borrow::ToOwned>::to_owned(slice);
+    let _: Shared = owned.slice_ref(::core::ops::Deref::deref(&owned));
+    let v = owned.slice(1..);
+    ::core::assert!(::core::result::Result::is_ok(&v));
+    let _: SharedMut =
+        <SharedMutSlice as ::std::borrow::ToOwned>::to_owned(SharedMutSlice::new(b"foo"));
+}
@@ -0,0 +1,242 @@
+//! Incremental validators implementing `custom_slice::IncrementalValidator`.
+
+use std::fmt::Write as _;
+use std::io::{self, IoSlice, Write as _};
+
+use custom_slice::{IncrementalValidator, MaxLen, MaxLenState, TooLongError};
+
+/// Incremental validator for strings without ASCII uppercase letters.
+pub struct NoUpperState {
+    /// Offset of the next chunk.
+    offset: usize,
+}
+
+impl IncrementalValidator<str> for NoUpperState {
+    type Error = UpperError;
+
+    fn start(current: &str) -> Self {
+        Self {
+            offset: current.len(),
+        }
+    }
+
+    fn feed(&mut self, chunk: &str) -> Result<(), Self::Error> {
+        if let Some(pos) = chunk.bytes().position(|b| b.is_ascii_uppercase()) {
+            return Err(UpperError(self.offset + pos));
+        }
+        self.offset += chunk.len();
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Error for uppercase letters, with the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpperError(usize);
+
+/// Incremental validator for UTF-8 bytes.
+pub struct Utf8State {
+    /// Incomplete character at the end of the previous chunk.
+    pending: Vec<u8>,
+}
+
+impl IncrementalValidator<[u8]> for Utf8State {
+    type Error = std::str::Utf8Error;
+
+    fn start(_current: &[u8]) -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    fn feed(&mut self, chunk: &[u8]) -> Result<(), Self::Error> {
+        let mut buf = std::mem::take(&mut self.pending);
+        buf.extend_from_slice(chunk);
+        match std::str::from_utf8(&buf) {
+            Ok(_) => Ok(()),
+            // Incomplete character at the end.
+            Err(e) if e.error_len().is_none() => {
+                self.pending = buf[e.valid_up_to()..].to_vec();
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn finish(self) -> Result<(), Self::Error> {
+        std::str::from_utf8(&self.pending).map(|_| ())
+    }
+
+    fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+mod no_upper {
+    use super::{NoUpperState, UpperError};
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// String without ASCII uppercase letters.
+        #[derive(Debug, Default)]
+        #[custom_slice(owned)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(incremental_validator = "NoUpperState")]
+        #[custom_slice(push_checked = "pub fn push_str")]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(derive(FmtWrite))]
+        pub struct NoUpperString(String);
+
+        /// String slice without ASCII uppercase letters.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct NoUpperStr(str);
+
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), UpperError> {
+            match s.bytes().position(|b| b.is_ascii_uppercase()) {
+                Some(pos) => Err(UpperError(pos)),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+mod utf8 {
+    use super::Utf8State;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// UTF-8 bytes.
+        #[derive(Debug, Default)]
+        #[custom_slice(owned)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(incremental_validator = "Utf8State")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        #[custom_slice(derive(IoWrite))]
+        pub struct Utf8Buf(Vec<u8>);
+
+        /// UTF-8 bytes slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct Utf8Bytes([u8]);
+
+        impl Utf8Buf {
+            /// Appends the bytes if they are valid UTF-8.
+            #[custom_slice(push_checked)]
+            pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), std::str::Utf8Error>;
+        }
+
+        #[custom_slice(validator)]
+        fn validate(s: &[u8]) -> Result<(), std::str::Utf8Error> {
+            std::str::from_utf8(s).map(|_| ())
+        }
+    }
+}
+
+mod short {
+    use super::{MaxLen, MaxLenState};
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, Default)]
+        #[custom_slice(owned)]
+        #[custom_slice(debug_validate)]
+        #[custom_slice(incremental_validator = "MaxLenState<8>")]
+        #[custom_slice(push_checked = "pub fn push_str")]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        pub struct ShortString(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(type = "MaxLen<8>"))]
+        pub struct ShortStr(str);
+    }
+}
+
+#[test]
+fn push_checked() {
+    use self::no_upper::NoUpperString;
+
+    let mut s = NoUpperString::default();
+    assert_eq!(s.push_str("foo"), Ok(()));
+    assert_eq!(s.push_str("bar"), Ok(()));
+    assert_eq!(s.push_str("baZ"), Err(UpperError(8)));
+    assert_eq!(s.as_str(), "foobar");
+}
+
+#[test]
+fn fmt_write() {
+    use self::no_upper::NoUpperString;
+
+    let mut s = NoUpperString::default();
+    let name = "foo";
+    assert!(write!(s, "{}-{}", name, 42).is_ok());
+    assert_eq!(s.as_str(), "foo-42");
+    let name = "Bar";
+    assert!(write!(s, "{}", name).is_err());
+    assert_eq!(s.as_str(), "foo-42");
+}
+
+#[test]
+fn io_write() {
+    use self::utf8::Utf8Buf;
+
+    let mut buf = Utf8Buf::default();
+    buf.write_all("\u{3042}".as_bytes())
+        .expect("Should succeed");
+    assert_eq!(buf.as_bytes(), "\u{3042}".as_bytes());
+
+    // Incomplete character at the end is left for the next write.
+    assert_eq!(buf.write(b"a\xe3\x81").expect("Should succeed"), 1);
+    assert_eq!(buf.as_bytes(), "\u{3042}a".as_bytes());
+    assert_eq!(buf.write(b"\xe3\x81\x82").expect("Should succeed"), 3);
+    assert_eq!(buf.as_bytes(), "\u{3042}a\u{3042}".as_bytes());
+
+    // Only an incomplete character.
+    let e = buf.write(b"\xe3\x81").expect_err("Should fail");
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(buf.as_bytes(), "\u{3042}a\u{3042}".as_bytes());
+
+    // Invalid data.
+    let e = buf.write(b"b\xff").expect_err("Should fail");
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(buf.as_bytes(), "\u{3042}a\u{3042}".as_bytes());
+}
+
+#[test]
+fn io_write_vectored() {
+    use self::utf8::Utf8Buf;
+
+    let mut buf = Utf8Buf::default();
+    // A character split across buffers.
+    let bufs = [IoSlice::new(b"a\xe3"), IoSlice::new(b"\x81\x82b")];
+    assert_eq!(buf.write_vectored(&bufs).expect("Should succeed"), 5);
+    assert_eq!(buf.as_bytes(), "a\u{3042}b".as_bytes());
+
+    let bufs = [IoSlice::new(b"a\xe3"), IoSlice::new(b"b")];
+    assert!(buf.write_vectored(&bufs).is_err());
+    assert_eq!(buf.as_bytes(), "a\u{3042}b".as_bytes());
+}
+
+#[test]
+fn push_bytes() {
+    use self::utf8::Utf8Buf;
+
+    let mut buf = Utf8Buf::default();
+    assert!(buf.push_bytes(b"foo").is_ok());
+    assert!(buf.push_bytes(b"\xff").is_err());
+    assert_eq!(buf.as_bytes(), b"foo");
+}
+
+#[test]
+fn max_len() {
+    use self::short::ShortString;
+
+    let mut s = ShortString::default();
+    assert!(s.push_str("abcd").is_ok());
+    assert!(s.push_str("efgh").is_ok());
+    let e: TooLongError = s.push_str("i").expect_err("Should fail");
+    assert_eq!(e.actual_len(), 9);
+    assert_eq!(s.as_str(), "abcdefgh");
+}
@@ -0,0 +1,223 @@
+//! `JsonSchema` derive target for `schemars`.
+
+use regex::Regex;
+use schemars::{schema_for, JsonSchema};
+use serde_json::json;
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[custom_slice(owned)]
+        #[custom_slice(json_schema(description = "Identifier of the resource."))]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty, max_len = 64, charset = "a-z0-9_-")))]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod nickname {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct Nickname(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, max_len = 4, no_leading = "-")))]
+        pub struct NicknameStr(str);
+    }
+}
+
+mod currency {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct CurrencyCode(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(regex = "^[A-Z]{3}$"))]
+        pub struct CurrencyCodeStr(str);
+    }
+}
+
+mod label {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct Label(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(ascii, no_leading = "0-9-")))]
+        pub struct LabelStr(str);
+    }
+}
+
+mod digest {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct Digest(Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(validator(all(non_empty, max_len = 32)))]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct DigestBytes([u8]);
+    }
+}
+
+mod uuid {
+    use schemars::{json_schema, Schema, SchemaGenerator};
+
+    /// Returns the schema for UUID strings.
+    fn uuid_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "format": "uuid",
+        })
+    }
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "()"))]
+        #[custom_slice(json_schema(with = "uuid_schema", description = "UUID."))]
+        #[custom_slice(derive(JsonSchema))]
+        pub struct Uuid(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        pub struct UuidStr(str);
+
+        /// Checks the value is a hyphenated UUID.
+        #[custom_slice(validator)]
+        fn validate(s: &str) -> Result<(), ()> {
+            let is_valid = s.len() == 36
+                && s.char_indices().all(|(i, c)| match i {
+                    8 | 13 | 18 | 23 => c == '-',
+                    _ => c.is_ascii_hexdigit(),
+                });
+            if is_valid {
+                Ok(())
+            } else {
+                Err(())
+            }
+        }
+    }
+}
+
+#[test]
+fn rules() {
+    let schema = schema_for!(ident::Identifier);
+    assert_eq!(schema.get("title"), Some(&json!("Identifier")));
+    assert_eq!(schema.get("type"), Some(&json!("string")));
+    assert_eq!(schema.get("minLength"), Some(&json!(1)));
+    assert_eq!(schema.get("maxLength"), Some(&json!(64)));
+    assert_eq!(
+        schema.get("description"),
+        Some(&json!("Identifier of the resource."))
+    );
+
+    let pattern = schema.get("pattern").and_then(|v| v.as_str()).unwrap();
+    assert_eq!(pattern, r"^[a-z0-9_\-]*$");
+    let re = Regex::new(pattern).expect("Should be valid");
+    assert!(re.is_match("foo-bar_2"));
+    assert!(!re.is_match("Foo"));
+
+    let schema = schema_for!(ident::IdentStr);
+    assert_eq!(schema.get("title"), Some(&json!("IdentStr")));
+    assert_eq!(
+        schema.get("pattern").and_then(|v| v.as_str()),
+        Some(pattern)
+    );
+    assert_eq!(schema.get("description"), None);
+}
+
+#[test]
+fn max_len_for_non_ascii() {
+    let schema = schema_for!(nickname::Nickname);
+    assert_eq!(schema.get("minLength"), Some(&json!(1)));
+    // `max_len` counts bytes, so it cannot be emitted as `maxLength`.
+    assert_eq!(schema.get("maxLength"), None);
+    // 2 characters in 6 bytes.
+    assert!(nickname::NicknameStr::new("\u{3042}\u{3044}").is_err());
+    assert!(nickname::NicknameStr::new("ab").is_ok());
+}
+
+#[test]
+fn regex() {
+    let schema = schema_for!(currency::CurrencyCode);
+    assert_eq!(schema.get("type"), Some(&json!("string")));
+    assert_eq!(schema.get("pattern"), Some(&json!("^[A-Z]{3}$")));
+}
+
+#[test]
+fn multiple_char_rules() {
+    let schema = schema_for!(label::Label);
+    let patterns = schema
+        .get("allOf")
+        .and_then(|v| v.as_array())
+        .unwrap()
+        .iter()
+        .map(|v| Regex::new(v["pattern"].as_str().unwrap()).expect("Should be valid"))
+        .collect::<Vec<_>>();
+    assert_eq!(patterns.len(), 2);
+    let is_match = |s: &str| patterns.iter().all(|re| re.is_match(s));
+    assert!(is_match("foo-1"));
+    assert!(is_match(""));
+    assert!(!is_match("1foo"));
+    assert!(!is_match("-foo"));
+    assert!(!is_match("f\u{f6}\u{f6}"));
+}
+
+#[test]
+fn bytes() {
+    let schema = schema_for!(digest::DigestBytes);
+    assert_eq!(schema.get("type"), Some(&json!("array")));
+    assert_eq!(schema.get("minItems"), Some(&json!(1)));
+    assert_eq!(schema.get("maxItems"), Some(&json!(32)));
+    assert_eq!(schema.get("pattern"), None);
+    assert_eq!(
+        schema_for!(digest::Digest).get("maxItems"),
+        Some(&json!(32))
+    );
+}
+
+#[test]
+fn schema_fn() {
+    let schema = schema_for!(uuid::Uuid);
+    assert_eq!(schema.get("format"), Some(&json!("uuid")));
+    assert_eq!(schema.get("description"), Some(&json!("UUID.")));
+    // Constraints of hand-written validators are unknown.
+    assert_eq!(schema.get("pattern"), None);
+    assert!(uuid::Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8".to_owned()).is_ok());
+}
+
+#[test]
+fn reference() {
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Request {
+        id: ident::Identifier,
+        currency: currency::CurrencyCode,
+    }
+
+    let schema = schema_for!(Request);
+    assert_eq!(
+        schema.pointer("/properties/id/$ref"),
+        Some(&json!("#/$defs/Identifier"))
+    );
+    assert_eq!(
+        schema.pointer("/$defs/CurrencyCode/pattern"),
+        Some(&json!("^[A-Z]{3}$"))
+    );
+}
@@ -0,0 +1,128 @@
+//! `ToSql` and `FromSql` derive targets for `postgres-types`.
+
+use bytes::BytesMut;
+use postgres_types::{FromSql, IsNull, ToSql, Type};
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(ToSql, FromSql))]
+        #[custom_slice(sql(postgres))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, charset = "a-z_")))]
+        #[custom_slice(derive(ToSql))]
+        #[custom_slice(sql(postgres))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod digest {
+    use std::fmt;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// Digest.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "DigestError"))]
+        #[custom_slice(derive(ToSql, FromSql))]
+        pub struct Digest(Vec<u8>);
+
+        /// Digest slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "DigestError"))]
+        #[custom_slice(derive(ToSql))]
+        pub struct DigestSlice([u8]);
+
+        /// Accepts 4-byte digests.
+        #[custom_slice(validator)]
+        fn validate(v: &[u8]) -> Result<(), DigestError> {
+            if v.len() == 4 {
+                Ok(())
+            } else {
+                Err(DigestError)
+            }
+        }
+    }
+
+    /// Digest error.
+    #[derive(Debug)]
+    pub struct DigestError;
+
+    impl fmt::Display for DigestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a digest should be 4 bytes long")
+        }
+    }
+
+    impl std::error::Error for DigestError {}
+}
+
+fn encode(v: &dyn ToSql, ty: &Type) -> BytesMut {
+    let mut buf = BytesMut::new();
+    let is_null = v.to_sql_checked(ty, &mut buf).expect("Should succeed");
+    assert!(matches!(is_null, IsNull::No));
+    buf
+}
+
+#[test]
+fn roundtrip() {
+    use self::ident::{IdentStr, Identifier};
+
+    let ident = Identifier::new("foo_bar".to_owned()).expect("Should succeed");
+    let encoded = encode(&ident, &Type::TEXT);
+    assert_eq!(encoded, encode(&"foo_bar", &Type::TEXT));
+    let slice = IdentStr::new("foo_bar").expect("Should succeed");
+    assert_eq!(encode(&slice, &Type::VARCHAR), encoded);
+
+    assert!(<Identifier as FromSql>::accepts(&Type::TEXT));
+    let decoded = Identifier::from_sql(&Type::TEXT, &encoded).expect("Should succeed");
+    assert_eq!(decoded, ident);
+}
+
+#[test]
+fn wrong_type_is_rejected() {
+    use self::ident::{IdentStr, Identifier};
+
+    let ident = Identifier::new("foo".to_owned()).expect("Should succeed");
+    let slice = IdentStr::new("foo").expect("Should succeed");
+    let mut buf = BytesMut::new();
+    assert!(ident.to_sql_checked(&Type::INT4, &mut buf).is_err());
+    assert!(slice.to_sql_checked(&Type::INT4, &mut buf).is_err());
+    assert!(!<Identifier as FromSql>::accepts(&Type::INT4));
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    use self::digest::{Digest, DigestError, DigestSlice};
+    use self::ident::{IdentStrError, Identifier};
+
+    let e = Identifier::from_sql(&Type::TEXT, b"foo-bar").expect_err("Should fail");
+    let inner = e
+        .downcast_ref::<IdentStrError>()
+        .expect("Should be the validator error");
+    assert_eq!(inner.offset(), 3);
+
+    let digest = Digest::new(vec![0, 1, 2, 255]).expect("Should succeed");
+    let slice = DigestSlice::new(&[0, 1, 2, 255]).expect("Should succeed");
+    let encoded = encode(&digest, &Type::BYTEA);
+    assert_eq!(encoded, encode(&slice, &Type::BYTEA));
+    assert_eq!(
+        Digest::from_sql(&Type::BYTEA, &encoded).expect("Should succeed"),
+        digest
+    );
+    let e = Digest::from_sql(&Type::BYTEA, &[0, 1, 2]).expect_err("Should fail");
+    assert!(e.is::<DigestError>(), "{:?}", e);
+}
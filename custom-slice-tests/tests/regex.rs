@@ -0,0 +1,120 @@
+//! Validators generated from regular expressions.
+
+mod str_slice {
+    use std::convert::TryFrom;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// An identifier.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(Deref, TryFromInner))]
+        pub struct Identifier(String);
+
+        /// An identifier slice.
+        #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(Deref, TryFromInner))]
+        #[custom_slice(validator(regex = "^[a-z][a-z0-9_]{0,31}$"))]
+        pub struct Ident(str);
+    }
+
+    #[test]
+    fn valid() {
+        let ident = Ident::new("foo_bar2").expect("Should never fail: valid identifier");
+        assert_eq!(&**ident, "foo_bar2");
+        let owned = Identifier::new("foo".to_owned()).expect("Should never fail: valid identifier");
+        assert_eq!(&**owned, "foo");
+    }
+
+    #[test]
+    fn invalid() {
+        let e: IdentError = Ident::new("2foo").expect_err("Should fail: invalid identifier");
+        assert_eq!(e.input(), "2foo");
+        assert_eq!(e.pattern(), "^[a-z][a-z0-9_]{0,31}$");
+        assert_eq!(
+            e.to_string(),
+            r#""2foo" does not match the pattern "^[a-z][a-z0-9_]{0,31}$""#
+        );
+
+        let e =
+            Identifier::try_from("Foo".to_owned()).expect_err("Should fail: invalid identifier");
+        assert_eq!(e.input(), "Foo");
+        assert!(<&Ident>::try_from("a".repeat(33).as_str()).is_err());
+    }
+}
+
+mod bytes_slice {
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct HexBuf(Vec<u8>);
+
+        #[derive(Debug)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        #[custom_slice(validator(regex = "^(?-u:[0-9a-f])*$", error = "NotHexError"))]
+        pub struct Hex([u8]);
+    }
+
+    #[test]
+    fn valid() {
+        let hex = Hex::new(b"0123abcd").expect("Should never fail: valid hex digits");
+        assert_eq!(hex.as_bytes(), b"0123abcd");
+        assert!(HexBuf::new(b"ff".to_vec()).is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        let e: NotHexError = Hex::new(b"xy\xff").expect_err("Should fail: invalid hex digits");
+        assert_eq!(e.input(), b"xy\xff");
+        assert_eq!(
+            e.to_string(),
+            r#"b"xy\xff" does not match the pattern "^(?-u:[0-9a-f])*$""#
+        );
+    }
+}
+
+#[no_implicit_prelude]
+mod without_prelude {
+    #[allow(dead_code)]
+    mod std {}
+    #[allow(dead_code)]
+    type Result = ();
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Name(::std::string::String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(regex = "^[a-z]+$"))]
+        pub struct NameStr(str);
+    }
+
+    ::custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        pub struct Bytes(::std::vec::Vec<u8>);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(regex = "^[a-z]+$"))]
+        pub struct BytesSlice([u8]);
+    }
+
+    #[test]
+    fn validate() {
+        ::std::assert!(NameStr::new("foo").is_ok());
+        ::std::assert!(NameStr::new("Foo").is_err());
+        ::std::assert!(BytesSlice::new(b"foo").is_ok());
+        ::std::assert!(BytesSlice::new(b"Foo").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! `Archive` derive target for `rkyv`.
+
+use rkyv::rancor;
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(Archive))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, charset = "a-z_")))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod digest {
+    use std::fmt;
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// Digest.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "DigestError"))]
+        #[custom_slice(derive(Archive))]
+        pub struct Digest(Vec<u8>);
+
+        /// Digest slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_bytes")]
+        #[custom_slice(error(type = "DigestError"))]
+        pub struct DigestSlice([u8]);
+
+        /// Accepts 4-byte digests.
+        #[custom_slice(validator)]
+        fn validate(v: &[u8]) -> Result<(), DigestError> {
+            if v.len() == 4 {
+                Ok(())
+            } else {
+                Err(DigestError)
+            }
+        }
+    }
+
+    /// Digest error.
+    #[derive(Debug)]
+    pub struct DigestError;
+
+    impl fmt::Display for DigestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a digest should be 4 bytes long")
+        }
+    }
+
+    impl std::error::Error for DigestError {}
+}
+
+mod label {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(Archive))]
+        pub struct Label(String);
+
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        pub struct LabelStr(str);
+    }
+}
+
+/// A struct with custom slice fields.
+#[derive(Debug, PartialEq, Eq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct Record {
+    name: ident::Identifier,
+    digest: digest::Digest,
+}
+
+/// A struct with the same layout as `Record` and raw fields.
+#[derive(rkyv::Archive, rkyv::Serialize)]
+struct RawRecord {
+    name: String,
+    digest: Vec<u8>,
+}
+
+#[test]
+fn roundtrip() {
+    use self::digest::Digest;
+    use self::ident::{ArchivedIdentifier, IdentStr, Identifier};
+
+    let ident = Identifier::new("foo_bar".to_owned()).expect("Should succeed");
+    let bytes = rkyv::to_bytes::<rancor::Error>(&ident).expect("Should succeed");
+    let archived =
+        rkyv::access::<ArchivedIdentifier, rancor::Error>(&bytes).expect("Should succeed");
+    let slice: &IdentStr = archived;
+    assert_eq!(slice.as_str(), "foo_bar");
+    let deserialized =
+        rkyv::deserialize::<Identifier, rancor::Error>(archived).expect("Should succeed");
+    assert_eq!(deserialized, ident);
+
+    let record = Record {
+        name: ident,
+        digest: Digest::new(vec![0, 1, 2, 255]).expect("Should succeed"),
+    };
+    let bytes = rkyv::to_bytes::<rancor::Error>(&record).expect("Should succeed");
+    let archived = rkyv::access::<ArchivedRecord, rancor::Error>(&bytes).expect("Should succeed");
+    assert_eq!(archived.name.as_str(), "foo_bar");
+    assert_eq!(archived.digest.as_bytes(), &[0, 1, 2, 255]);
+    let deserialized =
+        rkyv::deserialize::<Record, rancor::Error>(archived).expect("Should succeed");
+    assert_eq!(deserialized, record);
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    use self::ident::ArchivedIdentifier;
+
+    let bytes = rkyv::to_bytes::<rancor::Error>(&"foo-bar".to_owned()).expect("Should succeed");
+    let e = rkyv::access::<ArchivedIdentifier, rancor::Error>(&bytes)
+        .err()
+        .expect("Should fail");
+    let message = e.to_string();
+    assert!(
+        message.contains("character not in `a-z_` at byte offset 3"),
+        "{}",
+        message
+    );
+
+    let raw = RawRecord {
+        name: "foo".to_owned(),
+        digest: vec![0, 1, 2],
+    };
+    let bytes = rkyv::to_bytes::<rancor::Error>(&raw).expect("Should succeed");
+    let e = rkyv::access::<ArchivedRecord, rancor::Error>(&bytes)
+        .err()
+        .expect("Should fail");
+    let message = e.to_string();
+    assert!(
+        message.contains("a digest should be 4 bytes long"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn without_validator() {
+    use self::label::{ArchivedLabel, Label};
+
+    let label = Label::new(String::new());
+    let bytes = rkyv::to_bytes::<rancor::Error>(&label).expect("Should succeed");
+    let archived = rkyv::access::<ArchivedLabel, rancor::Error>(&bytes).expect("Should succeed");
+    assert_eq!(archived.as_str(), "");
+}
@@ -0,0 +1,134 @@
+//! `ToSql` and `FromSql` derive targets for `rusqlite`.
+
+use rusqlite::{params, Connection};
+
+mod ident {
+    custom_slice_macros::define_slice_types_pair! {
+        /// Identifier.
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(derive(ToSql, FromSql))]
+        #[custom_slice(sql(rusqlite))]
+        pub struct Identifier(String);
+
+        /// Identifier slice.
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(get_ref = "pub fn as_str")]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(validator(all(non_empty, charset = "a-z_")))]
+        #[custom_slice(derive(ToSql))]
+        #[custom_slice(sql(rusqlite))]
+        pub struct IdentStr(str);
+    }
+}
+
+mod bytes {
+    custom_slice_macros::define_slice_types_pair! {
+        #[derive(Debug, PartialEq, Eq)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(ToSql, FromSql))]
+        pub struct Bytes(Vec<u8>);
+
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_unchecked = "pub fn new")]
+        #[custom_slice(derive(ToSql))]
+        pub struct BytesSlice([u8]);
+    }
+}
+
+fn connection() -> Connection {
+    let conn = Connection::open_in_memory().expect("Should succeed");
+    conn.execute_batch("CREATE TABLE t (id INTEGER PRIMARY KEY, v)")
+        .expect("Should succeed");
+    conn
+}
+
+#[test]
+fn roundtrip() {
+    use self::ident::{IdentStr, Identifier};
+
+    let conn = connection();
+    let ident = Identifier::new("foo_bar".to_owned()).expect("Should succeed");
+    let slice = IdentStr::new("baz").expect("Should succeed");
+    conn.execute(
+        "INSERT INTO t (id, v) VALUES (1, ?1), (2, ?2)",
+        params![ident, slice],
+    )
+    .expect("Should succeed");
+
+    let raw: String = conn
+        .query_row("SELECT v FROM t WHERE id = 1", [], |row| row.get(0))
+        .expect("Should succeed");
+    assert_eq!(raw, "foo_bar");
+    let v: Identifier = conn
+        .query_row("SELECT v FROM t WHERE id = 1", [], |row| row.get(0))
+        .expect("Should succeed");
+    assert_eq!(v, ident);
+    let v: Identifier = conn
+        .query_row("SELECT v FROM t WHERE id = 2", [], |row| row.get(0))
+        .expect("Should succeed");
+    assert_eq!(v.as_str(), slice.as_str());
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    use self::ident::{IdentStrError, Identifier};
+
+    let conn = connection();
+    conn.execute("INSERT INTO t (id, v) VALUES (1, 'foo-bar'), (2, 42)", [])
+        .expect("Should succeed");
+
+    let e = conn
+        .query_row("SELECT v FROM t WHERE id = 1", [], |row| {
+            row.get::<_, Identifier>(0)
+        })
+        .expect_err("Should fail");
+    match e {
+        rusqlite::Error::FromSqlConversionFailure(0, _, inner) => {
+            let inner = inner
+                .downcast_ref::<IdentStrError>()
+                .expect("Should be the validator error");
+            assert_eq!(inner.offset(), 3);
+        }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+
+    // Errors of the inner type are returned as is.
+    let e = conn
+        .query_row("SELECT v FROM t WHERE id = 2", [], |row| {
+            row.get::<_, Identifier>(0)
+        })
+        .expect_err("Should fail");
+    assert!(
+        matches!(e, rusqlite::Error::InvalidColumnType(..)),
+        "{:?}",
+        e
+    );
+}
+
+#[test]
+fn without_validator() {
+    use self::bytes::{Bytes, BytesSlice};
+
+    let conn = connection();
+    conn.execute(
+        "INSERT INTO t (id, v) VALUES (1, ?1), (2, ?2)",
+        params![Bytes::new(vec![0, 1, 255]), BytesSlice::new(&[])],
+    )
+    .expect("Should succeed");
+    let v: Bytes = conn
+        .query_row("SELECT v FROM t WHERE id = 1", [], |row| row.get(0))
+        .expect("Should succeed");
+    assert_eq!(v, Bytes::new(vec![0, 1, 255]));
+    let v: Bytes = conn
+        .query_row("SELECT v FROM t WHERE id = 2", [], |row| row.get(0))
+        .expect("Should succeed");
+    assert_eq!(v, Bytes::new(vec![]));
+}
@@ -0,0 +1,133 @@
+//! Validators implementing `custom_slice::Validator`.
+
+use custom_slice::{MaxLen, TooLongError, Validator};
+
+/// ASCII lowercase letters.
+pub enum AsciiLower {}
+
+impl Validator<str> for AsciiLower {
+    type Error = NotAsciiLower;
+
+    fn validate(s: &str) -> Result<(), Self::Error> {
+        if s.bytes().all(|b| b.is_ascii_lowercase()) {
+            Ok(())
+        } else {
+            Err(NotAsciiLower)
+        }
+    }
+}
+
+/// Error for `AsciiLower`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotAsciiLower;
+
+impl std::fmt::Display for NotAsciiLower {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not ASCII lowercase")
+    }
+}
+
+impl std::error::Error for NotAsciiLower {}
+
+mod ascii_lower {
+    use super::{AsciiLower, NotAsciiLower};
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// A lowercase word.
+        #[derive(Debug)]
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(generate = "WordError"))]
+        #[custom_slice(derive(Deref, TryFromInner))]
+        pub struct Word(String);
+
+        /// A lowercase word slice.
+        #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "NotAsciiLower"))]
+        #[custom_slice(derive(Deref, TryFromInner))]
+        #[custom_slice(validator(type = "AsciiLower"))]
+        pub struct WordStr(str);
+    }
+}
+
+/// Another pair sharing the same validator.
+mod ascii_lower_other {
+    use super::{AsciiLower, NotAsciiLower};
+
+    custom_slice_macros::define_slice_types_pair! {
+        #[custom_slice(owned)]
+        pub struct Tag(String);
+
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "NotAsciiLower"))]
+        #[custom_slice(validator(type = "AsciiLower"))]
+        pub struct TagStr(str);
+    }
+}
+
+mod max_len {
+    use custom_slice::{MaxLen, TooLongError};
+
+    custom_slice_macros::define_slice_types_pair! {
+        /// Short bytes.
+        #[custom_slice(owned)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "TooLongError", map = "{|e, _v| e}"))]
+        pub struct ShortBytes(Vec<u8>);
+
+        /// Short bytes slice.
+        #[repr(transparent)]
+        #[custom_slice(slice)]
+        #[custom_slice(new_checked = "pub fn new")]
+        #[custom_slice(error(type = "TooLongError"))]
+        #[custom_slice(validator(type = "MaxLen<4>"))]
+        pub struct ShortBytesSlice([u8]);
+    }
+}
+
+/// Generic code using the validator.
+fn is_valid<V: Validator<str>>(s: &str) -> bool {
+    V::validate(s).is_ok()
+}
+
+#[test]
+fn ascii_lower() {
+    use self::ascii_lower::{Word, WordError, WordStr};
+    use std::convert::TryFrom;
+
+    assert_eq!(
+        &**WordStr::new("foo").expect("Should never fail: valid"),
+        "foo"
+    );
+    assert_eq!(WordStr::new("Foo").unwrap_err(), NotAsciiLower);
+    assert_eq!(
+        &**Word::new("foo".to_owned()).expect("Should never fail: valid"),
+        "foo"
+    );
+    let e: WordError = Word::try_from("Foo".to_owned()).unwrap_err();
+    assert_eq!(e.error(), &NotAsciiLower);
+    assert_eq!(e.into_inner(), "Foo");
+    assert!(<&WordStr>::try_from("bar").is_ok());
+
+    assert!(ascii_lower_other::TagStr::new("foo").is_ok());
+    assert!(ascii_lower_other::TagStr::new("FOO").is_err());
+    assert!(is_valid::<AsciiLower>("foo"));
+}
+
+#[test]
+fn max_len() {
+    use self::max_len::{ShortBytes, ShortBytesSlice};
+
+    assert!(ShortBytesSlice::new(b"abcd").is_ok());
+    let e: TooLongError = ShortBytes::new(b"abcde".to_vec())
+        .err()
+        .expect("Should fail: too long");
+    assert_eq!((e.actual_len(), e.max_len()), (5, 4));
+    assert!(is_valid::<MaxLen<4>>("abcd"));
+    assert!(!is_valid::<MaxLen<4>>("abcde"));
+}
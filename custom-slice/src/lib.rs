@@ -0,0 +1,436 @@
+//! Runtime support for custom slice types defined by `custom-slice-macros`.
+//!
+//! Types implementing [`Validator`] can be used by
+//! `#[custom_slice(validator(type = "ValidatorType"))]`, and types implementing
+//! [`IncrementalValidator`] can be used by
+//! `#[custom_slice(incremental_validator = "ValidatorType")]`.
+//! [`FromBytes`] and [`AsBytes`] are implemented by `FromBytes` and `AsBytes`
+//! derive targets.
+//! [`OnceCell`] is used by generated regex validators.
+
+use std::{cell::UnsafeCell, error, ffi, fmt, mem::MaybeUninit, sync::Once};
+
+/// Validator for the inner type of custom slice types.
+///
+/// Validators are usually zero-sized types, and a single validator can be
+/// shared by many types and used by generic code.
+///
+/// # Examples
+///
+/// ```
+/// use custom_slice::Validator;
+///
+/// /// ASCII lowercase letters.
+/// enum AsciiLower {}
+///
+/// impl Validator<str> for AsciiLower {
+///     type Error = usize;
+///
+///     fn validate(s: &str) -> Result<(), Self::Error> {
+///         match s.bytes().position(|b| !b.is_ascii_lowercase()) {
+///             Some(pos) => Err(pos),
+///             None => Ok(()),
+///         }
+///     }
+/// }
+///
+/// assert!(AsciiLower::validate("foo").is_ok());
+/// assert_eq!(AsciiLower::validate("foO"), Err(2));
+/// ```
+pub trait Validator<Inner: ?Sized> {
+    /// Validation error.
+    type Error;
+
+    /// Validates the given value.
+    ///
+    /// Returns `Ok(())` for valid data, `Err(_)` for invalid data.
+    fn validate(inner: &Inner) -> Result<(), Self::Error>;
+}
+
+/// Validator which validates values appended to a valid value.
+///
+/// The validator state is created for each append operation, and the
+/// appended data is given by [`feed`][`Self::feed`] in one or more chunks.
+/// Chunk boundaries can be anywhere (for example, in the middle of a
+/// multi-byte character), so the state should keep incomplete data until the
+/// next chunk, and [`finish`][`Self::finish`] should reject it.
+///
+/// Validation of appended chunks should be consistent with the validator of
+/// the whole value, that is, the whole value after the append operation should
+/// pass the validator if and only if `feed` and `finish` succeed.
+///
+/// # Examples
+///
+/// ```
+/// use custom_slice::IncrementalValidator;
+///
+/// /// Validator for strings without spaces.
+/// struct NoSpaces;
+///
+/// impl IncrementalValidator<str> for NoSpaces {
+///     type Error = ();
+///
+///     fn start(_current: &str) -> Self {
+///         NoSpaces
+///     }
+///
+///     fn feed(&mut self, chunk: &str) -> Result<(), Self::Error> {
+///         if chunk.contains(' ') {
+///             Err(())
+///         } else {
+///             Ok(())
+///         }
+///     }
+///
+///     fn finish(self) -> Result<(), Self::Error> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut state = NoSpaces::start("foo");
+/// assert!(state.feed("bar").is_ok());
+/// assert!(state.feed("baz qux").is_err());
+/// ```
+pub trait IncrementalValidator<Inner: ?Sized>: Sized {
+    /// Validation error.
+    type Error;
+
+    /// Creates the validator state to append data to the given valid value.
+    fn start(current: &Inner) -> Self;
+
+    /// Validates the next chunk of the appended data.
+    fn feed(&mut self, chunk: &Inner) -> Result<(), Self::Error>;
+
+    /// Validates the end of the appended data.
+    fn finish(self) -> Result<(), Self::Error>;
+
+    /// Returns the length of the incomplete data at the end of the fed
+    /// chunks, such as a partial multi-byte character kept by the state.
+    ///
+    /// `std::io::Write` impls by `IoWrite` derive target append only the
+    /// data before it and report a partial write, so that the rest can be
+    /// written with the next chunk.
+    ///
+    /// The default implementation returns `0`.
+    fn pending_len(&self) -> usize {
+        0
+    }
+}
+
+/// Owned values which can be extended by the slice values.
+///
+/// This is used by the generated methods and impls to append the validated
+/// data, such as `push_checked` and `std::fmt::Write`.
+pub trait Append<Slice: ?Sized> {
+    /// Appends the given slice.
+    fn append(&mut self, slice: &Slice);
+}
+
+impl Append<str> for String {
+    fn append(&mut self, slice: &str) {
+        self.push_str(slice);
+    }
+}
+
+impl<T: Clone> Append<[T]> for Vec<T> {
+    fn append(&mut self, slice: &[T]) {
+        self.extend_from_slice(slice);
+    }
+}
+
+impl Append<ffi::OsStr> for ffi::OsString {
+    fn append(&mut self, slice: &ffi::OsStr) {
+        self.push(slice);
+    }
+}
+
+/// Checked conversion from bytes.
+///
+/// This is similar to `zerocopy::FromBytes`, but the value is checked by the
+/// validator.
+///
+/// # Examples
+///
+/// ```
+/// use custom_slice::{CastError, FromBytes};
+///
+/// /// UTF-8 string.
+/// #[repr(transparent)]
+/// struct Utf8Str(str);
+///
+/// impl FromBytes for Utf8Str {
+///     type Error = std::str::Utf8Error;
+///
+///     fn from_bytes(bytes: &[u8]) -> Result<&Self, CastError<Self::Error>> {
+///         let s = std::str::from_utf8(bytes).map_err(CastError::Invalid)?;
+///         Ok(unsafe { &*(s as *const str as *const Utf8Str) })
+///     }
+/// }
+///
+/// assert!(Utf8Str::from_bytes(b"foo").is_ok());
+/// assert!(matches!(
+///     Utf8Str::from_bytes(b"\xff"),
+///     Err(CastError::Invalid(_))
+/// ));
+/// ```
+pub trait FromBytes {
+    /// Validation error.
+    type Error;
+
+    /// Casts the bytes into the value, and validates it.
+    fn from_bytes(bytes: &[u8]) -> Result<&Self, CastError<Self::Error>>;
+}
+
+/// Conversion to bytes.
+///
+/// This is similar to `zerocopy::AsBytes`.
+pub trait AsBytes {
+    /// Returns the bytes of the value.
+    fn as_bytes(&self) -> &[u8];
+}
+
+/// Error of casting bytes into a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastError<E> {
+    /// The bytes are not aligned for the element type.
+    Misaligned,
+    /// The length of the bytes is not a multiple of the element size.
+    InvalidLength,
+    /// The value is rejected by the validator.
+    Invalid(E),
+}
+
+impl<E: fmt::Display> fmt::Display for CastError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastError::Misaligned => f.write_str("the bytes are not aligned for the element type"),
+            CastError::InvalidLength => {
+                f.write_str("the length of the bytes is not a multiple of the element size")
+            }
+            CastError::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: error::Error + 'static> error::Error for CastError<E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CastError::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Validator for the maximum length.
+///
+/// The length of `str` is counted in bytes, and the length of `[T]` is
+/// counted in elements.
+///
+/// # Examples
+///
+/// ```
+/// use custom_slice::{MaxLen, Validator};
+///
+/// assert!(<MaxLen<4> as Validator<str>>::validate("abcd").is_ok());
+/// assert!(<MaxLen<4> as Validator<str>>::validate("abcde").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaxLen<const N: usize> {}
+
+impl<const N: usize> Validator<str> for MaxLen<N> {
+    type Error = TooLongError;
+
+    fn validate(inner: &str) -> Result<(), Self::Error> {
+        TooLongError::check(inner.len(), N)
+    }
+}
+
+impl<T, const N: usize> Validator<[T]> for MaxLen<N> {
+    type Error = TooLongError;
+
+    fn validate(inner: &[T]) -> Result<(), Self::Error> {
+        TooLongError::check(inner.len(), N)
+    }
+}
+
+/// Incremental validator state for [`MaxLen`].
+///
+/// # Examples
+///
+/// ```
+/// use custom_slice::{IncrementalValidator, MaxLenState};
+///
+/// let mut state = <MaxLenState<4> as IncrementalValidator<str>>::start("ab");
+/// assert!(state.feed("c").is_ok());
+/// assert!(state.feed("de").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaxLenState<const N: usize> {
+    /// Length of the value, including the data fed so far.
+    len: usize,
+}
+
+impl<const N: usize> MaxLenState<N> {
+    /// Adds the length of the chunk.
+    fn feed_len(&mut self, len: usize) -> Result<(), TooLongError> {
+        self.len = self.len.saturating_add(len);
+        TooLongError::check(self.len, N)
+    }
+}
+
+impl<const N: usize> IncrementalValidator<str> for MaxLenState<N> {
+    type Error = TooLongError;
+
+    fn start(current: &str) -> Self {
+        Self { len: current.len() }
+    }
+
+    fn feed(&mut self, chunk: &str) -> Result<(), Self::Error> {
+        self.feed_len(chunk.len())
+    }
+
+    fn finish(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> IncrementalValidator<[T]> for MaxLenState<N> {
+    type Error = TooLongError;
+
+    fn start(current: &[T]) -> Self {
+        Self { len: current.len() }
+    }
+
+    fn feed(&mut self, chunk: &[T]) -> Result<(), Self::Error> {
+        self.feed_len(chunk.len())
+    }
+
+    fn finish(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Error for a value longer than the maximum length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TooLongError {
+    /// Length of the value.
+    len: usize,
+    /// Maximum length.
+    max: usize,
+}
+
+impl TooLongError {
+    /// Checks the length.
+    fn check(len: usize, max: usize) -> Result<(), Self> {
+        if len > max {
+            Err(Self { len, max })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the length of the value.
+    pub fn actual_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximum length.
+    pub fn max_len(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for TooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value has length {}, but the maximum length is {}",
+            self.len, self.max
+        )
+    }
+}
+
+impl error::Error for TooLongError {}
+
+/// Thread-safe cell which is initialized only once.
+///
+/// This is similar to `std::sync::OnceLock`, which requires Rust 1.70, and
+/// can be used for statics.
+///
+/// # Examples
+///
+/// ```
+/// use custom_slice::OnceCell;
+///
+/// static VALUE: OnceCell<String> = OnceCell::new();
+///
+/// assert_eq!(VALUE.get_or_init(|| "foo".to_owned()), "foo");
+/// assert_eq!(VALUE.get_or_init(|| "bar".to_owned()), "foo");
+/// ```
+pub struct OnceCell<T> {
+    /// Initialization state.
+    once: Once,
+    /// Value, initialized by `once`.
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// The value is shared between threads after the initialization, and it can
+// be initialized and dropped by different threads.
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+unsafe impl<T: Send> Send for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    /// Creates a new uninitialized cell.
+    pub const fn new() -> Self {
+        Self {
+            once: Once::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the value, initializing it by `f` if the cell is not
+    /// initialized yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` panics, or if an earlier initialization panicked.
+    pub fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> &T {
+        let value = self.value.get();
+        // The value is written only once, while other threads calling
+        // `call_once` are blocked.
+        self.once.call_once(|| unsafe {
+            (*value).as_mut_ptr().write(f());
+        });
+        // `call_once` returns only after the initialization has completed,
+        // and the value is never modified after that.
+        unsafe { &*(*value).as_ptr() }
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple("OnceCell");
+        if self.once.is_completed() {
+            // The value is initialized and never modified after that.
+            tuple.field(unsafe { &*(*self.value.get()).as_ptr() });
+        } else {
+            tuple.field(&format_args!("<uninit>"));
+        }
+        tuple.finish()
+    }
+}
+
+impl<T> Drop for OnceCell<T> {
+    fn drop(&mut self) {
+        if self.once.is_completed() {
+            // The value is initialized, and it is not accessed after this.
+            unsafe { self.value.get_mut().as_mut_ptr().drop_in_place() }
+        }
+    }
+}